@@ -0,0 +1,45 @@
+//! Compare the sequential and the parallel construction of Rubik-sized groups.
+//!
+//! Run with `cargo bench --features parallel`.
+extern crate permutation_rs;
+
+use permutation_rs::group::permutation::Permutation;
+use permutation_rs::group::Group;
+use permutation_rs::puzzle::{models, Puzzle};
+use std::time::{Duration, Instant};
+
+const RUNS: u32 = 5;
+
+fn main() {
+    for (name, puzzle) in [
+        ("2x2x2", models::cube2()),
+        ("pyraminx", models::pyraminx()),
+        ("3x3x3", models::cube3()),
+    ] {
+        let sequential = time(&puzzle, |gset, generators| {
+            Group::with_base(gset, vec![], generators)
+        });
+        let parallel = time(&puzzle, Group::new_parallel);
+        println!(
+            "{:10} sequential {:>8.1} ms  parallel {:>8.1} ms",
+            name,
+            millis(sequential),
+            millis(parallel)
+        );
+    }
+}
+
+fn time<F>(puzzle: &Puzzle, construct: F) -> Duration
+where
+    F: Fn(Vec<u64>, Vec<Permutation>) -> Group<u64, Permutation>,
+{
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        construct(puzzle.gset(), puzzle.generators());
+    }
+    start.elapsed() / RUNS
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
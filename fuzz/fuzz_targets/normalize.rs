@@ -0,0 +1,27 @@
+//! Free reduction must leave no zero exponents, no adjacent syllables with the
+//! same symbol, and must agree with multiplying the syllables one by one.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use permutation_rs::group::free::Word;
+use permutation_rs::group::GroupElement;
+
+fuzz_target!(|elements: Vec<(u8, i8)>| {
+    let elements: Vec<(u8, i64)> = elements
+        .into_iter()
+        .map(|(symbol, exponent)| (symbol % 4, exponent as i64))
+        .collect();
+
+    let word = Word::new(elements.clone());
+
+    let terms: Vec<&(u8, i64)> = word.iter().collect();
+    assert!(terms.iter().all(|(_, exponent)| *exponent != 0));
+    assert!(terms.windows(2).all(|pair| pair[0].0 != pair[1].0));
+    let product = elements
+        .into_iter()
+        .fold(Word::identity(), |product, syllable| {
+            product.times(&Word::new(vec![syllable]))
+        });
+    assert_eq!(product, word);
+    assert!(word.times(&word.inverse()).is_identity());
+});
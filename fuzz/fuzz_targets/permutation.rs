@@ -0,0 +1,16 @@
+//! Multiplication of permutations must be associative and inverses must
+//! cancel, also for permutations on different domains.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use permutation_rs::group::permutation::Permutation;
+use permutation_rs::group::testing::{assert_action_axioms, assert_group_axioms};
+
+fuzz_target!(|elements: (Permutation, Permutation, Permutation)| {
+    let (a, b, c) = elements;
+    let points: Vec<u64> = (0..66).collect();
+    let elements = vec![a, b, c];
+
+    assert_group_axioms(&elements);
+    assert_action_axioms(&elements, &points);
+});
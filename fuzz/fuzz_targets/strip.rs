@@ -0,0 +1,27 @@
+//! Every product of generators must strip to the identity, and stripping must
+//! not depend on whether the element is borrowed.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use permutation_rs::group::permutation::Permutation;
+use permutation_rs::group::tree::SLP;
+use permutation_rs::group::{Group, GroupElement};
+use std::collections::HashMap;
+
+fuzz_target!(|input: (Vec<Permutation>, SLP)| {
+    let (generators, slp) = input;
+    let generators: Vec<Permutation> = generators.into_iter().take(4).collect();
+    if generators.is_empty() {
+        return;
+    }
+    let degree = generators.iter().map(|g| g.degree()).max().unwrap_or(0) as u64;
+    let group = Group::with_base((0..degree).collect(), vec![], generators.clone());
+    let images: HashMap<u64, Permutation> = (0..16)
+        .map(|n| (n, generators[n as usize % generators.len()].clone()))
+        .collect();
+
+    let element = slp.evaluate_with(&images);
+
+    assert!(group.strip_ref(&element).is_identity());
+    assert_eq!(group.strip(element.clone()), group.strip_ref(&element));
+});
@@ -0,0 +1,193 @@
+//! Solve a scrambled permutation puzzle.
+//!
+//! ```text
+//! solve <puzzle> (--state <labels> | --scramble <moves>) [options]
+//! ```
+//!
+//! The puzzle is read from a definition file, see `permutation_rs::puzzle`. The
+//! scrambled puzzle is either given as a state, listing for every position the
+//! label of the sticker that occupies it, or as a sequence of moves.
+//!
+//! Options:
+//!
+//! * `--base <labels>|greedy` the base to build the group with, either a comma
+//!   separated list of sticker labels or a greedily determined small base.
+//! * `--reduce` reduce the exponent of each move modulo the order of that move.
+//! * `--format word|moves` print the solution as a word, e.g. `R^1U^-1`, or as
+//!   moves, e.g. `R U'`. Defaults to `moves`.
+extern crate permutation_rs;
+
+use permutation_rs::group::free::Word;
+use permutation_rs::group::permutation::Permutation;
+use permutation_rs::group::Group;
+use permutation_rs::puzzle::Puzzle;
+use std::collections::HashMap;
+use std::env;
+use std::process;
+
+enum Input {
+    State(String),
+    Scramble(String),
+}
+
+enum Base {
+    Automatic,
+    Greedy,
+    Labels(Vec<String>),
+}
+
+enum Format {
+    Word,
+    Moves,
+}
+
+struct Options {
+    puzzle: String,
+    input: Input,
+    base: Base,
+    reduce: bool,
+    format: Format,
+}
+
+fn main() {
+    let arguments: Vec<String> = env::args().skip(1).collect();
+    match options(&arguments).and_then(|options| solve(&options)) {
+        Ok(solution) => println!("{}", solution),
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!(
+                "usage: solve <puzzle> (--state <labels> | --scramble <moves>) \
+                 [--base <labels>|greedy] [--reduce] [--format word|moves]"
+            );
+            process::exit(1);
+        }
+    }
+}
+
+fn options(arguments: &[String]) -> Result<Options, String> {
+    let mut puzzle: Option<String> = None;
+    let mut input: Option<Input> = None;
+    let mut base = Base::Automatic;
+    let mut reduce = false;
+    let mut format = Format::Moves;
+    let mut index = 0;
+    while index < arguments.len() {
+        let argument = arguments[index].as_str();
+        let mut value = || {
+            index += 1;
+            arguments
+                .get(index)
+                .cloned()
+                .ok_or_else(|| format!("{} needs a value", argument))
+        };
+        match argument {
+            "--state" => input = Some(Input::State(value()?)),
+            "--scramble" => input = Some(Input::Scramble(value()?)),
+            "--base" => {
+                let labels = value()?;
+                base = if labels == "greedy" {
+                    Base::Greedy
+                } else {
+                    Base::Labels(labels.split(',').map(|l| l.trim().to_string()).collect())
+                };
+            }
+            "--reduce" => reduce = true,
+            "--format" => {
+                format = match value()?.as_str() {
+                    "word" => Format::Word,
+                    "moves" => Format::Moves,
+                    other => return Err(format!("unknown format '{}'", other)),
+                }
+            }
+            _ if argument.starts_with("--") => {
+                return Err(format!("unknown option '{}'", argument));
+            }
+            _ => puzzle = Some(argument.to_string()),
+        }
+        index += 1;
+    }
+    Ok(Options {
+        puzzle: puzzle.ok_or("missing puzzle definition")?,
+        input: input.ok_or("missing --state or --scramble")?,
+        base,
+        reduce,
+        format,
+    })
+}
+
+fn solve(options: &Options) -> Result<String, String> {
+    let puzzle = Puzzle::load(&options.puzzle).map_err(|error| error.to_string())?;
+    let scramble = match options.input {
+        Input::State(ref labels) => state(&puzzle, labels)?,
+        Input::Scramble(ref moves) => scramble(&puzzle, moves)?,
+    };
+    let base = match options.base {
+        Base::Automatic => vec![],
+        Base::Greedy => {
+            Group::with_base(puzzle.gset(), vec![], puzzle.generators()).greedy_minimal_base()
+        }
+        Base::Labels(ref labels) => labels
+            .iter()
+            .map(|label| {
+                puzzle
+                    .sticker(label)
+                    .ok_or_else(|| format!("unknown sticker '{}'", label))
+            })
+            .collect::<Result<Vec<u64>, String>>()?,
+    };
+    let group = Group::with_base(puzzle.gset(), base, puzzle.slp_generators());
+
+    let mut solution = group
+        .solve_verified(&scramble, &puzzle.morphism(), &puzzle.move_images())
+        .map_err(|error| error.to_string())?
+        .word;
+    if options.reduce {
+        solution = reduce(&puzzle, &solution);
+    }
+    Ok(match options.format {
+        Format::Word => format!("{}", solution),
+        Format::Moves => solution.format_with(&puzzle.notation()),
+    })
+}
+
+fn state(puzzle: &Puzzle, labels: &str) -> Result<Permutation, String> {
+    let labels: Vec<&str> = labels.split_whitespace().collect();
+    if labels.len() != puzzle.stickers().len() {
+        return Err(format!(
+            "state should have {} stickers",
+            puzzle.stickers().len()
+        ));
+    }
+    let mut images: HashMap<u64, u64> = HashMap::new();
+    for (position, label) in labels.iter().enumerate() {
+        let sticker = puzzle
+            .sticker(label)
+            .ok_or_else(|| format!("unknown sticker '{}'", label))?;
+        if images.insert(sticker, position as u64).is_some() {
+            return Err(format!("sticker '{}' occurs more than once", label));
+        }
+    }
+    Ok(Permutation::new(images))
+}
+
+fn scramble(puzzle: &Puzzle, moves: &str) -> Result<Permutation, String> {
+    puzzle
+        .sequence(moves)
+        .and_then(|sequence| sequence.permutation(puzzle))
+        .map_err(|error| error.to_string())
+}
+
+fn reduce(puzzle: &Puzzle, word: &Word<String>) -> Word<String> {
+    let terms: Vec<(String, i64)> = word
+        .iter()
+        .map(|(name, exponent)| {
+            let order = puzzle.move_named(name).expect("move should exist").order() as i64;
+            let mut reduced = exponent.rem_euclid(order);
+            if 2 * reduced > order {
+                reduced -= order;
+            }
+            (name.clone(), reduced)
+        })
+        .collect();
+    Word::new(terms)
+}
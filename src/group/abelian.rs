@@ -0,0 +1,235 @@
+//! The abelianization _G_/_G'_ of a group.
+//!
+//! The derived subgroup _G'_ is the normal closure of the commutators of the
+//! generators. The quotient _G_/_G'_ is the largest abelian quotient of _G_. Its
+//! structure follows from the relations between the images of the generators,
+//! brought in [Smith normal form](https://en.wikipedia.org/wiki/Smith_normal_form).
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let s4 = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation]);
+//!
+//! assert_eq!(s4.abelian_invariants(), vec![2]);
+//! # }
+//! ```
+
+use super::{Group, GroupAction, GroupElement};
+use std::hash::Hash;
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// Determine if all elements of this group commute.
+    pub fn is_abelian(&self) -> bool {
+        let generators = self.generators();
+        generators.iter().enumerate().all(|(index, g)| {
+            generators[(index + 1)..]
+                .iter()
+                .all(|h| g.times(h) == h.times(g))
+        })
+    }
+
+    /// The smallest normal subgroup of this group that contains `elements`.
+    pub fn normal_closure(&self, elements: Vec<G>) -> Group<Domain, G> {
+        let generators = self.generators();
+        let mut closure_generators: Vec<G> = elements
+            .into_iter()
+            .filter(|element| !element.is_identity())
+            .collect();
+        let mut closure = Group::with_base(self.gset.clone(), vec![], closure_generators.clone());
+        let mut index = 0;
+        while index < closure_generators.len() {
+            let element = closure_generators[index].clone();
+            for g in &generators {
+                let conjugate = element.conjugate_by(g);
+                if !closure.contains(&conjugate) {
+                    closure_generators.push(conjugate);
+                    closure =
+                        Group::with_base(self.gset.clone(), vec![], closure_generators.clone());
+                }
+            }
+            index += 1;
+        }
+        closure
+    }
+
+    /// The derived subgroup, generated by all commutators of this group.
+    pub fn derived_subgroup(&self) -> Group<Domain, G> {
+        let generators = self.generators();
+        let mut commutators = vec![];
+        for (index, g) in generators.iter().enumerate() {
+            for h in &generators[(index + 1)..] {
+                commutators.push(g.commutator(h));
+            }
+        }
+        self.normal_closure(commutators)
+    }
+
+    /// The abelian invariants of this group, i.e. the orders of the cyclic
+    /// factors of _G_/_G'_, each dividing the next.
+    ///
+    /// The quotient is enumerated element by element, so this is meant for
+    /// groups with a small abelianization.
+    pub fn abelian_invariants(&self) -> Vec<u64> {
+        let generators = self.generators();
+        let derived = self.derived_subgroup();
+        let rank = generators.len();
+
+        let mut representatives: Vec<(Vec<i64>, G)> = vec![(vec![0; rank], G::identity())];
+        let mut relations: Vec<Vec<i64>> = vec![];
+        let mut index = 0;
+        while index < representatives.len() {
+            for (generator_index, generator) in generators.iter().enumerate() {
+                let (ref vector, ref element) = representatives[index];
+                let mut next_vector = vector.clone();
+                next_vector[generator_index] += 1;
+                let next = element.times(generator);
+                let inverse = next.inverse();
+                match representatives
+                    .iter()
+                    .find(|(_, candidate)| derived.contains(&candidate.times(&inverse)))
+                {
+                    Some((known, _)) => {
+                        let relation: Vec<i64> =
+                            next_vector.iter().zip(known).map(|(a, b)| a - b).collect();
+                        if relation.iter().any(|&entry| entry != 0) {
+                            relations.push(relation);
+                        }
+                    }
+                    None => representatives.push((next_vector, next)),
+                }
+            }
+            index += 1;
+        }
+
+        smith_normal_form(relations, rank)
+            .into_iter()
+            .filter(|&d| d != 1)
+            .collect()
+    }
+}
+
+/// The non-zero diagonal of the Smith normal form of the matrix with `rows`,
+/// each of length `columns`, followed by a 0 for every missing pivot.
+fn smith_normal_form(mut rows: Vec<Vec<i64>>, columns: usize) -> Vec<u64> {
+    let mut diagonal: Vec<u64> = vec![];
+    let mut column = 0;
+    while column < columns {
+        let pivot = (0..rows.len())
+            .flat_map(|r| (column..columns).map(move |c| (r, c)))
+            .filter(|&(r, c)| rows[r][c] != 0)
+            .min_by_key(|&(r, c)| rows[r][c].abs());
+        let (pivot_row, pivot_column) = match pivot {
+            Some(position) => position,
+            None => {
+                diagonal.extend(vec![0; columns - column]);
+                break;
+            }
+        };
+        rows.swap(0, pivot_row);
+        for row in rows.iter_mut() {
+            row.swap(column, pivot_column);
+        }
+        let pivot = rows[0][column];
+
+        let mut reduced = true;
+        let (head, tail) = rows.split_at_mut(1);
+        for row in tail.iter_mut() {
+            let factor = row[column] / pivot;
+            for (entry, pivot_entry) in row[column..].iter_mut().zip(&head[0][column..]) {
+                *entry -= factor * pivot_entry;
+            }
+            reduced &= row[column] == 0;
+        }
+        for c in (column + 1)..columns {
+            let factor = rows[0][c] / pivot;
+            for row in rows.iter_mut() {
+                row[c] -= factor * row[column];
+            }
+            reduced &= rows[0][c] == 0;
+        }
+        if !reduced {
+            continue;
+        }
+        if let Some(r) =
+            (1..rows.len()).find(|&r| ((column + 1)..columns).any(|c| rows[r][c] % pivot != 0))
+        {
+            let (head, tail) = rows.split_at_mut(1);
+            for (entry, other) in head[0][column..].iter_mut().zip(&tail[r - 1][column..]) {
+                *entry += other;
+            }
+            continue;
+        }
+        diagonal.push(pivot.unsigned_abs());
+        rows.remove(0);
+        column += 1;
+    }
+    diagonal.sort_by(|a, b| match (a, b) {
+        (0, 0) => std::cmp::Ordering::Equal,
+        (0, _) => std::cmp::Ordering::Greater,
+        (_, 0) => std::cmp::Ordering::Less,
+        _ => a.cmp(b),
+    });
+    diagonal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::permutation::Permutation;
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn smith_normal_form_should_diagonalize() {
+        let rows = vec![vec![2, 4, 4], vec![-6, 6, 12], vec![10, -4, -16]];
+
+        assert_eq!(smith_normal_form(rows, 3), vec![2, 6, 12]);
+    }
+
+    #[test]
+    fn cyclic_group_should_be_its_own_abelianization() {
+        let rotation = permute!(0, 1, 1, 2, 2, 0, 3, 4, 4, 3);
+        let group = Group::with_base(vec![0, 1, 2, 3, 4], vec![], vec![rotation]);
+
+        assert_eq!(group.abelian_invariants(), vec![6]);
+    }
+
+    #[test]
+    fn abelian_invariants_should_split_products() {
+        let a = permute!(0, 1, 1, 0, 2, 2, 3, 3, 4, 4, 5, 5);
+        let b = permute!(0, 0, 1, 1, 2, 3, 3, 4, 4, 5, 5, 2);
+        let c = permute!(0, 1, 1, 0, 2, 4, 3, 5, 4, 2, 5, 3);
+        let group = Group::with_base(vec![0, 1, 2, 3, 4, 5], vec![], vec![a, b, c]);
+
+        assert_eq!(group.abelian_invariants(), vec![2, 4]);
+    }
+
+    #[test]
+    fn only_commuting_generators_should_be_abelian() {
+        let transposition = permute!(0, 1, 1, 0, 2, 2);
+        let rotation = permute!(0, 1, 1, 2, 2, 0);
+        let cyclic = Group::with_base(vec![0, 1, 2], vec![], vec![rotation.clone()]);
+        let symmetric = Group::with_base(vec![0, 1, 2], vec![], vec![transposition, rotation]);
+
+        assert!(cyclic.is_abelian());
+        assert!(!symmetric.is_abelian());
+    }
+
+    #[test]
+    fn derived_subgroup_of_symmetric_group_should_be_alternating() {
+        let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+        let group = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation]);
+
+        assert_eq!(group.derived_subgroup().order(), 12);
+    }
+}
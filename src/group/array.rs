@@ -0,0 +1,204 @@
+//! Permutations of a fixed number of points, without heap allocation.
+//!
+//! An `ArrayPermutation<N>` permutes the points `0..N` and stores its images in
+//! an array. It is `Copy`, so it suits the hot loops of a search on a puzzle of
+//! fixed size, e.g. the 48 moving stickers of the 3x3x3 cube. Points beyond `N`
+//! are fixed.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use std::convert::TryFrom;
+//! # use permutation_rs::group::{GroupAction, GroupElement};
+//! # use permutation_rs::group::array::ArrayPermutation;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let rotation = permute!(0, 1, 1, 2, 2, 0);
+//! let fixed = ArrayPermutation::<3>::try_from(&rotation).unwrap();
+//!
+//! assert_eq!(fixed.act_on(&0), 1);
+//! assert!(fixed.times(&fixed).times(&fixed).is_identity());
+//! assert_eq!(Permutation::from(fixed), rotation);
+//! # }
+//! ```
+
+use super::permutation::{Permutation, PermutationError};
+use super::{GroupAction, GroupElement};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::fmt::Display;
+
+/// A permutation of the points `0..N`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArrayPermutation<const N: usize> {
+    images: [u16; N],
+}
+
+impl<const N: usize> ArrayPermutation<N> {
+    /// The identity permutation.
+    pub fn identity() -> ArrayPermutation<N> {
+        let mut images = [0u16; N];
+        for (point, image) in images.iter_mut().enumerate() {
+            *image = point as u16;
+        }
+        ArrayPermutation { images }
+    }
+
+    /// Create a permutation that maps point `i` to `images[i]`, provided the
+    /// images are a permutation of `0..N`.
+    pub fn try_new(images: [u16; N]) -> Result<ArrayPermutation<N>, PermutationError> {
+        let mut seen = [false; N];
+        for image in images.iter() {
+            let index = *image as usize;
+            if index >= N {
+                return Err(PermutationError::ImageOutsideDomain(*image as u64));
+            }
+            if seen[index] {
+                return Err(PermutationError::DuplicateImage(*image as u64));
+            }
+            seen[index] = true;
+        }
+        Ok(ArrayPermutation { images })
+    }
+
+    /// The images of the points `0..N`.
+    pub fn images(&self) -> &[u16; N] {
+        &self.images
+    }
+}
+
+impl<const N: usize> GroupElement for ArrayPermutation<N> {
+    fn identity() -> ArrayPermutation<N> {
+        ArrayPermutation::identity()
+    }
+
+    fn is_identity(&self) -> bool {
+        self.images
+            .iter()
+            .enumerate()
+            .all(|(point, image)| point == *image as usize)
+    }
+
+    fn times(&self, multiplicant: &ArrayPermutation<N>) -> ArrayPermutation<N> {
+        let mut images = [0u16; N];
+        for (image, own) in images.iter_mut().zip(self.images.iter()) {
+            *image = multiplicant.images[*own as usize];
+        }
+        ArrayPermutation { images }
+    }
+
+    fn inverse(&self) -> ArrayPermutation<N> {
+        let mut images = [0u16; N];
+        for (point, image) in self.images.iter().enumerate() {
+            images[*image as usize] = point as u16;
+        }
+        ArrayPermutation { images }
+    }
+}
+
+impl<const N: usize> GroupAction for ArrayPermutation<N> {
+    type Domain = u64;
+
+    fn act_on(&self, original: &u64) -> u64 {
+        match self.images.get(*original as usize) {
+            Some(image) => *image as u64,
+            None => *original,
+        }
+    }
+}
+
+impl<const N: usize> From<ArrayPermutation<N>> for Permutation {
+    fn from(permutation: ArrayPermutation<N>) -> Permutation {
+        let images: HashMap<u64, u64> = permutation
+            .images
+            .iter()
+            .enumerate()
+            .map(|(point, image)| (point as u64, *image as u64))
+            .collect();
+        Permutation::new_unchecked(images)
+    }
+}
+
+impl<'a, const N: usize> TryFrom<&'a Permutation> for ArrayPermutation<N> {
+    type Error = PermutationError;
+
+    /// Fails when `permutation` moves a point beyond `N`.
+    fn try_from(permutation: &'a Permutation) -> Result<ArrayPermutation<N>, PermutationError> {
+        if let Some(point) = permutation
+            .moved_points()
+            .into_iter()
+            .find(|point| *point as usize >= N)
+        {
+            return Err(PermutationError::ImageOutsideDomain(point));
+        }
+        let mut images = [0u16; N];
+        for (point, image) in images.iter_mut().enumerate() {
+            *image = permutation.act_on(&(point as u64)) as u16;
+        }
+        Ok(ArrayPermutation { images })
+    }
+}
+
+impl<const N: usize> Display for ArrayPermutation<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", Permutation::from(*self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::models;
+    use super::*;
+
+    #[test]
+    fn cube_moves_should_convert_back_and_forth() {
+        let puzzle = models::cube3();
+        for permutation in puzzle.generators() {
+            let fixed = ArrayPermutation::<48>::try_from(&permutation).unwrap();
+
+            assert_eq!(Permutation::from(fixed), permutation);
+        }
+    }
+
+    #[test]
+    fn products_should_agree_with_permutation() {
+        let puzzle = models::cube3();
+        let r = puzzle.move_named("R").unwrap();
+        let u = puzzle.move_named("U").unwrap();
+        let fixed_r = ArrayPermutation::<48>::try_from(r).unwrap();
+        let fixed_u = ArrayPermutation::<48>::try_from(u).unwrap();
+
+        let product = fixed_r.times(&fixed_u.inverse());
+
+        assert_eq!(Permutation::from(product), r.times(&u.inverse()));
+        let mut power = product;
+        let mut order = 1;
+        while !power.is_identity() {
+            power = power.times(&product);
+            order += 1;
+        }
+        assert_eq!(order, 63);
+    }
+
+    #[test]
+    fn try_new_should_reject_non_bijections() {
+        assert_eq!(
+            ArrayPermutation::try_new([0u16, 0u16]),
+            Err(PermutationError::DuplicateImage(0))
+        );
+        assert_eq!(
+            ArrayPermutation::try_new([0u16, 2u16]),
+            Err(PermutationError::ImageOutsideDomain(2))
+        );
+    }
+
+    #[test]
+    fn points_beyond_size_should_not_convert() {
+        let swap = permute!(3u64, 4u64, 4u64, 3u64);
+
+        assert!(ArrayPermutation::<4>::try_from(&swap).is_err());
+        assert!(ArrayPermutation::<5>::try_from(&swap).is_ok());
+    }
+}
@@ -0,0 +1,307 @@
+//! The automorphism group of a graph.
+//!
+//! An automorphism of a graph is a permutation of its vertices that maps edges
+//! onto edges. The symmetries of a puzzle, e.g. the rotations of a cube, are
+//! automorphisms of the graph of its pieces.
+//!
+//! The automorphisms are found with partition refinement, in the spirit of
+//! [nauty](https://pallini.di.uniroma1.it/). The vertices are colored, and the
+//! colors are refined until vertices of the same color have the same number of
+//! neighbours of every color. A vertex of the first color class with more than
+//! one vertex is then individualized, i.e. given a color of its own, and the
+//! refinement is repeated. Following the first vertex every time leads to a
+//! discrete coloring, the reference leaf. Every other leaf with the same colors
+//! defines a permutation that may be an automorphism. Automorphisms that are
+//! already known prune the vertices that need to be tried.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::group::automorphism::graph_automorphisms;
+//! let pentagon = vec![vec![1, 4], vec![0, 2], vec![1, 3], vec![2, 4], vec![3, 0]];
+//!
+//! let group = graph_automorphisms(&pentagon);
+//!
+//! assert_eq!(group.order(), 10);
+//! ```
+
+use super::permutation::Permutation;
+use super::{Group, GroupAction};
+use std::collections::{HashMap, HashSet};
+
+/// The automorphism group of the directed graph with vertices 0..n, where
+/// `adjacency[u]` lists the vertices with an edge from `u`. List each edge in
+/// both directions for an undirected graph.
+pub fn graph_automorphisms(adjacency: &[Vec<usize>]) -> Group<u64, Permutation> {
+    colored_graph_automorphisms(adjacency, &vec![0; adjacency.len()])
+}
+
+/// The automorphisms of a graph that also preserve the `colors` of the
+/// vertices.
+///
+/// Panics when there is not a color for every vertex.
+pub fn colored_graph_automorphisms(
+    adjacency: &[Vec<usize>],
+    colors: &[usize],
+) -> Group<u64, Permutation> {
+    assert_eq!(adjacency.len(), colors.len(), "every vertex needs a color");
+    let graph = Graph::new(adjacency);
+    let gset: Vec<u64> = (0..adjacency.len() as u64).collect();
+
+    let mut reference = vec![graph.refine(ranks(colors))];
+    let mut path: Vec<usize> = vec![];
+    while let Some(cell) = target_cell(reference.last().expect("root coloring")) {
+        let vertex = cell[0];
+        let next = graph.individualize(reference.last().expect("root coloring"), vertex);
+        path.push(vertex);
+        reference.push(next);
+    }
+
+    let mut generators: Vec<Permutation> = vec![];
+    for depth in (0..path.len()).rev() {
+        let vertex = path[depth];
+        let prefix = &path[0..depth];
+        let cell = target_cell(&reference[depth]).expect("cell on reference path");
+        for candidate in cell {
+            let stabilizer: Vec<&Permutation> = generators
+                .iter()
+                .filter(|g| prefix.iter().all(|&p| g.act_on(&(p as u64)) == p as u64))
+                .collect();
+            if orbit(vertex, &stabilizer).contains(&candidate) {
+                continue;
+            }
+            let next = graph.individualize(&reference[depth], candidate);
+            if shape(&next) != shape(&reference[depth + 1]) {
+                continue;
+            }
+            if let Some(automorphism) = graph.search(&reference, next, depth + 1) {
+                generators.push(automorphism);
+            }
+        }
+    }
+    Group::with_base(gset, vec![], generators)
+}
+
+/// A directed graph with its edges indexed in both directions.
+struct Graph {
+    outgoing: Vec<Vec<usize>>,
+    incoming: Vec<Vec<usize>>,
+    edges: HashSet<(usize, usize)>,
+}
+
+impl Graph {
+    fn new(adjacency: &[Vec<usize>]) -> Graph {
+        let mut incoming: Vec<Vec<usize>> = vec![vec![]; adjacency.len()];
+        let mut edges = HashSet::new();
+        for (u, neighbours) in adjacency.iter().enumerate() {
+            for &v in neighbours {
+                incoming[v].push(u);
+                edges.insert((u, v));
+            }
+        }
+        Graph {
+            outgoing: adjacency.to_vec(),
+            incoming,
+            edges,
+        }
+    }
+
+    /// Refine `colors` until vertices of the same color have the same number
+    /// of neighbours of every color, in either direction.
+    fn refine(&self, mut colors: Vec<usize>) -> Vec<usize> {
+        loop {
+            let signatures: Vec<(usize, Vec<usize>, Vec<usize>)> = (0..colors.len())
+                .map(|u| {
+                    let mut outgoing: Vec<usize> =
+                        self.outgoing[u].iter().map(|&v| colors[v]).collect();
+                    let mut incoming: Vec<usize> =
+                        self.incoming[u].iter().map(|&v| colors[v]).collect();
+                    outgoing.sort_unstable();
+                    incoming.sort_unstable();
+                    (colors[u], outgoing, incoming)
+                })
+                .collect();
+            let refined = ranks(&signatures);
+            if count(&refined) == count(&colors) {
+                return refined;
+            }
+            colors = refined;
+        }
+    }
+
+    /// Give `vertex` a color of its own and refine.
+    fn individualize(&self, colors: &[usize], vertex: usize) -> Vec<usize> {
+        let split: Vec<(usize, bool)> = colors
+            .iter()
+            .enumerate()
+            .map(|(u, &color)| (color, u != vertex))
+            .collect();
+        self.refine(ranks(&split))
+    }
+
+    /// An automorphism that maps the leaf of `reference` to a leaf below
+    /// `colors`, the coloring at `depth`.
+    fn search(
+        &self,
+        reference: &[Vec<usize>],
+        colors: Vec<usize>,
+        depth: usize,
+    ) -> Option<Permutation> {
+        let cell = match target_cell(&colors) {
+            Some(cell) => cell,
+            None => return self.automorphism(reference.last().expect("leaf"), &colors),
+        };
+        for vertex in cell {
+            let next = self.individualize(&colors, vertex);
+            if shape(&next) != shape(&reference[depth + 1]) {
+                continue;
+            }
+            let found = self.search(reference, next, depth + 1);
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+
+    /// The permutation that maps every vertex of the discrete coloring `from`
+    /// to the vertex with the same color in `to`, if it is an automorphism.
+    fn automorphism(&self, from: &[usize], to: &[usize]) -> Option<Permutation> {
+        let mut vertex_of_color = vec![0; to.len()];
+        for (vertex, &color) in to.iter().enumerate() {
+            vertex_of_color[color] = vertex;
+        }
+        let image: Vec<usize> = from.iter().map(|&color| vertex_of_color[color]).collect();
+        if self
+            .edges
+            .iter()
+            .all(|&(u, v)| self.edges.contains(&(image[u], image[v])))
+        {
+            let images: HashMap<u64, u64> = image
+                .iter()
+                .enumerate()
+                .map(|(u, &v)| (u as u64, v as u64))
+                .collect();
+            Some(Permutation::new(images))
+        } else {
+            None
+        }
+    }
+}
+
+/// Replace every value by its rank among the distinct values.
+fn ranks<T>(values: &[T]) -> Vec<usize>
+where
+    T: Ord + Clone,
+{
+    let mut distinct: Vec<T> = values.to_vec();
+    distinct.sort();
+    distinct.dedup();
+    values
+        .iter()
+        .map(|value| distinct.binary_search(value).expect("value is present"))
+        .collect()
+}
+
+/// The number of distinct colors.
+fn count(colors: &[usize]) -> usize {
+    colors.iter().max().map_or(0, |&max| max + 1)
+}
+
+/// The number of vertices of each color, which is the same for colorings that
+/// an automorphism maps onto each other.
+fn shape(colors: &[usize]) -> Vec<usize> {
+    let mut sizes = vec![0; count(colors)];
+    for &color in colors {
+        sizes[color] += 1;
+    }
+    sizes
+}
+
+/// The vertices of the first color with more than one vertex.
+fn target_cell(colors: &[usize]) -> Option<Vec<usize>> {
+    let sizes = shape(colors);
+    let color = sizes.iter().position(|&size| size > 1)?;
+    Some(
+        colors
+            .iter()
+            .enumerate()
+            .filter(|&(_, &c)| c == color)
+            .map(|(vertex, _)| vertex)
+            .collect(),
+    )
+}
+
+/// The vertices `vertex` is mapped to by products of `generators`.
+fn orbit(vertex: usize, generators: &[&Permutation]) -> HashSet<usize> {
+    let mut result: HashSet<usize> = HashSet::new();
+    result.insert(vertex);
+    let mut frontier = vec![vertex];
+    while let Some(current) = frontier.pop() {
+        for generator in generators {
+            let image = generator.act_on(&(current as u64)) as usize;
+            if result.insert(image) {
+                frontier.push(image);
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+        let mut adjacency = vec![vec![]; n];
+        for &(u, v) in edges {
+            adjacency[u].push(v);
+            adjacency[v].push(u);
+        }
+        adjacency
+    }
+
+    #[test]
+    fn cube_graph_should_have_the_octahedral_group() {
+        let edges: Vec<(usize, usize)> = (0..8usize)
+            .flat_map(|u| (0..3).map(move |bit| (u, u ^ (1 << bit))))
+            .filter(|&(u, v)| u < v)
+            .collect();
+
+        let group = graph_automorphisms(&undirected(8, &edges));
+
+        assert_eq!(group.order(), 48);
+    }
+
+    #[test]
+    fn graphs_without_structure_should_be_symmetric() {
+        let complete = undirected(4, &[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+        let empty = vec![vec![]; 4];
+
+        assert_eq!(graph_automorphisms(&complete).order(), 24);
+        assert_eq!(graph_automorphisms(&empty).order(), 24);
+    }
+
+    #[test]
+    fn directions_and_colors_should_be_preserved() {
+        let directed = vec![vec![1], vec![2], vec![3], vec![4], vec![0]];
+        let pentagon = undirected(5, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+
+        assert_eq!(graph_automorphisms(&directed).order(), 5);
+        assert_eq!(
+            colored_graph_automorphisms(&pentagon, &[1, 0, 0, 0, 0]).order(),
+            2
+        );
+    }
+
+    #[test]
+    fn petersen_graph_should_have_120_automorphisms() {
+        let mut edges = vec![];
+        for i in 0..5 {
+            edges.push((i, (i + 1) % 5));
+            edges.push((i, i + 5));
+            edges.push((i + 5, (i + 2) % 5 + 5));
+        }
+
+        assert_eq!(graph_automorphisms(&undirected(10, &edges)).order(), 120);
+    }
+}
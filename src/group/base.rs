@@ -0,0 +1,110 @@
+//! Describe group elements by the images of the base.
+//!
+//! Only the identity fixes every base point, so an element of a group is
+//! determined by the images of the base points. Those images are a compact
+//! description of the element, and the starting point of backtrack searches.
+//! `Group::element_from_base_images` reconstructs the element, one
+//! transversal per level.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let group = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation.clone()]);
+//!
+//! let images = group.base_images(&rotation);
+//!
+//! assert_eq!(group.element_from_base_images(&images), Some(rotation));
+//! # }
+//! ```
+
+use super::{Group, GroupAction, GroupElement};
+use std::hash::Hash;
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
+{
+    /// The base points of the stabilizer chain, one for each level.
+    pub fn base(&self) -> Vec<Domain> {
+        self.levels
+            .iter()
+            .map(|level| level.orbit.base.clone())
+            .collect()
+    }
+
+    /// The images of the base points under `element`.
+    pub fn base_images(&self, element: &G) -> Vec<Domain> {
+        self.levels
+            .iter()
+            .map(|level| element.act_on(&level.orbit.base))
+            .collect()
+    }
+
+    /// The unique element of this group that maps the base to `images`, or
+    /// `None` when there is no such element.
+    pub fn element_from_base_images(&self, images: &[Domain]) -> Option<G> {
+        if images.len() != self.levels.len() {
+            return None;
+        }
+        let mut points: Vec<Domain> = images.to_vec();
+        let mut transversals: Vec<G> = vec![];
+        for (index, level) in self.levels.iter().enumerate() {
+            let transversal = level.orbit.representative(&points[index])?;
+            let inverse = transversal.inverse();
+            for point in points[(index + 1)..].iter_mut() {
+                *point = inverse.act_on(point);
+            }
+            transversals.push(transversal);
+        }
+        Some(
+            transversals
+                .into_iter()
+                .rev()
+                .fold(G::identity(), |product, transversal| {
+                    product.times(&transversal)
+                }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::models;
+    use super::super::permutation::Permutation;
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn elements_should_be_reconstructed_from_their_base_images() {
+        let puzzle = models::cube2();
+        let group = Group::with_base(puzzle.gset(), vec![], puzzle.generators());
+        let element = puzzle
+            .sequence("R U2 F' R U'")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+
+        let images = group.base_images(&element);
+
+        assert_eq!(images.len(), group.base().len());
+        assert_eq!(group.element_from_base_images(&images), Some(element));
+    }
+
+    #[test]
+    fn impossible_images_should_have_no_element() {
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+        let group = Group::with_base(vec![0, 1, 2, 3], vec![], vec![rotation]);
+
+        assert_eq!(group.base().len(), 1);
+        assert!(group.element_from_base_images(&[2]).is_some());
+        assert_eq!(group.element_from_base_images(&[7]), None);
+        assert_eq!(group.element_from_base_images(&[1, 2]), None);
+    }
+}
@@ -0,0 +1,270 @@
+//! Transitivity, primitivity and block systems.
+//!
+//! A block is a subset of the gset that every element of the group maps either
+//! onto itself or onto a disjoint subset. The images of a block partition the
+//! gset into a block system. A transitive group with no block systems besides
+//! the trivial ones, singletons and the whole gset, is called primitive.
+//!
+//! The smallest block that contains two points is found by merging classes of
+//! points, as described by Atkinson.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let square = Group::with_base(vec![0, 1, 2, 3], vec![], vec![rotation]);
+//!
+//! assert!(square.is_transitive(&[0, 1, 2, 3]));
+//! assert!(!square.is_primitive(&[0, 1, 2, 3]));
+//! assert_eq!(square.minimal_block_system(), Some(vec![vec![0, 2], vec![1, 3]]));
+//!
+//! let blocks = square.minimal_block_system().unwrap();
+//! assert_eq!(square.action_on_blocks(&blocks).order(), 2);
+//! # }
+//! ```
+
+use super::orbit::Orbit;
+use super::permutation::Permutation;
+use super::{Group, GroupAction, GroupElement};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// Determine if this group maps every point of `gset` to every other point
+    /// of `gset`, and nothing else.
+    pub fn is_transitive(&self, gset: &[Domain]) -> bool {
+        match gset.first() {
+            Some(start) => {
+                let orbit = Orbit::new(start.clone(), self.generators());
+                orbit.len() == gset.len() && gset.iter().all(|point| orbit.contains(point))
+            }
+            None => true,
+        }
+    }
+
+    /// Determine if this group is transitive on `gset` and has no non trivial
+    /// block system on it.
+    pub fn is_primitive(&self, gset: &[Domain]) -> bool {
+        self.is_transitive(gset) && minimal_blocks(gset, &self.generators()).is_none()
+    }
+
+    /// A non trivial block system of this transitive group with blocks as small
+    /// as possible, or `None` when the group is primitive.
+    ///
+    /// Blocks and the points in them follow the order of the gset.
+    pub fn minimal_block_system(&self) -> Option<Vec<Vec<Domain>>> {
+        minimal_blocks(&self.gset, &self.generators())
+    }
+
+    /// The non trivial block systems of this transitive group in which the block
+    /// of the first point is the smallest block containing it and one other
+    /// point. Every system occurs once, ordered by increasing block size.
+    pub fn block_systems(&self) -> Vec<Vec<Vec<Domain>>> {
+        let generators = self.generators();
+        let mut systems: Vec<Vec<Vec<Domain>>> = vec![];
+        if let Some(start) = self.gset.first() {
+            for other in &self.gset[1..] {
+                let blocks = block_system(&self.gset, &generators, start, other);
+                if blocks.len() > 1 && !systems.contains(&blocks) {
+                    systems.push(blocks);
+                }
+            }
+        }
+        systems.sort_by_key(|blocks| blocks[0].len());
+        systems
+    }
+
+    /// The action of this group on `blocks`, a block system. Block `i` is
+    /// represented by the point `i`.
+    ///
+    /// Panics when `blocks` is not a block system of this group.
+    pub fn action_on_blocks(&self, blocks: &[Vec<Domain>]) -> Group<u64, Permutation> {
+        let block_of: HashMap<&Domain, u64> = blocks
+            .iter()
+            .enumerate()
+            .flat_map(|(index, block)| block.iter().map(move |point| (point, index as u64)))
+            .collect();
+        let generators: Vec<Permutation> = self
+            .generators()
+            .iter()
+            .map(|generator| {
+                let images: HashMap<u64, u64> = blocks
+                    .iter()
+                    .enumerate()
+                    .map(|(index, block)| {
+                        let image = generator.act_on(&block[0]);
+                        (index as u64, block_of[&image])
+                    })
+                    .collect();
+                Permutation::new(images)
+            })
+            .collect();
+        let gset: Vec<u64> = (0..blocks.len() as u64).collect();
+        Group::with_base(gset, vec![], generators)
+    }
+}
+
+/// The smallest non trivial block system of the transitive group generated by
+/// `generators`, if there is any.
+pub(super) fn minimal_blocks<Domain, G>(
+    gset: &[Domain],
+    generators: &[G],
+) -> Option<Vec<Vec<Domain>>>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain>,
+{
+    let start = gset.first()?;
+    gset[1..]
+        .iter()
+        .map(|other| block_system(gset, generators, start, other))
+        .filter(|blocks| blocks.len() > 1)
+        .min_by_key(|blocks| blocks[0].len())
+}
+
+/// The finest block system in which `first` and `second` share a block.
+pub(super) fn block_system<Domain, G>(
+    gset: &[Domain],
+    generators: &[G],
+    first: &Domain,
+    second: &Domain,
+) -> Vec<Vec<Domain>>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain>,
+{
+    let index: HashMap<Domain, usize> = gset
+        .iter()
+        .enumerate()
+        .map(|(position, point)| (point.clone(), position))
+        .collect();
+    let mut classes = Classes::new(gset.len());
+    let mut to_merge = vec![(index[first], index[second])];
+    while let Some((a, b)) = to_merge.pop() {
+        let (a, b) = (classes.find(a), classes.find(b));
+        if a == b {
+            continue;
+        }
+        classes.union(a, b);
+        for generator in generators {
+            let image_a = index[&generator.act_on(&gset[a])];
+            let image_b = index[&generator.act_on(&gset[b])];
+            to_merge.push((image_a, image_b));
+        }
+    }
+
+    let mut blocks: Vec<Vec<Domain>> = vec![];
+    let mut block_of: HashMap<usize, usize> = HashMap::new();
+    for (position, point) in gset.iter().enumerate() {
+        let root = classes.find(position);
+        let block = *block_of.entry(root).or_insert_with(|| {
+            blocks.push(vec![]);
+            blocks.len() - 1
+        });
+        blocks[block].push(point.clone());
+    }
+    blocks
+}
+
+/// A union find structure over the positions of the gset.
+struct Classes {
+    parents: Vec<usize>,
+}
+
+impl Classes {
+    fn new(size: usize) -> Classes {
+        Classes {
+            parents: (0..size).collect(),
+        }
+    }
+
+    fn find(&mut self, position: usize) -> usize {
+        let mut root = position;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut current = position;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        self.parents[b] = a;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_points_should_break_transitivity() {
+        let rotation = permute!(0, 1, 1, 2, 2, 0, 3, 3);
+        let group = Group::with_base(vec![0, 1, 2, 3], vec![], vec![rotation]);
+
+        assert!(group.is_transitive(&[0, 1, 2]));
+        assert!(!group.is_transitive(&[0, 1, 2, 3]));
+        assert!(!group.is_transitive(&[0, 1]));
+    }
+
+    #[test]
+    fn symmetric_group_should_be_primitive() {
+        let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+        let group = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation]);
+
+        assert!(group.is_primitive(&[0, 1, 2, 3]));
+        assert_eq!(group.minimal_block_system(), None);
+    }
+
+    #[test]
+    fn minimal_block_system_should_prefer_small_blocks() {
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 0);
+        let group = Group::with_base(vec![0, 1, 2, 3, 4, 5], vec![], vec![rotation]);
+
+        assert_eq!(
+            group.minimal_block_system(),
+            Some(vec![vec![0, 3], vec![1, 4], vec![2, 5]])
+        );
+    }
+
+    #[test]
+    fn block_systems_should_list_every_system_once() {
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 0);
+        let group = Group::with_base(vec![0, 1, 2, 3, 4, 5], vec![], vec![rotation]);
+
+        assert_eq!(
+            group.block_systems(),
+            vec![
+                vec![vec![0, 3], vec![1, 4], vec![2, 5]],
+                vec![vec![0, 2, 4], vec![1, 3, 5]],
+            ]
+        );
+    }
+
+    #[test]
+    fn action_on_blocks_should_permute_blocks() {
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 0);
+        let reflection = permute!(0, 0, 1, 5, 2, 4, 3, 3, 4, 2, 5, 1);
+        let group = Group::with_base(vec![0, 1, 2, 3, 4, 5], vec![], vec![rotation, reflection]);
+        let blocks = vec![vec![0, 3], vec![1, 4], vec![2, 5]];
+
+        let quotient = group.action_on_blocks(&blocks);
+
+        assert_eq!(quotient.order(), 6);
+        assert!(quotient.is_member(permute!(0, 1, 1, 2, 2, 0)));
+    }
+}
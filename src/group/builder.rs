@@ -0,0 +1,618 @@
+//! Construct groups while following the progress of the construction.
+//!
+//! For large generator sets `Group::with_base` can take a long time without
+//! any sign of life. A `GroupBuilder` reports the orbit sizes and strong
+//! generator counts of the levels as the Schreier-Sims algorithm finds them,
+//! and signals each level that is complete. A shared flag or a time limit
+//! stops the construction between steps of the algorithm.
+//!
+//! The builder also decides how the group is constructed. A base hint fixes
+//! the first base points. When the order of the group is known, the
+//! construction ends as soon as it is reached. Sifting random elements instead
+//! of every Schreier generator is faster for big groups, and the `parallel`
+//! feature spreads the work over all cores.
+//!
+//! Identities, duplicates and inverses of earlier generators are always
+//! dropped. On request, so is every generator that the others generate as
+//! well. The transversals of the levels are kept as Schreier vectors, unless
+//! the builder is asked to store them, which trades memory for faster sifting.
+//!
+//! `build_with_summary` also counts the steps the construction took. With the
+//! `tracing` feature every construction emits `tracing` spans and events as
+//! well.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::builder::GroupBuilder;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let mut orbits = vec![];
+//!
+//! let group = GroupBuilder::new(vec![0, 1, 2, 3], vec![transposition, rotation])
+//!     .on_orbit_size(|level, size| orbits.push((level, size)))
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(group.order(), 24);
+//! assert_eq!(orbits.last(), Some(&(2, 2)));
+//! # }
+//! ```
+
+use super::generating::irredundant_generators;
+#[cfg(feature = "parallel")]
+use super::parallel::Parallel;
+use super::random::random_schreier_sims;
+use super::{
+    schreier_sims_with, Group, GroupAction, GroupElement, KnownOrder, Monitor, Sequential, Strategy,
+};
+use num_bigint::BigUint;
+use std::error;
+use std::fmt::{self, Display, Formatter};
+use std::hash::Hash;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Builds a group with the Schreier-Sims algorithm, reporting its progress.
+pub struct GroupBuilder<'a, Domain, G> {
+    gset: Vec<Domain>,
+    base: Vec<Domain>,
+    generators: Vec<G>,
+    strategy: Box<dyn Strategy<Domain, G> + 'a>,
+    construction: Construction,
+    transversals: Transversals,
+    irredundant: bool,
+    progress: Progress<'a>,
+}
+
+/// How a `GroupBuilder` finds the strong generators of the levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Construction {
+    /// Sift every Schreier generator, so the group is always correct. This is
+    /// the default.
+    Deterministic,
+    /// Sift random elements of the group, starting from `seed`, until `rounds`
+    /// consecutive elements sift. This is faster for big groups, but without
+    /// a known order the group can be a proper subgroup, with a probability
+    /// that shrinks exponentially with `rounds`.
+    Randomized {
+        /// The seed of the random elements.
+        seed: u64,
+        /// The number of consecutive elements that should sift.
+        rounds: usize,
+    },
+}
+
+/// How a `GroupBuilder` keeps the transversals of the levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transversals {
+    /// Keep a Schreier vector per level, and multiply a transversal out of
+    /// the strong generators when it is needed. This is the default, and
+    /// takes the least memory.
+    SchreierVectors,
+    /// Store the transversal of every point of every orbit as well, so that
+    /// sifting takes a single product per level.
+    Explicit,
+}
+
+impl<'a, Domain, G> GroupBuilder<'a, Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// A builder for the group generated by `generators` on `gset`.
+    pub fn new(gset: Vec<Domain>, generators: Vec<G>) -> GroupBuilder<'a, Domain, G> {
+        GroupBuilder {
+            gset,
+            base: vec![],
+            generators,
+            strategy: Box::new(Sequential),
+            construction: Construction::Deterministic,
+            transversals: Transversals::SchreierVectors,
+            irredundant: false,
+            progress: Progress::default(),
+        }
+    }
+
+    /// Find the strong generators as `construction` describes.
+    pub fn with_construction(mut self, construction: Construction) -> Self {
+        self.construction = construction;
+        self
+    }
+
+    /// Keep the transversals of the levels as `transversals` describes.
+    pub fn with_transversals(mut self, transversals: Transversals) -> Self {
+        self.transversals = transversals;
+        self
+    }
+
+    /// Tell the builder that the group has `order` elements. The construction
+    /// stops as soon as the product of the orbit lengths equals `order`, and
+    /// fails when it ends with another order.
+    ///
+    /// Like `Group::new_with_order`, an `order` that is too small can result
+    /// in a group that is too small when it divides the real order.
+    pub fn with_known_order(mut self, order: u128) -> Self {
+        self.progress.known_order = Some(KnownOrder::new(order));
+        self
+    }
+
+    /// Drop every generator that is generated by the others before the
+    /// construction, with `irredundant_generators`. This takes a construction
+    /// per generator, but pays off when the group is used a lot.
+    pub fn with_irredundant_generators(mut self) -> Self {
+        self.irredundant = true;
+        self
+    }
+
+    /// Use `base` as the first base points, like `Group::with_base` does.
+    pub fn with_base(mut self, base: Vec<Domain>) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// Call `callback` with the index of a level each time every Schreier
+    /// generator of that level sifts.
+    ///
+    /// Levels are completed from the last to the first. A level can be
+    /// reported more than once, when a lower level adds strong generators to
+    /// it.
+    pub fn on_level_complete<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(usize) + 'a,
+    {
+        self.progress.level_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// Call `callback` with the index of a level and the size of its orbit
+    /// each time that orbit is computed.
+    pub fn on_orbit_size<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(usize, usize) + 'a,
+    {
+        self.progress.orbit_size = Some(Box::new(callback));
+        self
+    }
+
+    /// Call `callback` with the index of a level and the number of its strong
+    /// generators each time that number changes.
+    pub fn on_stabilizer_count<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(usize, usize) + 'a,
+    {
+        self.progress.stabilizer_count = Some(Box::new(callback));
+        self
+    }
+
+    /// Stop the construction once `flag` is set, e.g. from another thread.
+    pub fn with_cancellation(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.progress.cancellation = Some(flag);
+        self
+    }
+
+    /// Stop the construction once it takes longer than `limit`.
+    pub fn with_time_limit(mut self, limit: Duration) -> Self {
+        self.progress.time_limit = Some(limit);
+        self
+    }
+
+    /// Build the group, or report why the construction stopped.
+    ///
+    /// The result is the same as the result of `Group::with_base`.
+    pub fn build(self) -> Result<Group<Domain, G>, GroupError> {
+        self.build_with_summary().map(|(group, _)| group)
+    }
+
+    /// Build the group together with a summary of the steps the construction
+    /// took, or report why the construction stopped.
+    pub fn build_with_summary(
+        mut self,
+    ) -> Result<(Group<Domain, G>, ConstructionSummary), GroupError> {
+        self.progress.started = Some(Instant::now());
+        let generators = if self.irredundant {
+            irredundant_generators(&self.gset, self.generators)
+        } else {
+            self.generators
+        };
+        let levels = match self.construction {
+            Construction::Deterministic => schreier_sims_with(
+                self.strategy.as_ref(),
+                &mut self.progress,
+                &self.gset,
+                &self.base,
+                generators,
+            ),
+            Construction::Randomized { seed, rounds } => random_schreier_sims(
+                self.strategy.as_ref(),
+                &mut self.progress,
+                &self.gset,
+                &self.base,
+                generators,
+                seed,
+                rounds,
+            ),
+        };
+        let mut levels = match (levels, self.progress.stopped) {
+            (Some(levels), _) => levels,
+            (None, Some(error)) => return Err(error),
+            (None, None) => unreachable!("the construction stops for a reason"),
+        };
+        if let Some(expected) = self.progress.known_order.as_ref().map(|known| known.order) {
+            let found = levels
+                .iter()
+                .fold(BigUint::from(1u32), |order, level| order * level.length());
+            if found != BigUint::from(expected) {
+                return Err(GroupError::OrderMismatch { expected, found });
+            }
+        }
+        if self.transversals == Transversals::Explicit {
+            for level in &mut levels {
+                level.orbit.store_transversals();
+            }
+        }
+        let mut summary = self.progress.summary;
+        summary.levels = levels.len();
+        summary.strong_generators = levels
+            .iter()
+            .map(|level| level.orbit.generators.len())
+            .sum();
+        #[cfg(feature = "tracing")]
+        ::tracing::info!(?summary, "construction complete");
+        let group = Group {
+            gset: self.gset,
+            levels,
+        };
+        Ok((group, summary))
+    }
+}
+
+/// The number of steps a construction took.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConstructionSummary {
+    /// The number of levels of the stabilizer chain.
+    pub levels: usize,
+    /// The number of strong generators, summed over the levels.
+    pub strong_generators: usize,
+    /// The number of times an orbit was computed.
+    pub orbit_computations: usize,
+    /// The number of Schreier generators that did not sift.
+    pub sift_failures: usize,
+    /// The number of times a level was found to be complete.
+    pub completed_levels: usize,
+}
+
+#[cfg(feature = "parallel")]
+impl<'a, Domain, G> GroupBuilder<'a, Domain, G>
+where
+    Domain: Eq + Hash + Clone + Send + Sync,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone + Send + Sync,
+{
+    /// Compute orbits and sift Schreier generators on all cores, like
+    /// `Group::new_parallel` does.
+    pub fn parallel(mut self) -> Self {
+        self.strategy = Box::new(Parallel);
+        self
+    }
+}
+
+/// The callbacks and the limits of a `GroupBuilder`.
+#[derive(Default)]
+struct Progress<'a> {
+    level_complete: Option<Box<dyn FnMut(usize) + 'a>>,
+    orbit_size: Option<Box<dyn FnMut(usize, usize) + 'a>>,
+    stabilizer_count: Option<Box<dyn FnMut(usize, usize) + 'a>>,
+    cancellation: Option<Arc<AtomicBool>>,
+    time_limit: Option<Duration>,
+    started: Option<Instant>,
+    stopped: Option<GroupError>,
+    known_order: Option<KnownOrder>,
+    summary: ConstructionSummary,
+}
+
+impl<'a> Monitor for Progress<'a> {
+    fn orbit_size(&mut self, level: usize, size: usize) {
+        self.summary.orbit_computations += 1;
+        if let Some(known_order) = self.known_order.as_mut() {
+            known_order.orbit_size(level, size);
+        }
+        if let Some(callback) = self.orbit_size.as_mut() {
+            callback(level, size);
+        }
+    }
+
+    fn stabilizer_count(&mut self, level: usize, count: usize) {
+        if let Some(callback) = self.stabilizer_count.as_mut() {
+            callback(level, count);
+        }
+    }
+
+    fn level_complete(&mut self, level: usize) {
+        self.summary.completed_levels += 1;
+        if let Some(callback) = self.level_complete.as_mut() {
+            callback(level);
+        }
+    }
+
+    fn sift_failed(&mut self, _level: usize, _depth: usize) {
+        self.summary.sift_failures += 1;
+    }
+
+    fn proceed(&mut self) -> bool {
+        let cancelled = self
+            .cancellation
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed));
+        if cancelled {
+            self.stopped = Some(GroupError::Cancelled);
+            return false;
+        }
+        if let (Some(limit), Some(started)) = (self.time_limit, self.started) {
+            let elapsed = started.elapsed();
+            if elapsed > limit {
+                self.stopped = Some(GroupError::TimeLimitExceeded { limit, elapsed });
+                return false;
+            }
+        }
+        true
+    }
+
+    fn is_complete(&self) -> bool {
+        self.known_order
+            .as_ref()
+            .is_some_and(|known_order| known_order.is_complete())
+    }
+}
+
+/// The reasons a `GroupBuilder` fails to build a group.
+#[derive(Debug, PartialEq, Eq)]
+pub enum GroupError {
+    /// The cancellation flag was set.
+    Cancelled,
+    /// The construction took longer than allowed.
+    TimeLimitExceeded {
+        /// The time limit of the builder.
+        limit: Duration,
+        /// The time the construction took before it stopped.
+        elapsed: Duration,
+    },
+    /// The constructed group does not have the known order.
+    OrderMismatch {
+        /// The order the builder was told.
+        expected: u128,
+        /// The order of the constructed group.
+        found: BigUint,
+    },
+}
+
+impl Display for GroupError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            GroupError::Cancelled => write!(f, "construction was cancelled"),
+            GroupError::TimeLimitExceeded { limit, elapsed } => write!(
+                f,
+                "construction stopped after {:?}, exceeding the limit of {:?}",
+                elapsed, limit
+            ),
+            GroupError::OrderMismatch { expected, found } => write!(
+                f,
+                "constructed a group of order {} instead of {}",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl error::Error for GroupError {}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::models;
+    use super::*;
+
+    #[test]
+    fn builder_should_create_the_same_group() {
+        let puzzle = models::cube2();
+
+        let expected = Group::with_base(puzzle.gset(), vec![], puzzle.generators());
+        let group = GroupBuilder::new(puzzle.gset(), puzzle.generators())
+            .build()
+            .unwrap();
+
+        assert_eq!(group.order(), 3_674_160);
+        assert_eq!(group.levels.len(), expected.levels.len());
+        for (left, right) in group.levels.iter().zip(expected.levels.iter()) {
+            assert_eq!(left.orbit.base, right.orbit.base);
+            assert_eq!(left.orbit.generators, right.orbit.generators);
+        }
+    }
+
+    #[test]
+    fn stored_transversals_should_match_the_schreier_vectors() {
+        let puzzle = models::cube2();
+        let expected = Group::with_base(puzzle.gset(), vec![], puzzle.generators());
+
+        let group = GroupBuilder::new(puzzle.gset(), puzzle.generators())
+            .with_transversals(Transversals::Explicit)
+            .build()
+            .unwrap();
+
+        assert_eq!(group.order(), expected.order());
+        for (left, right) in group.levels.iter().zip(expected.levels.iter()) {
+            assert!(left.orbit.transversals.is_some());
+            for point in right.orbit_points() {
+                assert_eq!(left.transversal(point), right.transversal(point));
+            }
+        }
+        let state = puzzle
+            .sequence("R U F'")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+        assert!(group.contains(&state));
+    }
+
+    #[test]
+    fn builder_should_report_final_orbits_and_levels() {
+        let puzzle = models::cube2();
+        let mut orbits: Vec<usize> = vec![];
+        let mut counts: Vec<usize> = vec![];
+        let mut completed = vec![];
+
+        let group = GroupBuilder::new(puzzle.gset(), puzzle.generators())
+            .on_orbit_size(|level, size| {
+                if orbits.len() <= level {
+                    orbits.resize(level + 1, 0);
+                }
+                orbits[level] = size;
+            })
+            .on_stabilizer_count(|level, count| {
+                if counts.len() <= level {
+                    counts.resize(level + 1, 0);
+                }
+                counts[level] = count;
+            })
+            .on_level_complete(|level| completed.push(level))
+            .build()
+            .unwrap();
+
+        let lengths: Vec<usize> = group.levels.iter().map(|level| level.length()).collect();
+        let generators: Vec<usize> = group
+            .levels
+            .iter()
+            .map(|level| level.orbit.generators.len())
+            .collect();
+        assert_eq!(orbits, lengths);
+        assert_eq!(counts, generators);
+        assert_eq!(completed.last(), Some(&0));
+    }
+
+    #[test]
+    fn builder_should_summarize_the_construction() {
+        let puzzle = models::cube2();
+
+        let (group, summary) = GroupBuilder::new(puzzle.gset(), puzzle.generators())
+            .build_with_summary()
+            .unwrap();
+
+        assert_eq!(summary.levels, group.levels.len());
+        assert!(summary.sift_failures > 0);
+        assert!(summary.completed_levels >= summary.levels);
+        assert_eq!(
+            summary.strong_generators,
+            group
+                .levels
+                .iter()
+                .map(|level| level.orbit.generators.len())
+                .sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn builder_should_drop_redundant_generators() {
+        let puzzle = models::cube2();
+        let mut generators = puzzle.generators();
+        generators.extend(puzzle.generators().iter().map(|g| g.inverse()));
+        generators.push(generators[0].times(&generators[1]));
+
+        let group = GroupBuilder::new(puzzle.gset(), generators)
+            .with_irredundant_generators()
+            .build()
+            .unwrap();
+
+        assert_eq!(group.order(), 3_674_160);
+        assert_eq!(
+            group.levels[0].orbit.generators.len(),
+            puzzle.generators().len()
+        );
+    }
+
+    #[test]
+    fn builder_should_check_the_known_order() {
+        let puzzle = models::cube2();
+
+        let group = GroupBuilder::new(puzzle.gset(), puzzle.generators())
+            .with_known_order(3_674_160)
+            .build()
+            .unwrap();
+        let error = GroupBuilder::new(puzzle.gset(), puzzle.generators())
+            .with_known_order(7)
+            .build()
+            .unwrap_err();
+
+        assert_eq!(group.order(), 3_674_160);
+        assert_eq!(
+            error,
+            GroupError::OrderMismatch {
+                expected: 7,
+                found: BigUint::from(3_674_160u32)
+            }
+        );
+    }
+
+    #[test]
+    fn randomized_construction_should_find_the_group() {
+        let puzzle = models::cube2();
+
+        let group = GroupBuilder::new(puzzle.gset(), puzzle.generators())
+            .with_construction(Construction::Randomized {
+                seed: 1,
+                rounds: 30,
+            })
+            .build()
+            .unwrap();
+        let known = GroupBuilder::new(puzzle.gset(), puzzle.generators())
+            .with_construction(Construction::Randomized { seed: 2, rounds: 0 })
+            .with_known_order(3_674_160)
+            .build();
+
+        assert_eq!(group.order(), 3_674_160);
+        assert!(known.is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn parallel_builder_should_create_the_same_group() {
+        let puzzle = models::cube2();
+
+        let group = GroupBuilder::new(puzzle.gset(), puzzle.generators())
+            .parallel()
+            .build()
+            .unwrap();
+
+        assert_eq!(group.order(), 3_674_160);
+    }
+
+    #[test]
+    fn builder_should_stop_when_cancelled() {
+        let puzzle = models::cube2();
+        let flag = Arc::new(AtomicBool::new(false));
+        let signal = flag.clone();
+
+        let result = GroupBuilder::new(puzzle.gset(), puzzle.generators())
+            .with_cancellation(flag)
+            .on_level_complete(move |_| signal.store(true, Ordering::Relaxed))
+            .build();
+
+        assert_eq!(result.err(), Some(GroupError::Cancelled));
+    }
+
+    #[test]
+    fn builder_should_stop_beyond_the_time_limit() {
+        let puzzle = models::cube2();
+
+        let result = GroupBuilder::new(puzzle.gset(), puzzle.generators())
+            .with_time_limit(Duration::from_millis(1))
+            .on_level_complete(|_| std::thread::sleep(Duration::from_millis(2)))
+            .build();
+
+        match result {
+            Err(GroupError::TimeLimitExceeded { limit, elapsed }) => assert!(elapsed > limit),
+            _ => panic!("construction should exceed the time limit"),
+        }
+    }
+}
@@ -11,13 +11,13 @@ use std::collections::VecDeque;
 pub fn elements_generated_by(generators: &Vec<Permutation>) -> Vec<Permutation> {
     let mut elements: Vec<Permutation> = vec![];
     let mut to_visit: VecDeque<Permutation> = VecDeque::new();
-    to_visit.push_back(identity(generators));
+    to_visit.push_back(Permutation::identity());
 
     while !to_visit.is_empty() {
         let element = to_visit.pop_front().unwrap();
         elements.push(element.clone());
 
-        for ref g in generators {
+        for g in generators {
             let next = element.times(g);
             if !elements.contains(&next) && !to_visit.contains(&next) {
                 to_visit.push_back(next);
@@ -28,22 +28,11 @@ pub fn elements_generated_by(generators: &Vec<Permutation>) -> Vec<Permutation>
     elements
 }
 
-/// Calculate an identity element for a set of generators. Assume that set is
-/// non empty, panics otherwise.
-pub fn identity<G>(generators: &Vec<G>) -> G
-where
-    G: GroupElement,
-{
-    let g = generators.get(0).expect("at least one generator");
-    let inverse = g.inverse();
-    g.times(&inverse)
-}
-
 /// Calculate the nth factorial number.
 ///
 /// The n! is defined as n * (n-1) * ... * 1
 pub fn fact(m: u64) -> u64 {
-    (1..m).map(|n| n + 1).fold(1u64, |acc, n| acc * n)
+    (1..m).map(|n| n + 1).product()
 }
 
 #[cfg(test)]
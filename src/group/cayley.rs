@@ -0,0 +1,283 @@
+//! [Cayley graphs](https://en.wikipedia.org/wiki/Cayley_graph) of groups.
+//!
+//! The vertices of a Cayley graph are the elements of a group. Every vertex _g_
+//! has an edge to _gs_ for every generator _s_. The graph is explored breadth
+//! first from the identity, so the distance of a vertex is the length of a
+//! shortest word in the generators for its element.
+//!
+//! Elements are told apart by their base images: the images of the base points
+//! of the stabilizer chain of the group.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::cayley::CayleyGraph;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2);
+//! let rotation = permute!(0, 1, 1, 2, 2, 0);
+//! let generators = vec![transposition, rotation];
+//! let group = Group::with_base(vec![0, 1, 2], vec![], generators.clone());
+//!
+//! let graph = CayleyGraph::new(&group, &generators);
+//!
+//! assert_eq!(graph.growth_series(), vec![1, 2, 3]);
+//! assert_eq!(graph.diameter_up_to(5), Some(2));
+//! # }
+//! ```
+
+use super::{Group, GroupAction, GroupElement};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+
+/// The part of a Cayley graph within a radius of the identity.
+pub struct CayleyGraph<G> {
+    elements: Vec<G>,
+    distances: Vec<usize>,
+    edges: Vec<(usize, usize, usize)>,
+    complete: bool,
+}
+
+impl<G> CayleyGraph<G>
+where
+    G: GroupElement + PartialEq + Clone,
+{
+    /// The Cayley graph of `group` with respect to `generators`.
+    pub fn new<Domain>(group: &Group<Domain, G>, generators: &[G]) -> CayleyGraph<G>
+    where
+        Domain: Eq + Hash + Clone,
+        G: GroupAction<Domain = Domain>,
+    {
+        CayleyGraph::with_radius(group, generators, usize::MAX)
+    }
+
+    /// The part of the Cayley graph of `group` with respect to `generators`
+    /// within distance `radius` of the identity.
+    pub fn with_radius<Domain>(
+        group: &Group<Domain, G>,
+        generators: &[G],
+        radius: usize,
+    ) -> CayleyGraph<G>
+    where
+        Domain: Eq + Hash + Clone,
+        G: GroupAction<Domain = Domain>,
+    {
+        let base: Vec<Domain> = group
+            .levels
+            .iter()
+            .map(|level| level.orbit.base.clone())
+            .collect();
+        let key = |element: &G| -> Vec<Domain> {
+            base.iter().map(|point| element.act_on(point)).collect()
+        };
+        let identity = G::identity();
+
+        let mut index: HashMap<Vec<Domain>, usize> = HashMap::new();
+        index.insert(key(&identity), 0);
+        let mut elements = vec![identity];
+        let mut distances = vec![0];
+        let mut edges = vec![];
+        let mut current = 0;
+        while current < elements.len() {
+            for (generator_index, generator) in generators.iter().enumerate() {
+                let next = elements[current].times(generator);
+                let next_key = key(&next);
+                let target = match index.get(&next_key) {
+                    Some(&target) => target,
+                    None if distances[current] < radius => {
+                        index.insert(next_key, elements.len());
+                        elements.push(next);
+                        distances.push(distances[current] + 1);
+                        elements.len() - 1
+                    }
+                    None => continue,
+                };
+                edges.push((current, generator_index, target));
+            }
+            current += 1;
+        }
+        let complete = elements.len() as u128 == group.order();
+        CayleyGraph {
+            elements,
+            distances,
+            edges,
+            complete,
+        }
+    }
+
+    /// The number of vertices.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// A Cayley graph always contains the identity, so it is never empty.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The elements of the vertices, in breadth first order.
+    pub fn elements(&self) -> &[G] {
+        &self.elements
+    }
+
+    /// The distance of the vertex at `index` to the identity.
+    pub fn distance(&self, index: usize) -> usize {
+        self.distances[index]
+    }
+
+    /// The edges as `(from, generator, to)`, with vertices and generators
+    /// referred to by index.
+    pub fn edges(&self) -> &[(usize, usize, usize)] {
+        &self.edges
+    }
+
+    /// Determine if every element of the group is a vertex.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// The number of vertices at distance 0, 1, 2, ... of the identity.
+    pub fn growth_series(&self) -> Vec<usize> {
+        let mut series = vec![];
+        for distance in &self.distances {
+            if series.len() <= *distance {
+                series.resize(distance + 1, 0);
+            }
+            series[*distance] += 1;
+        }
+        series
+    }
+
+    /// The diameter of the graph, if the whole group lies within distance
+    /// `radius` of the identity.
+    pub fn diameter_up_to(&self, radius: usize) -> Option<usize> {
+        let diameter = self.distances.last().cloned().unwrap_or(0);
+        if self.complete && diameter <= radius {
+            Some(diameter)
+        } else {
+            None
+        }
+    }
+}
+
+impl<G> CayleyGraph<G>
+where
+    G: GroupElement + PartialEq + Clone + Display,
+{
+    /// The graph in the [DOT](https://graphviz.org/doc/info/lang.html)
+    /// language. Vertices are labeled by their element, edges by the index of
+    /// their generator.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph cayley {\n");
+        for (index, element) in self.elements.iter().enumerate() {
+            dot.push_str(&format!(
+                "  {} [label=\"{}\"];\n",
+                index,
+                escape_dot(&element.to_string())
+            ));
+        }
+        for (from, generator, to) in &self.edges {
+            dot.push_str(&format!(
+                "  {} -> {} [label=\"{}\"];\n",
+                from, to, generator
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// The graph in [GraphML](http://graphml.graphdrawing.org/). Vertices
+    /// carry their element and distance, edges the index of their generator.
+    pub fn to_graphml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        xml.push_str(
+            "  <key id=\"element\" for=\"node\" attr.name=\"element\" attr.type=\"string\"/>\n",
+        );
+        xml.push_str(
+            "  <key id=\"distance\" for=\"node\" attr.name=\"distance\" attr.type=\"int\"/>\n",
+        );
+        xml.push_str(
+            "  <key id=\"generator\" for=\"edge\" attr.name=\"generator\" attr.type=\"int\"/>\n",
+        );
+        xml.push_str("  <graph id=\"cayley\" edgedefault=\"directed\">\n");
+        for (index, element) in self.elements.iter().enumerate() {
+            xml.push_str(&format!(
+                "    <node id=\"n{}\"><data key=\"element\">{}</data><data key=\"distance\">{}</data></node>\n",
+                index,
+                escape_xml(&element.to_string()),
+                self.distances[index]
+            ));
+        }
+        for (from, generator, to) in &self.edges {
+            xml.push_str(&format!(
+                "    <edge source=\"n{}\" target=\"n{}\"><data key=\"generator\">{}</data></edge>\n",
+                from, to, generator
+            ));
+        }
+        xml.push_str("  </graph>\n</graphml>\n");
+        xml
+    }
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::permutation::Permutation;
+    use super::*;
+
+    fn cyclic() -> (Group<u64, Permutation>, Vec<Permutation>) {
+        let rotation = permute!(0, 1, 1, 2, 2, 0);
+        let generators = vec![rotation];
+        let group = Group::with_base(vec![0, 1, 2], vec![], generators.clone());
+        (group, generators)
+    }
+
+    #[test]
+    fn radius_should_limit_the_graph() {
+        let (group, generators) = cyclic();
+
+        let graph = CayleyGraph::with_radius(&group, &generators, 1);
+
+        assert_eq!(graph.len(), 2);
+        assert!(!graph.is_complete());
+        assert_eq!(graph.diameter_up_to(5), None);
+    }
+
+    #[test]
+    fn dot_should_list_vertices_and_edges() {
+        let (group, generators) = cyclic();
+
+        let graph = CayleyGraph::new(&group, &generators);
+
+        assert_eq!(
+            graph.to_dot(),
+            "digraph cayley {\n  0 [label=\"Id\"];\n  1 [label=\"(0 1 2)\"];\n  2 [label=\"(0 2 1)\"];\n  0 -> 1 [label=\"0\"];\n  1 -> 2 [label=\"0\"];\n  2 -> 0 [label=\"0\"];\n}\n"
+        );
+    }
+
+    #[test]
+    fn graphml_should_contain_every_edge() {
+        let (group, generators) = cyclic();
+
+        let graph = CayleyGraph::new(&group, &generators);
+        let xml = graph.to_graphml();
+
+        assert_eq!(xml.matches("<node ").count(), 3);
+        assert_eq!(xml.matches("<edge ").count(), 3);
+        assert!(xml.contains("<edge source=\"n2\" target=\"n0\">"));
+    }
+}
@@ -0,0 +1,213 @@
+//! Centralizers and the center of a group.
+//!
+//! The centralizer of a set of elements consists of the elements that commute
+//! with each of them. It is found by a backtrack search over the stabilizer
+//! chain, one level at a time from the bottom up. At a level, the centralizer
+//! in the stabilizer of the earlier base points is known to contain the
+//! elements found so far. Only base images outside their orbit need a search
+//! for a new element. An element _g_ that commutes with _h_ maps the cycles of
+//! _h_ onto cycles of _h_: it maps _p_^_h_ to (_p_^_g_)^_h_. So choosing the
+//! image of one point forces the images of everything that can be reached from
+//! it, which prunes the search considerably.
+//!
+//! The center of a group is the centralizer of the group itself. For the
+//! Rubik's cube it has two elements: the identity and the superflip.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let reflection = permute!(0, 0, 1, 3, 2, 2, 3, 1);
+//! let square = Group::with_base(vec![0, 1, 2, 3], vec![], vec![rotation, reflection]);
+//!
+//! let center = square.center();
+//!
+//! assert_eq!(center.order(), 2);
+//! assert!(center.contains(&permute!(0, 2, 1, 3, 2, 0, 3, 1)));
+//! # }
+//! ```
+
+use super::orbit::Orbit;
+use super::{Group, GroupAction, GroupElement};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::slice;
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// The center of this group, the elements that commute with every element.
+    pub fn center(&self) -> Group<Domain, G> {
+        self.centralizer_of(&self.generators())
+    }
+
+    /// The elements of this group that commute with `element`.
+    pub fn centralizer(&self, element: &G) -> Group<Domain, G> {
+        self.centralizer_of(slice::from_ref(element))
+    }
+
+    /// The elements of this group that commute with every element of
+    /// `subgroup`, which does not need to be a subgroup of this group.
+    pub fn centralizer_of_subgroup(&self, subgroup: &Group<Domain, G>) -> Group<Domain, G> {
+        self.centralizer_of(&subgroup.generators())
+    }
+
+    /// The elements of this group that commute with each of `elements`.
+    fn centralizer_of(&self, elements: &[G]) -> Group<Domain, G> {
+        let base = self.base();
+        let mut found: Vec<G> = vec![];
+        for index in (0..self.levels.len()).rev() {
+            let level = &self.levels[index];
+            let mut fixed = Forced::new();
+            for point in &base[0..index] {
+                fixed.force(point, point, elements);
+            }
+            for point in level.orbit.iter() {
+                let known = Orbit::new(base[index].clone(), found.clone());
+                if known.contains(point) {
+                    continue;
+                }
+                let mut forced = fixed.clone();
+                if !forced.force(&base[index], point, elements) {
+                    continue;
+                }
+                let transversal = level.orbit.representative(point).expect("point in orbit");
+                if let Some(g) = self.commuting_element(elements, index + 1, transversal, &forced) {
+                    found.push(g);
+                }
+            }
+        }
+        Group::with_base(self.gset.clone(), base, found)
+    }
+
+    /// Extend `prefix`, a product of transversals of the levels before
+    /// `index` that forces the images in `forced`, to an element that
+    /// commutes with each of `elements`.
+    fn commuting_element(
+        &self,
+        elements: &[G],
+        index: usize,
+        prefix: G,
+        forced: &Forced<Domain>,
+    ) -> Option<G> {
+        if index == self.levels.len() {
+            let commutes = elements.iter().all(|h| prefix.times(h) == h.times(&prefix));
+            return if commutes { Some(prefix) } else { None };
+        }
+        let level = &self.levels[index];
+        for point in level.orbit.iter() {
+            let image = prefix.act_on(point);
+            let mut extended = forced.clone();
+            if !extended.force(&level.orbit.base, &image, elements) {
+                continue;
+            }
+            let transversal = level.orbit.representative(point).expect("point in orbit");
+            let found =
+                self.commuting_element(elements, index + 1, transversal.times(&prefix), &extended);
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+}
+
+/// The images an element that commutes with some elements is forced to have
+/// so far.
+#[derive(Clone)]
+struct Forced<Domain>
+where
+    Domain: Eq + Hash + Clone,
+{
+    images: HashMap<Domain, Domain>,
+    used: HashSet<Domain>,
+}
+
+impl<Domain> Forced<Domain>
+where
+    Domain: Eq + Hash + Clone,
+{
+    fn new() -> Forced<Domain> {
+        Forced {
+            images: HashMap::new(),
+            used: HashSet::new(),
+        }
+    }
+
+    /// Force `point` to map to `image`, and with it _p_^_h_ to _q_^_h_ for
+    /// every forced _p_ to _q_ and every _h_ in `elements`. Returns whether
+    /// that is consistent with the images forced before.
+    fn force<G>(&mut self, point: &Domain, image: &Domain, elements: &[G]) -> bool
+    where
+        G: GroupAction<Domain = Domain>,
+    {
+        let mut pending = vec![(point.clone(), image.clone())];
+        while let Some((current, target)) = pending.pop() {
+            match self.images.get(&current) {
+                Some(known) => {
+                    if known != &target {
+                        return false;
+                    }
+                    continue;
+                }
+                None => {
+                    if !self.used.insert(target.clone()) {
+                        return false;
+                    }
+                    self.images.insert(current.clone(), target.clone());
+                }
+            }
+            for h in elements {
+                pending.push((h.act_on(&current), h.act_on(&target)));
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::permutation::Permutation;
+    use super::super::testing::s4;
+    use super::*;
+
+    #[test]
+    fn symmetric_group_should_have_a_trivial_center() {
+        assert_eq!(s4().center().order(), 1);
+    }
+
+    #[test]
+    fn centralizer_should_commute_with_the_element() {
+        let group = s4();
+        let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+
+        let centralizer = group.centralizer(&transposition);
+
+        assert_eq!(centralizer.order(), 4);
+        assert!(centralizer.contains(&permute!(0, 0, 1, 1, 2, 3, 3, 2)));
+    }
+
+    #[test]
+    fn centralizer_of_the_klein_four_group_should_be_itself() {
+        let group = s4();
+        let klein = Group::with_base(
+            vec![0, 1, 2, 3],
+            vec![],
+            vec![
+                permute!(0, 1, 1, 0, 2, 3, 3, 2),
+                permute!(0, 2, 1, 3, 2, 0, 3, 1),
+            ],
+        );
+
+        let centralizer = group.centralizer_of_subgroup(&klein);
+
+        assert_eq!(centralizer.order(), 4);
+        assert_eq!(klein.center().order(), 4);
+    }
+}
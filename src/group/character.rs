@@ -0,0 +1,241 @@
+//! Conjugacy classes and the character of a permutation representation.
+//!
+//! A group acting on a set acts on the vector space with that set as basis.
+//! The trace of an element in this representation, its character value, is
+//! the number of points it fixes. Characters are constant on conjugacy
+//! classes, so a `Character` holds one value per class.
+//!
+//! The inner product of characters tells how representations decompose. The
+//! inner product of a permutation character with the trivial character is the
+//! number of orbits, which is Burnside's lemma. The inner product of the
+//! character of a transitive action with itself is its rank, which is 2
+//! exactly when the action is doubly transitive.
+//!
+//! The classes are found by enumerating the group, so this is meant for small
+//! groups.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let s4 = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation]);
+//!
+//! let character = s4.permutation_character(&[0, 1, 2, 3]);
+//!
+//! assert_eq!(s4.conjugacy_classes().len(), 5);
+//! assert_eq!(character.inner_product(&character.trivial()), 1);
+//! assert_eq!(character.inner_product(&character), 2);
+//! # }
+//! ```
+
+use super::{Group, GroupAction, GroupElement};
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// A conjugacy class of a group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConjugacyClass<G> {
+    /// An element of the class.
+    pub representative: G,
+    /// The number of elements in the class.
+    pub size: usize,
+}
+
+/// A class function of a group: one value for each conjugacy class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Character {
+    sizes: Vec<usize>,
+    values: Vec<i64>,
+}
+
+impl Character {
+    /// The values, one for each class in the order of
+    /// `Group::conjugacy_classes`.
+    pub fn values(&self) -> &[i64] {
+        &self.values
+    }
+
+    /// The value at the identity, i.e. the dimension of the representation.
+    pub fn degree(&self) -> i64 {
+        self.values.first().cloned().unwrap_or(1)
+    }
+
+    /// The character of the same group with other `values`.
+    ///
+    /// Panics when there is not a value for every class.
+    pub fn with_values(&self, values: Vec<i64>) -> Character {
+        assert_eq!(values.len(), self.sizes.len(), "one value per class");
+        Character {
+            sizes: self.sizes.clone(),
+            values,
+        }
+    }
+
+    /// The trivial character of the same group, which is 1 everywhere.
+    pub fn trivial(&self) -> Character {
+        self.with_values(vec![1; self.sizes.len()])
+    }
+
+    /// The character of the tensor product, i.e. the product of the values.
+    pub fn product(&self, other: &Character) -> Character {
+        let values = self
+            .values
+            .iter()
+            .zip(&other.values)
+            .map(|(a, b)| a * b)
+            .collect();
+        self.with_values(values)
+    }
+
+    /// The inner product, the sum over all elements of the products of the
+    /// values divided by the order of the group.
+    ///
+    /// The inner product of two characters is an integer. For characters of
+    /// representations it is the number of irreducible constituents they share,
+    /// counted with multiplicities.
+    ///
+    /// Panics when the sum is not divisible by the order, which happens for
+    /// values from `with_values` that are not a character.
+    pub fn inner_product(&self, other: &Character) -> i64 {
+        let order = self.sizes.iter().sum::<usize>() as i64;
+        let sum: i64 = self
+            .sizes
+            .iter()
+            .zip(self.values.iter().zip(&other.values))
+            .map(|(&size, (a, b))| size as i64 * a * b)
+            .sum();
+        assert_eq!(
+            sum % order,
+            0,
+            "the inner product of characters should be an integer"
+        );
+        sum / order
+    }
+}
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + Eq + Hash + Clone,
+{
+    /// The conjugacy classes of this group, starting with the class of the
+    /// identity.
+    pub fn conjugacy_classes(&self) -> Vec<ConjugacyClass<G>> {
+        let generators = self.generators();
+        let mut elements = self.elements();
+        if let Some(index) = elements.iter().position(|element| element.is_identity()) {
+            elements.swap(0, index);
+        }
+        let mut seen: HashSet<G> = HashSet::new();
+        let mut classes = vec![];
+        for element in elements {
+            if seen.contains(&element) {
+                continue;
+            }
+            seen.insert(element.clone());
+            let mut frontier = vec![element.clone()];
+            let mut size = 1;
+            while let Some(current) = frontier.pop() {
+                for generator in &generators {
+                    let conjugate = current.conjugate_by(generator);
+                    if seen.insert(conjugate.clone()) {
+                        size += 1;
+                        frontier.push(conjugate);
+                    }
+                }
+            }
+            classes.push(ConjugacyClass {
+                representative: element,
+                size,
+            });
+        }
+        classes
+    }
+
+    /// The character of the action of this group on `points`: the number of
+    /// points each class fixes.
+    pub fn permutation_character(&self, points: &[Domain]) -> Character {
+        let classes = self.conjugacy_classes();
+        let values = classes
+            .iter()
+            .map(|class| {
+                points
+                    .iter()
+                    .filter(|&point| &class.representative.act_on(point) == point)
+                    .count() as i64
+            })
+            .collect();
+        Character {
+            sizes: classes.iter().map(|class| class.size).collect(),
+            values,
+        }
+    }
+
+    /// The number of orbits of this group on `points`, by Burnside's lemma.
+    ///
+    /// `points` should be a union of orbits.
+    pub fn orbit_count(&self, points: &[Domain]) -> usize {
+        let character = self.permutation_character(points);
+        character.inner_product(&character.trivial()) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::permutation::Permutation;
+    use super::super::testing::s4;
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn conjugacy_classes_should_partition_the_group() {
+        let classes = s4().conjugacy_classes();
+
+        let mut sizes: Vec<usize> = classes.iter().map(|class| class.size).collect();
+        assert!(classes[0].representative.is_identity());
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![1, 3, 6, 6, 8]);
+    }
+
+    #[test]
+    fn permutation_character_should_decompose() {
+        let group = s4();
+        let character = group.permutation_character(&[0, 1, 2, 3]);
+        let standard =
+            character.with_values(character.values().iter().map(|value| value - 1).collect());
+
+        assert_eq!(character.degree(), 4);
+        assert_eq!(standard.inner_product(&standard), 1);
+        assert_eq!(standard.inner_product(&character.trivial()), 0);
+        assert_eq!(
+            character
+                .product(&character)
+                .inner_product(&character.trivial()),
+            2
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "should be an integer")]
+    fn inner_product_should_panic_for_class_functions() {
+        let group = s4();
+        let character = group.permutation_character(&[0, 1, 2, 3]);
+        let indicator = character.with_values(vec![1, 0, 0, 0, 0]);
+
+        indicator.inner_product(&character.trivial());
+    }
+
+    #[test]
+    fn orbit_count_should_follow_burnside() {
+        let rotation = permute!(0, 1, 1, 2, 2, 0, 3, 4, 4, 3);
+        let group = Group::with_base(vec![0, 1, 2, 3, 4], vec![], vec![rotation]);
+
+        assert_eq!(group.orbit_count(&[0, 1, 2, 3, 4]), 2);
+        assert_eq!(group.orbit_count(&[3, 4]), 1);
+    }
+}
@@ -0,0 +1,258 @@
+//! [Composition series](https://en.wikipedia.org/wiki/Composition_series) and
+//! simplicity.
+//!
+//! A composition series _G_ = _G_0 > _G_1 > ... > _G_k = 1 has every _G_i+1
+//! maximal normal in _G_i, so every factor _G_i/_G_i+1 is simple. The factors
+//! identify the structure of a group, e.g. the 3x3x3 cube group has alternating
+//! groups among its factors.
+//!
+//! A maximal normal subgroup is found in one of two ways. When the derived
+//! subgroup _G'_ is proper, the abelian quotient _G_/_G'_ has a subgroup of
+//! prime index, and its preimage is maximal normal. Otherwise _G_ is perfect,
+//! and normal closures of random elements and of their powers of prime order
+//! are grown as long as they stay proper. The latter is a Monte Carlo method:
+//! a normal subgroup that no random element hits can be missed.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let s4 = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation]);
+//!
+//! let orders: Vec<u128> = s4
+//!     .composition_series()
+//!     .iter()
+//!     .map(|group| group.order())
+//!     .collect();
+//!
+//! assert_eq!(orders, vec![24, 12, 4, 2, 1]);
+//! assert!(!s4.is_simple());
+//! # }
+//! ```
+
+use super::permutation::Permutation;
+use super::random::XorShift;
+use super::{Group, GroupAction, GroupElement};
+use num_bigint::BigUint;
+use std::hash::Hash;
+
+/// The number of random elements in a row that have to fail to enlarge a
+/// normal subgroup before it is considered maximal.
+const TRIALS: usize = 20;
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// A composition series, from a copy of this group down to the trivial
+    /// group.
+    ///
+    /// The search is randomized, but seeded, so the same group always yields
+    /// the same series.
+    pub fn composition_series(&self) -> Vec<Group<Domain, G>> {
+        let mut random = XorShift::new(seed(&self.big_order()));
+        let mut series = vec![Group::with_base(
+            self.gset.clone(),
+            vec![],
+            self.generators(),
+        )];
+        while series
+            .last()
+            .map(|group| group.big_order() > BigUint::from(1u32))
+            .unwrap_or(false)
+        {
+            let next = series
+                .last()
+                .expect("series should not be empty")
+                .maximal_normal_subgroup(&mut random);
+            series.push(next);
+        }
+        series
+    }
+
+    /// Determine if this group is simple, i.e. non trivial without proper non
+    /// trivial normal subgroups.
+    ///
+    /// For perfect groups this is a Monte Carlo test: a normal subgroup that no
+    /// random element hits goes unnoticed.
+    pub fn is_simple(&self) -> bool {
+        let order = self.big_order();
+        if order == BigUint::from(1u32) {
+            return false;
+        }
+        if self.derived_subgroup().big_order() < order {
+            return matches!(self.order_factored().as_slice(), [(_, 1)]);
+        }
+        let mut random = XorShift::new(seed(&order));
+        (0..TRIALS).all(|_| {
+            let element = self.random_element(&mut random);
+            candidates(&element)
+                .into_iter()
+                .all(|candidate| self.normal_closure(vec![candidate]).big_order() == order)
+        })
+    }
+
+    fn maximal_normal_subgroup(&self, random: &mut XorShift) -> Group<Domain, G> {
+        let derived = self.derived_subgroup();
+        if derived.big_order() < self.big_order() {
+            return self.preimage_of_prime_index(&derived);
+        }
+        let mut normal = Group::with_base(self.gset.clone(), vec![], vec![]);
+        let mut failures = 0;
+        while failures < TRIALS {
+            let element = self.random_element(random);
+            let mut enlarged = false;
+            for candidate in candidates(&element) {
+                if normal.contains(&candidate) {
+                    continue;
+                }
+                let mut elements = normal.generators();
+                elements.push(candidate);
+                let closure = self.normal_closure(elements);
+                if closure.big_order() < self.big_order() {
+                    normal = closure;
+                    enlarged = true;
+                }
+            }
+            failures = if enlarged { 0 } else { failures + 1 };
+        }
+        normal
+    }
+
+    /// The preimage of a subgroup of prime index of the abelian quotient by
+    /// `derived`.
+    fn preimage_of_prime_index(&self, derived: &Group<Domain, G>) -> Group<Domain, G> {
+        let quotient = self.quotient(derived);
+        let abelian = quotient.group();
+        let p = abelian.order_factored()[0].0;
+        let abelian_generators = abelian.generators();
+        let mut generators: Vec<Permutation> = abelian_generators
+            .iter()
+            .map(|g| power(g, p))
+            .filter(|g| !g.is_identity())
+            .collect();
+        for g in abelian_generators {
+            let mut extended = generators.clone();
+            extended.push(g);
+            let subgroup = Group::with_base(abelian.gset.clone(), vec![], extended.clone());
+            if subgroup.big_order() < abelian.big_order() {
+                generators = extended;
+            }
+        }
+
+        let representatives = quotient.cosets().representatives();
+        let mut preimage = derived.generators();
+        for g in generators {
+            preimage.push(representatives[g.act_on(&0) as usize].clone());
+        }
+        Group::with_base(self.gset.clone(), vec![], preimage)
+    }
+}
+
+/// An element and its powers of prime order. The latter find small normal
+/// subgroups, like a center, that normal closures of random elements miss.
+fn candidates<G>(element: &G) -> Vec<G>
+where
+    G: GroupElement + Clone,
+{
+    let order = element_order(element);
+    let mut candidates = vec![element.clone()];
+    let mut rest = order;
+    let mut p = 2;
+    while rest > 1 {
+        if rest.is_multiple_of(p) {
+            candidates.push(power(element, order / p));
+            while rest.is_multiple_of(p) {
+                rest /= p;
+            }
+        }
+        p += 1;
+    }
+    candidates
+}
+
+fn element_order<G>(element: &G) -> u64
+where
+    G: GroupElement + Clone,
+{
+    let mut order = 1;
+    let mut current = element.clone();
+    while !current.is_identity() {
+        current = current.times(element);
+        order += 1;
+    }
+    order
+}
+
+fn power<G>(element: &G, exponent: u64) -> G
+where
+    G: GroupElement + Clone,
+{
+    let mut result = G::identity();
+    for _ in 0..exponent {
+        result = result.times(element);
+    }
+    result
+}
+
+/// A seed for the random elements of a group of order `order`. It equals the
+/// order when that fits in a `u64`.
+fn seed(order: &BigUint) -> u64 {
+    order.iter_u64_digits().next().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn symmetric(n: u64) -> Group<u64, Permutation> {
+        let mut transposition = HashMap::new();
+        let mut rotation = HashMap::new();
+        for i in 0..n {
+            transposition.insert(i, i);
+            rotation.insert(i, (i + 1) % n);
+        }
+        transposition.insert(0, 1);
+        transposition.insert(1, 0);
+        Group::with_base(
+            (0..n).collect(),
+            vec![],
+            vec![Permutation::new(transposition), Permutation::new(rotation)],
+        )
+    }
+
+    #[test]
+    fn alternating_group_should_be_simple() {
+        let a5 = symmetric(5).derived_subgroup();
+
+        assert!(a5.is_simple());
+        assert!(!symmetric(5).is_simple());
+    }
+
+    #[test]
+    fn composition_series_of_s5_should_pass_a5() {
+        let orders: Vec<u128> = symmetric(5)
+            .composition_series()
+            .iter()
+            .map(|group| group.order())
+            .collect();
+
+        assert_eq!(orders, vec![120, 60, 1]);
+    }
+
+    #[test]
+    fn cyclic_groups_of_prime_order_should_be_simple() {
+        let rotation = permute!(0, 1, 1, 2, 2, 0);
+        let c3 = Group::with_base(vec![0, 1, 2], vec![], vec![rotation]);
+
+        assert!(c3.is_simple());
+        assert_eq!(c3.composition_series().len(), 2);
+    }
+}
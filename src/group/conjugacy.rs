@@ -0,0 +1,225 @@
+//! Decide whether two elements are conjugate in a group.
+//!
+//! Elements _a_ and _b_ are conjugate when _x_^-1 _a_ _x_ = _b_ for some _x_
+//! in the group. Such an _x_ maps every cycle of _a_ onto a cycle of _b_, so
+//! conjugate elements have the same cycle type. When the cycle types agree, a
+//! backtrack search chooses the images of the base points one level at a
+//! time. Every choice forces the images of the whole cycle of _a_ through that
+//! base point, which prunes choices that conflict with earlier ones.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::{Group, GroupElement};
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let group = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation]);
+//! let a = permute!(0, 1, 1, 2, 2, 0, 3, 3);
+//! let b = permute!(0, 0, 1, 3, 2, 1, 3, 2);
+//!
+//! let x = group.are_conjugate(&a, &b).unwrap();
+//!
+//! assert_eq!(a.conjugate_by(&x), b);
+//! # }
+//! ```
+
+use super::{Group, GroupAction, GroupElement};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// An element _x_ of this group with _x_^-1 `a` _x_ = `b`, or `None` when
+    /// `a` and `b` are not conjugate in this group.
+    pub fn are_conjugate(&self, a: &G, b: &G) -> Option<G> {
+        if cycle_type(&self.gset, a) != cycle_type(&self.gset, b) {
+            return None;
+        }
+        if self.levels.is_empty() {
+            return if a == b { Some(G::identity()) } else { None };
+        }
+        self.conjugating_element(a, b, 0, G::identity(), &Partial::new())
+    }
+
+    /// Extend the choices for the levels before `index`, which multiply to
+    /// `prefix` and force the images in `partial`, to an element that
+    /// conjugates `a` to `b`.
+    fn conjugating_element(
+        &self,
+        a: &G,
+        b: &G,
+        index: usize,
+        prefix: G,
+        partial: &Partial<Domain>,
+    ) -> Option<G> {
+        if index == self.levels.len() {
+            return if &a.conjugate_by(&prefix) == b {
+                Some(prefix)
+            } else {
+                None
+            };
+        }
+        let level = &self.levels[index];
+        let base = &level.orbit.base;
+        for point in level.orbit.iter() {
+            let image = prefix.act_on(point);
+            let mut extended = partial.clone();
+            if !extended.force(base, &image, a, b) {
+                continue;
+            }
+            let transversal = level.orbit.representative(point).expect("point in orbit");
+            let product = transversal.times(&prefix);
+            let found = self.conjugating_element(a, b, index + 1, product, &extended);
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+}
+
+/// The images a conjugating element is forced to have so far.
+#[derive(Clone)]
+struct Partial<Domain>
+where
+    Domain: Eq + Hash + Clone,
+{
+    images: HashMap<Domain, Domain>,
+    used: HashSet<Domain>,
+}
+
+impl<Domain> Partial<Domain>
+where
+    Domain: Eq + Hash + Clone,
+{
+    fn new() -> Partial<Domain> {
+        Partial {
+            images: HashMap::new(),
+            used: HashSet::new(),
+        }
+    }
+
+    /// Force `point` to map to `image`, and with it the cycle of `a` through
+    /// `point` onto the cycle of `b` through `image`. Returns whether that is
+    /// consistent with the images forced before.
+    fn force<G>(&mut self, point: &Domain, image: &Domain, a: &G, b: &G) -> bool
+    where
+        G: GroupAction<Domain = Domain>,
+    {
+        let mut current = point.clone();
+        let mut target = image.clone();
+        loop {
+            match self.images.get(&current) {
+                Some(known) => return known == &target,
+                None => {
+                    if !self.used.insert(target.clone()) {
+                        return false;
+                    }
+                    self.images.insert(current.clone(), target.clone());
+                }
+            }
+            current = a.act_on(&current);
+            target = b.act_on(&target);
+        }
+    }
+}
+
+/// The lengths of the cycles of `element` on `gset`, in increasing order.
+pub(super) fn cycle_type<Domain, G>(gset: &[Domain], element: &G) -> Vec<usize>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupAction<Domain = Domain>,
+{
+    let mut visited: HashSet<Domain> = HashSet::new();
+    let mut lengths = vec![];
+    for start in gset {
+        let mut length = 0;
+        let mut current = start.clone();
+        while visited.insert(current.clone()) {
+            current = element.act_on(&current);
+            length += 1;
+        }
+        if length > 0 {
+            lengths.push(length);
+        }
+    }
+    lengths.sort_unstable();
+    lengths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::models;
+    use super::super::permutation::Permutation;
+    use super::*;
+
+    #[test]
+    fn conjugates_should_be_recognized() {
+        let puzzle = models::cube2();
+        let group = Group::with_base(puzzle.gset(), vec![], puzzle.generators());
+        let a = puzzle
+            .sequence("R U R' U'")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+        let f = puzzle
+            .sequence("F U")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+        let b = a.conjugate_by(&f);
+
+        let x = group.are_conjugate(&a, &b).unwrap();
+
+        assert_eq!(a.conjugate_by(&x), b);
+        assert!(group.contains(&x));
+    }
+
+    #[test]
+    fn conjugacy_should_depend_on_the_group() {
+        let symmetric = Group::with_base(
+            vec![0, 1, 2, 3],
+            vec![],
+            vec![
+                permute!(0, 1, 1, 0, 2, 2, 3, 3),
+                permute!(0, 1, 1, 2, 2, 3, 3, 0),
+            ],
+        );
+        let alternating = Group::with_base(
+            vec![0, 1, 2, 3],
+            vec![],
+            vec![
+                permute!(0, 1, 1, 2, 2, 0, 3, 3),
+                permute!(0, 0, 1, 2, 2, 3, 3, 1),
+            ],
+        );
+        let a = permute!(0, 1, 1, 2, 2, 0, 3, 3);
+        let b = a.inverse();
+
+        assert!(symmetric.are_conjugate(&a, &b).is_some());
+        assert_eq!(alternating.are_conjugate(&a, &b), None);
+    }
+
+    #[test]
+    fn different_cycle_types_should_not_be_conjugate() {
+        let group = Group::with_base(
+            vec![0, 1, 2, 3],
+            vec![],
+            vec![
+                permute!(0, 1, 1, 0, 2, 2, 3, 3),
+                permute!(0, 1, 1, 2, 2, 3, 3, 0),
+            ],
+        );
+        let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+        let double = permute!(0, 1, 1, 0, 2, 3, 3, 2);
+
+        assert_eq!(cycle_type(&[0, 1, 2, 3], &double), vec![2, 2]);
+        assert_eq!(group.are_conjugate(&transposition, &double), None);
+    }
+}
@@ -0,0 +1,298 @@
+//! Transitive constituents of a group.
+//!
+//! A group that is not transitive, like the group of a puzzle with corners and
+//! edges, acts on each of its orbits separately. The action on an orbit is a
+//! transitive group, a constituent of the group. Restricting an element to an
+//! orbit is a homomorphism onto the constituent.
+//!
+//! A group is a subgroup of the direct product of its constituents. It can be
+//! a proper subgroup, because the constituents are linked. The group of the
+//! Rubik's cube, for instance, has index 2 in the direct product of its corner
+//! and edge constituents: corners and edges are always permuted with the same
+//! parity. `Group::intransitive_decomposition` reports the orbits, the
+//! constituents and this linkage. The elements that act trivially outside an
+//! orbit form the kernel of the projection onto the other constituents; the
+//! smaller it is compared to the constituent, the stronger the linkage.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let rotation = permute!(0, 1, 1, 2, 2, 0, 3, 4, 4, 3);
+//! let group = Group::with_base(vec![0, 1, 2, 3, 4], vec![], vec![rotation.clone()]);
+//!
+//! let (constituent, projection) = group.restrict_to_orbit(&[3, 4]);
+//!
+//! assert_eq!(constituent.order(), 2);
+//! assert_eq!(projection(&rotation), permute!(0, 1, 1, 0));
+//! # }
+//! ```
+
+use super::permutation::Permutation;
+use super::{orbits, Group, GroupAction, GroupElement};
+use num_bigint::BigUint;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// The orbits of this group on its gset. Orbits and the points in them
+    /// follow the order of the gset.
+    pub fn orbits(&self) -> Vec<Vec<Domain>> {
+        orbits(&self.gset, &self.generators())
+    }
+
+    /// The action of this group on `orbit`, with the point at index `i`
+    /// relabeled to `i`, and the homomorphism that maps every element of this
+    /// group to its restriction.
+    ///
+    /// Panics when `orbit` is not mapped onto itself.
+    pub fn restrict_to_orbit(
+        &self,
+        orbit: &[Domain],
+    ) -> (Group<u64, Permutation>, impl Fn(&G) -> Permutation) {
+        let gset: Vec<u64> = (0..orbit.len() as u64).collect();
+        let generators: Vec<Permutation> = self
+            .generators()
+            .iter()
+            .map(|generator| restriction(orbit, generator))
+            .collect();
+        let orbit = orbit.to_vec();
+        (
+            Group::with_base(gset, vec![], generators),
+            move |element: &G| restriction(&orbit, element),
+        )
+    }
+
+    /// The orbits of this group, its constituents and how it sits inside the
+    /// direct product of the constituents.
+    pub fn intransitive_decomposition(&self) -> Decomposition<Domain> {
+        let constituents = self
+            .orbits()
+            .into_iter()
+            .map(|orbit| {
+                let (group, _) = self.restrict_to_orbit(&orbit);
+                let kernel_order = self.pointwise_stabilizer_order(&orbit);
+                Constituent {
+                    orbit,
+                    group,
+                    kernel_order,
+                }
+            })
+            .collect();
+        Decomposition {
+            order: self.big_order(),
+            constituents,
+        }
+    }
+
+    /// The order of the subgroup that fixes every point outside of `orbit`.
+    fn pointwise_stabilizer_order(&self, orbit: &[Domain]) -> BigUint {
+        let inside: HashSet<&Domain> = orbit.iter().collect();
+        let outside: Vec<Domain> = self
+            .gset
+            .iter()
+            .filter(|point| !inside.contains(point))
+            .cloned()
+            .collect();
+        self.change_base(outside)
+            .levels
+            .iter()
+            .filter(|level| inside.contains(&level.orbit.base))
+            .fold(BigUint::from(1u32), |order, level| {
+                order * level.orbit.len()
+            })
+    }
+}
+
+/// How a group decomposes over its orbits.
+pub struct Decomposition<Domain> {
+    order: BigUint,
+    constituents: Vec<Constituent<Domain>>,
+}
+
+impl<Domain> Decomposition<Domain> {
+    /// The order of the group.
+    pub fn order(&self) -> &BigUint {
+        &self.order
+    }
+
+    /// A constituent for every orbit, in the order of the gset.
+    pub fn constituents(&self) -> &[Constituent<Domain>] {
+        &self.constituents
+    }
+
+    /// The order of the direct product of the constituents.
+    pub fn direct_product_order(&self) -> BigUint {
+        self.constituents
+            .iter()
+            .map(|constituent| constituent.group.big_order())
+            .product()
+    }
+
+    /// The index of the group in the direct product of its constituents,
+    /// which is 1 when the constituents are not linked.
+    pub fn index(&self) -> BigUint {
+        self.direct_product_order() / &self.order
+    }
+}
+
+impl<Domain> Display for Decomposition<Domain> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for constituent in &self.constituents {
+            writeln!(
+                f,
+                "orbit of {} points: constituent of order {}, kernel of order {}",
+                constituent.orbit.len(),
+                constituent.group.big_order(),
+                constituent.kernel_order
+            )?;
+        }
+        writeln!(
+            f,
+            "order {}, index {} in the direct product",
+            self.order,
+            self.index()
+        )
+    }
+}
+
+/// The action of a group on one of its orbits.
+pub struct Constituent<Domain> {
+    orbit: Vec<Domain>,
+    group: Group<u64, Permutation>,
+    kernel_order: BigUint,
+}
+
+impl<Domain> Constituent<Domain> {
+    /// The points of the orbit. The point at index `i` is relabeled to `i`.
+    pub fn orbit(&self) -> &[Domain] {
+        &self.orbit
+    }
+
+    /// The transitive group the group acts as on the orbit.
+    pub fn group(&self) -> &Group<u64, Permutation> {
+        &self.group
+    }
+
+    /// The order of the subgroup of elements that fix every point outside the
+    /// orbit.
+    pub fn kernel_order(&self) -> &BigUint {
+        &self.kernel_order
+    }
+}
+
+/// The permutation of the indices of `orbit` that `element` induces.
+///
+/// Panics when `element` maps a point of `orbit` outside of it.
+fn restriction<Domain, G>(orbit: &[Domain], element: &G) -> Permutation
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupAction<Domain = Domain>,
+{
+    let index: HashMap<&Domain, u64> = orbit
+        .iter()
+        .enumerate()
+        .map(|(i, point)| (point, i as u64))
+        .collect();
+    let images: HashMap<u64, u64> = orbit
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            let image = element.act_on(point);
+            (
+                i as u64,
+                *index.get(&image).expect("orbit should be invariant"),
+            )
+        })
+        .collect();
+    Permutation::new(images)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::models;
+    use super::*;
+
+    #[test]
+    fn constituents_of_a_cube_should_be_corners_and_edges() {
+        let puzzle = models::cube3();
+        let group = Group::with_base(puzzle.gset(), vec![], puzzle.generators());
+        let orbits = group.orbits();
+        let r = puzzle.move_named("R").unwrap();
+        let u = puzzle.move_named("U").unwrap();
+
+        let constituents: Vec<_> = orbits
+            .iter()
+            .map(|orbit| group.restrict_to_orbit(orbit))
+            .collect();
+
+        let orders: Vec<u128> = constituents
+            .iter()
+            .map(|(constituent, _)| constituent.order())
+            .collect();
+        assert_eq!(orders, vec![88_179_840, 980_995_276_800]);
+        for (constituent, projection) in &constituents {
+            assert!(constituent.contains(&projection(&r.times(u))));
+            assert_eq!(projection(&r.times(u)), projection(r).times(&projection(u)));
+        }
+    }
+
+    #[test]
+    fn decomposition_should_reveal_linked_constituents() {
+        let linked = Group::with_base(
+            vec![0, 1, 2, 3],
+            vec![],
+            vec![permute!(0, 1, 1, 0, 2, 3, 3, 2)],
+        );
+        let independent = Group::with_base(
+            vec![0, 1, 2, 3, 4],
+            vec![],
+            vec![permute!(0, 1, 1, 2, 2, 0, 3, 4, 4, 3)],
+        );
+
+        let decomposition = linked.intransitive_decomposition();
+        let kernels: Vec<BigUint> = independent
+            .intransitive_decomposition()
+            .constituents()
+            .iter()
+            .map(|constituent| constituent.kernel_order().clone())
+            .collect();
+
+        assert_eq!(decomposition.constituents().len(), 2);
+        assert_eq!(decomposition.index(), BigUint::from(2u32));
+        assert_eq!(
+            *decomposition.constituents()[0].kernel_order(),
+            BigUint::from(1u32)
+        );
+        assert_eq!(
+            independent.intransitive_decomposition().index(),
+            BigUint::from(1u32)
+        );
+        assert_eq!(kernels, vec![BigUint::from(3u32), BigUint::from(2u32)]);
+        assert_eq!(
+            decomposition.to_string(),
+            "orbit of 2 points: constituent of order 2, kernel of order 1\n\
+             orbit of 2 points: constituent of order 2, kernel of order 1\n\
+             order 2, index 2 in the direct product\n"
+        );
+    }
+
+    #[test]
+    fn restriction_should_relabel_the_orbit() {
+        let element = permute!(5u64, 7u64, 7u64, 9u64, 9u64, 5u64);
+
+        assert_eq!(
+            restriction(&[9u64, 7u64, 5u64], &element),
+            permute!(0, 2, 2, 1, 1, 0)
+        );
+    }
+}
@@ -0,0 +1,373 @@
+//! [Cosets](https://en.wikipedia.org/wiki/Coset) of a subgroup.
+//!
+//! The left coset _gH_ of a subgroup _H_ consists of the products _gh_. Two
+//! elements _g_ and _k_ lie in the same left coset when _g_^-1 _k_ is a member
+//! of _H_. The left cosets partition the group into _|G|_/_|H|_ parts. Right
+//! cosets _Hg_ are defined likewise.
+//!
+//! A `Cosets` holds one representative per coset, found by multiplying the
+//! known representatives with the generators of the group. Labeling an element
+//! compares it with each representative in turn, so this is meant for
+//! subgroups of small index.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2);
+//! let rotation = permute!(0, 1, 1, 2, 2, 0);
+//! let s3 = Group::with_base(vec![0, 1, 2], vec![], vec![transposition.clone(), rotation]);
+//! let subgroup = Group::with_base(vec![0, 1, 2], vec![], vec![transposition]);
+//!
+//! let cosets = s3.left_cosets(&subgroup);
+//!
+//! assert_eq!(cosets.len(), 3);
+//! # }
+//! ```
+//!
+//! The group acts on the right cosets _Hg_ by multiplication on the right. The
+//! Schreier coset graph draws this action: there is a vertex for every coset
+//! and an edge from _Hg_ to _Hgs_ for every generator _s_.
+//!
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2);
+//! let rotation = permute!(0, 1, 1, 2, 2, 0);
+//! let s3 = Group::with_base(vec![0, 1, 2], vec![], vec![transposition.clone(), rotation]);
+//! let subgroup = Group::with_base(vec![0, 1, 2], vec![], vec![transposition]);
+//!
+//! let graph = s3.coset_graph(&subgroup);
+//!
+//! assert_eq!(graph.target(0, 0), 0);
+//! assert_eq!(graph.action().len(), 2);
+//! # }
+//! ```
+
+use super::permutation::Permutation;
+use super::{Group, GroupAction, GroupElement};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Representatives of the cosets of a subgroup.
+pub struct Cosets<'a, Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
+{
+    subgroup: &'a Group<Domain, G>,
+    side: Side,
+    representatives: Vec<G>,
+}
+
+/// The side of a coset the representative is on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Side {
+    Left,
+    Right,
+}
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// The left cosets _gH_ of `subgroup`, which should be a subgroup of this
+    /// group.
+    pub fn left_cosets<'a>(&self, subgroup: &'a Group<Domain, G>) -> Cosets<'a, Domain, G> {
+        self.cosets(subgroup, Side::Left)
+    }
+
+    /// The right cosets _Hg_ of `subgroup`, which should be a subgroup of this
+    /// group.
+    pub fn right_cosets<'a>(&self, subgroup: &'a Group<Domain, G>) -> Cosets<'a, Domain, G> {
+        self.cosets(subgroup, Side::Right)
+    }
+
+    /// The Schreier coset graph of the right cosets of `subgroup`, which
+    /// should be a subgroup of this group, with an edge for every generator of
+    /// this group.
+    pub fn coset_graph(&self, subgroup: &Group<Domain, G>) -> CosetGraph<G> {
+        let generators = self.generators();
+        let cosets = self.right_cosets(subgroup);
+        let targets = cosets
+            .representatives()
+            .iter()
+            .map(|representative| {
+                generators
+                    .iter()
+                    .map(|generator| {
+                        cosets
+                            .coset_of(&representative.times(generator))
+                            .expect("product in group")
+                    })
+                    .collect()
+            })
+            .collect();
+        CosetGraph {
+            representatives: cosets.representatives,
+            targets,
+        }
+    }
+
+    fn cosets<'a>(&self, subgroup: &'a Group<Domain, G>, side: Side) -> Cosets<'a, Domain, G> {
+        let generators = self.generators();
+        let mut cosets = Cosets {
+            subgroup,
+            side,
+            representatives: vec![G::identity()],
+        };
+        let mut index = 0;
+        while index < cosets.representatives.len() {
+            for generator in &generators {
+                let candidate = match side {
+                    Side::Left => generator.times(&cosets.representatives[index]),
+                    Side::Right => cosets.representatives[index].times(generator),
+                };
+                if cosets.coset_of(&candidate).is_none() {
+                    cosets.representatives.push(candidate);
+                }
+            }
+            index += 1;
+        }
+        cosets
+    }
+}
+
+impl<'a, Domain, G> Cosets<'a, Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// The number of cosets, i.e. the index of the subgroup.
+    pub fn len(&self) -> usize {
+        self.representatives.len()
+    }
+
+    /// There is always the coset of the identity, so this is never empty.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// One representative per coset. The first is the identity.
+    pub fn representatives(&self) -> &[G] {
+        &self.representatives
+    }
+
+    /// The index of the coset that contains `g`, or `None` when `g` is not a
+    /// member of the group.
+    pub fn coset_of(&self, g: &G) -> Option<usize> {
+        self.representatives.iter().position(|representative| {
+            let quotient = match self.side {
+                Side::Left => representative.inverse().times(g),
+                Side::Right => g.times(&representative.inverse()),
+            };
+            self.subgroup.contains(&quotient)
+        })
+    }
+}
+
+/// The Schreier coset graph of a subgroup _H_: a vertex for every right coset
+/// _Hg_ and an edge from _Hg_ to _Hgs_ for every generator _s_.
+pub struct CosetGraph<G> {
+    representatives: Vec<G>,
+    targets: Vec<Vec<usize>>,
+}
+
+impl<G> CosetGraph<G> {
+    /// The number of vertices, i.e. the index of the subgroup.
+    pub fn len(&self) -> usize {
+        self.representatives.len()
+    }
+
+    /// There is always the coset of the identity, so this is never empty.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// One representative per coset. The first is the identity.
+    pub fn representatives(&self) -> &[G] {
+        &self.representatives
+    }
+
+    /// The coset the edge of the generator at index `generator` leads to from
+    /// the coset at index `coset`.
+    pub fn target(&self, coset: usize, generator: usize) -> usize {
+        self.targets[coset][generator]
+    }
+
+    /// The edges as `(from, generator, to)`, with cosets and generators
+    /// referred to by index.
+    pub fn edges(&self) -> Vec<(usize, usize, usize)> {
+        self.targets
+            .iter()
+            .enumerate()
+            .flat_map(|(from, targets)| {
+                targets
+                    .iter()
+                    .enumerate()
+                    .map(move |(generator, &to)| (from, generator, to))
+            })
+            .collect()
+    }
+
+    /// For every generator, the permutation of the cosets it induces. The
+    /// cosets are numbered by their index.
+    pub fn action(&self) -> Vec<Permutation> {
+        let generators = self.targets.first().map_or(0, |targets| targets.len());
+        (0..generators)
+            .map(|generator| {
+                let images: HashMap<u64, u64> = self
+                    .targets
+                    .iter()
+                    .enumerate()
+                    .map(|(from, targets)| (from as u64, targets[generator] as u64))
+                    .collect();
+                Permutation::new(images)
+            })
+            .collect()
+    }
+
+    /// The graph in the [DOT](https://graphviz.org/doc/info/lang.html)
+    /// language. Vertices are labeled with the index of their coset, edges
+    /// with the index of their generator.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph cosets {\n");
+        for index in 0..self.len() {
+            dot.push_str(&format!("  {} [label=\"{}\"];\n", index, index));
+        }
+        for (from, generator, to) in self.edges() {
+            dot.push_str(&format!(
+                "  {} -> {} [label=\"{}\"];\n",
+                from, to, generator
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone + Ord,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// The canonical representative of the right coset _Hg_ of this group
+    /// _H_: the member of the coset that maps the base points of _H_, in turn,
+    /// to the smallest points possible.
+    ///
+    /// Two elements have the same representative exactly when they lie in the
+    /// same right coset, so it labels a coset without listing them all.
+    pub fn right_coset_representative(&self, g: &G) -> G {
+        let mut representative = g.clone();
+        for level in &self.levels {
+            let best = level
+                .orbit
+                .iter()
+                .min_by_key(|point| representative.act_on(point))
+                .expect("an orbit contains its base point")
+                .clone();
+            if best != level.orbit.base {
+                let transversal = level
+                    .orbit
+                    .representative(&best)
+                    .expect("orbit points have a transversal");
+                representative = transversal.times(&representative);
+            }
+        }
+        representative
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::permutation::Permutation;
+    use super::*;
+
+    fn s3() -> (Group<u64, Permutation>, Group<u64, Permutation>) {
+        let transposition = permute!(0, 1, 1, 0, 2, 2);
+        let rotation = permute!(0, 1, 1, 2, 2, 0);
+        let group = Group::with_base(vec![0, 1, 2], vec![], vec![transposition.clone(), rotation]);
+        let subgroup = Group::with_base(vec![0, 1, 2], vec![], vec![transposition]);
+        (group, subgroup)
+    }
+
+    #[test]
+    fn cosets_should_partition_the_group() {
+        let (group, subgroup) = s3();
+
+        for cosets in &[group.left_cosets(&subgroup), group.right_cosets(&subgroup)] {
+            let mut sizes = vec![0; cosets.len()];
+            for element in group.elements() {
+                sizes[cosets.coset_of(&element).unwrap()] += 1;
+            }
+            assert_eq!(sizes, vec![2, 2, 2]);
+        }
+    }
+
+    #[test]
+    fn left_and_right_cosets_should_differ_for_non_normal_subgroups() {
+        let (group, subgroup) = s3();
+        let left = group.left_cosets(&subgroup);
+        let right = group.right_cosets(&subgroup);
+        let rotation = permute!(0, 1, 1, 2, 2, 0);
+        let other = permute!(0, 2, 1, 1, 2, 0);
+
+        assert_eq!(
+            left.coset_of(&rotation) == left.coset_of(&other),
+            right.coset_of(&rotation) != right.coset_of(&other)
+        );
+    }
+
+    #[test]
+    fn coset_graph_should_give_the_action_on_cosets() {
+        let (group, subgroup) = s3();
+
+        let graph = group.coset_graph(&subgroup);
+        let action = Group::with_base(vec![0, 1, 2], vec![], graph.action());
+
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.edges().len(), 6);
+        assert_eq!(action.order(), 6);
+    }
+
+    #[test]
+    fn right_coset_representatives_should_label_the_cosets() {
+        let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+        let group = Group::with_base(
+            vec![0, 1, 2, 3],
+            vec![],
+            vec![transposition.clone(), rotation],
+        );
+        let subgroup = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition]);
+        let cosets = group.right_cosets(&subgroup);
+
+        let mut labels: HashMap<Permutation, usize> = HashMap::new();
+        for element in group.elements() {
+            let representative = subgroup.right_coset_representative(&element);
+            let coset = cosets.coset_of(&element).unwrap();
+            assert_eq!(cosets.coset_of(&representative), Some(coset));
+            assert_eq!(*labels.entry(representative).or_insert(coset), coset);
+        }
+        assert_eq!(labels.len(), 12);
+    }
+
+    #[test]
+    fn dot_should_draw_the_coset_graph() {
+        let rotation = permute!(0, 1, 1, 2, 2, 0);
+        let group = Group::with_base(vec![0, 1, 2], vec![], vec![rotation]);
+        let trivial = Group::with_base(vec![0, 1, 2], vec![], vec![]);
+
+        assert_eq!(
+            group.coset_graph(&trivial).to_dot(),
+            "digraph cosets {\n  0 [label=\"0\"];\n  1 [label=\"1\"];\n  2 [label=\"2\"];\n  0 -> 1 [label=\"0\"];\n  1 -> 2 [label=\"0\"];\n  2 -> 0 [label=\"0\"];\n}\n"
+        );
+    }
+}
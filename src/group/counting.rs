@@ -0,0 +1,162 @@
+//! Count orbits with [Burnside's lemma](https://en.wikipedia.org/wiki/Burnside%27s_lemma).
+//!
+//! The number of orbits of a group acting on a set equals the average number of
+//! points that an element of the group fixes. This answers questions like "how
+//! many different necklaces of six beads in two colors are there?"
+//!
+//! Conjugate elements fix equally many points, so the average is taken over
+//! the conjugacy classes, weighted by their size. Finding the classes still
+//! runs through all elements of the group, so these functions are meant for
+//! small groups.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # extern crate num_bigint;
+//! # use num_bigint::BigUint;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::counting::count_colorings;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 0);
+//! let necklaces = Group::with_base(vec![0, 1, 2, 3, 4, 5], vec![], vec![rotation]);
+//!
+//! assert_eq!(count_colorings(&necklaces, 2), BigUint::from(14u32));
+//! # }
+//! ```
+
+use super::{Group, GroupAction, GroupElement};
+use num_bigint::BigUint;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// The points of `gset` that `g` fixes.
+pub fn fixed_points<Domain, G>(g: &G, gset: &[Domain]) -> Vec<Domain>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupAction<Domain = Domain>,
+{
+    gset.iter()
+        .filter(|point| &g.act_on(point) == *point)
+        .cloned()
+        .collect()
+}
+
+/// Count the orbits of `group` on `objects`, where `action` tells how an
+/// element acts on an object.
+///
+/// The objects should be closed under the action.
+pub fn count_orbits_on<Domain, G, T, F>(group: &Group<Domain, G>, objects: &[T], action: F) -> u128
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + Eq + Hash + Clone,
+    T: PartialEq,
+    F: Fn(&G, &T) -> T,
+{
+    let fixed: u128 = group
+        .conjugacy_classes()
+        .iter()
+        .map(|class| {
+            let fixed = objects
+                .iter()
+                .filter(|object| &action(&class.representative, object) == *object)
+                .count();
+            class.size as u128 * fixed as u128
+        })
+        .sum();
+    fixed / group.order()
+}
+
+/// Count the ways to color the gset of `group` with `colors` colors, where
+/// colorings that the group maps onto each other are the same.
+///
+/// A coloring is fixed by an element when each of its cycles has a single
+/// color, so an element with `c` cycles fixes `colors^c` colorings. That
+/// number grows quickly, so the count is a `BigUint`.
+pub fn count_colorings<Domain, G>(group: &Group<Domain, G>, colors: u64) -> BigUint
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + Eq + Hash + Clone,
+{
+    let fixed: BigUint = group
+        .conjugacy_classes()
+        .iter()
+        .map(|class| {
+            BigUint::from(class.size)
+                * BigUint::from(colors).pow(cycle_count(&class.representative, &group.gset))
+        })
+        .sum();
+    fixed / group.big_order()
+}
+
+fn cycle_count<Domain, G>(g: &G, gset: &[Domain]) -> u32
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupAction<Domain = Domain>,
+{
+    let mut visited: HashSet<Domain> = HashSet::new();
+    let mut cycles = 0;
+    for start in gset {
+        if visited.contains(start) {
+            continue;
+        }
+        cycles += 1;
+        let mut current = start.clone();
+        while visited.insert(current.clone()) {
+            current = g.act_on(&current);
+        }
+    }
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::permutation::Permutation;
+    use super::*;
+    use std::collections::HashMap;
+
+    fn dihedral6() -> Group<u64, Permutation> {
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 0);
+        let reflection = permute!(0, 0, 1, 5, 2, 4, 3, 3, 4, 2, 5, 1);
+        Group::with_base(vec![0, 1, 2, 3, 4, 5], vec![], vec![rotation, reflection])
+    }
+
+    #[test]
+    fn fixed_points_should_list_the_points_that_do_not_move() {
+        let reflection = permute!(0, 0, 1, 5, 2, 4, 3, 3, 4, 2, 5, 1);
+
+        assert_eq!(fixed_points(&reflection, &[0, 1, 2, 3, 4, 5]), vec![0, 3]);
+    }
+
+    #[test]
+    fn count_colorings_should_count_bracelets() {
+        assert_eq!(count_colorings(&dihedral6(), 2), BigUint::from(13u32));
+        assert_eq!(count_colorings(&dihedral6(), 3), BigUint::from(92u32));
+    }
+
+    #[test]
+    fn count_colorings_should_not_overflow() {
+        let trivial: Group<u64, Permutation> = Group::with_base((0..100).collect(), vec![], vec![]);
+
+        assert_eq!(count_colorings(&trivial, 3), BigUint::from(3u32).pow(100));
+    }
+
+    #[test]
+    fn count_orbits_on_should_count_orbits_of_pairs() {
+        let group = dihedral6();
+        let mut pairs: Vec<(u64, u64)> = vec![];
+        for a in 0..6 {
+            for b in (a + 1)..6 {
+                pairs.push((a, b));
+            }
+        }
+
+        let orbits = count_orbits_on(&group, &pairs, |g, &(a, b)| {
+            let (x, y) = (g.act_on(&a), g.act_on(&b));
+            (x.min(y), x.max(y))
+        });
+
+        assert_eq!(orbits, 3);
+    }
+}
@@ -0,0 +1,191 @@
+//! Encode group elements as a sequence of orbit indices.
+//!
+//! Stripping an element divides out one transversal per level. Each
+//! transversal belongs to a point of the orbit of that level, so the element is
+//! determined by the indices of those points in their orbits. The indices form
+//! a mixed radix number, with the orbit lengths as radices, which makes a
+//! compact encoding of the element. Its value, the rank, numbers the elements
+//! from 0 up to the order of the group.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let group = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation.clone()]);
+//!
+//! let code = group.encode(&rotation).unwrap();
+//!
+//! assert_eq!(code.len(), 3);
+//! assert_eq!(group.decode(&code), Some(rotation.clone()));
+//! assert_eq!(group.unrank(group.rank(&rotation).unwrap()), Some(rotation));
+//! # }
+//! ```
+
+use super::{Group, GroupAction, GroupElement};
+use std::hash::Hash;
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
+{
+    /// The index in the orbit of each level of the point that `element` maps
+    /// the base point to after stripping the levels before it, or `None` when
+    /// `element` is not a member of this group.
+    ///
+    /// Orbit points are indexed in the order they were found.
+    pub fn encode(&self, element: &G) -> Option<Vec<u32>> {
+        let mut code: Vec<u32> = vec![];
+        let mut residue: Option<G> = None;
+        for level in &self.levels {
+            let point = {
+                let current = residue.as_ref().unwrap_or(element);
+                current.act_on(&level.orbit.base)
+            };
+            let index = level.orbit.iter().position(|p| p == &point)?;
+            code.push(index as u32);
+            if point != level.orbit.base {
+                let transversal = level.orbit.representative(&point)?;
+                let current = residue.as_ref().unwrap_or(element);
+                residue = Some(current.times(&transversal.inverse()));
+            }
+        }
+        let is_member = residue.as_ref().unwrap_or(element).is_identity();
+        if is_member {
+            Some(code)
+        } else {
+            None
+        }
+    }
+
+    /// The element with `code` as encoding, or `None` when `code` does not
+    /// encode an element of this group.
+    pub fn decode(&self, code: &[u32]) -> Option<G> {
+        if code.len() != self.levels.len() {
+            return None;
+        }
+        let mut transversals: Vec<G> = vec![];
+        for (level, &index) in self.levels.iter().zip(code) {
+            let point = level.orbit.iter().nth(index as usize)?;
+            transversals.push(level.orbit.representative(point)?);
+        }
+        Some(
+            transversals
+                .into_iter()
+                .rev()
+                .fold(G::identity(), |product, transversal| {
+                    product.times(&transversal)
+                }),
+        )
+    }
+
+    /// The position of `element` in 0..|_G_|, or `None` when it is not a
+    /// member of this group.
+    ///
+    /// The rank is the encoding read as a mixed radix number, with the index of
+    /// the first level as the most significant digit.
+    pub fn rank(&self, element: &G) -> Option<u128> {
+        let code = self.encode(element)?;
+        Some(
+            code.iter()
+                .zip(&self.levels)
+                .fold(0u128, |rank, (&index, level)| {
+                    rank * level.length() as u128 + u128::from(index)
+                }),
+        )
+    }
+
+    /// The element with rank `rank`, or `None` when `rank` is not below the
+    /// order of this group.
+    pub fn unrank(&self, rank: u128) -> Option<G> {
+        let mut remainder = rank;
+        let mut code: Vec<u32> = vec![0; self.levels.len()];
+        for (digit, level) in code.iter_mut().zip(&self.levels).rev() {
+            let radix = level.length() as u128;
+            *digit = (remainder % radix) as u32;
+            remainder /= radix;
+        }
+        if remainder != 0 {
+            return None;
+        }
+        self.decode(&code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::models;
+    use super::super::permutation::Permutation;
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn encoding_should_round_trip() {
+        let puzzle = models::cube2();
+        let group = Group::with_base(puzzle.gset(), vec![], puzzle.generators());
+        let element = puzzle
+            .sequence("R U2 F' R U'")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+
+        let code = group.encode(&element).unwrap();
+
+        assert_eq!(code.len(), group.levels.len());
+        for (index, level) in code.iter().zip(&group.levels) {
+            assert!((*index as usize) < level.length());
+        }
+        assert_eq!(group.decode(&code), Some(element));
+    }
+
+    #[test]
+    fn encodings_should_be_distinct() {
+        let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+        let group = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation]);
+
+        let mut codes: Vec<Vec<u32>> = group
+            .elements()
+            .iter()
+            .map(|element| group.encode(element).unwrap())
+            .collect();
+        codes.sort();
+        codes.dedup();
+
+        assert_eq!(codes.len(), 24);
+    }
+
+    #[test]
+    fn ranks_should_number_the_elements() {
+        let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+        let group = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation]);
+
+        let ranks: Vec<u128> = (0..24)
+            .map(|rank| {
+                let element = group.unrank(rank).unwrap();
+                group.rank(&element).unwrap()
+            })
+            .collect();
+
+        assert_eq!(ranks, (0..24).collect::<Vec<u128>>());
+        assert_eq!(group.unrank(24), None);
+    }
+
+    #[test]
+    fn non_members_should_have_no_encoding() {
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+        let group = Group::with_base(vec![0, 1, 2, 3], vec![], vec![rotation]);
+        let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+
+        assert_eq!(group.encode(&transposition), None);
+        assert_eq!(group.rank(&transposition), None);
+        assert_eq!(group.decode(&[4]), None);
+        assert_eq!(group.decode(&[0, 0]), None);
+    }
+}
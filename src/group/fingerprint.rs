@@ -0,0 +1,168 @@
+//! A fingerprint of a stabilizer chain.
+//!
+//! The fingerprint of a group hashes its base, the orbit lengths of its levels
+//! and the strong generators of every level. Two runs of a construction that
+//! produce the same chain produce the same fingerprint, so a cache can tell
+//! whether a stored group is still valid, and a test whether a change to the
+//! construction changed its outcome.
+//!
+//! The strong generators of a level are hashed one by one and the hashes are
+//! sorted, so the order in which they were found does not matter. The hash is
+//! [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/) over the little endian
+//! bytes of the integers, so the fingerprint is the same on every platform and
+//! with every version of Rust, and can be stored along with a group.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2);
+//! let rotation = permute!(0, 1, 1, 2, 2, 0);
+//! let group = Group::with_base(vec![0, 1, 2], vec![], vec![transposition.clone(), rotation.clone()]);
+//! let again = Group::with_base(vec![0, 1, 2], vec![], vec![transposition, rotation]);
+//!
+//! assert_eq!(group.fingerprint(), again.fingerprint());
+//! # }
+//! ```
+
+use super::{Group, GroupAction, GroupElement};
+use std::hash::{Hash, Hasher};
+
+/// The offset basis of 64 bit FNV-1a.
+const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// The prime of 64 bit FNV-1a.
+const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Hash,
+{
+    /// A hash of the base, the orbit lengths and the strong generators of this
+    /// group.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a::new();
+        hasher.write_usize(self.levels.len());
+        for level in &self.levels {
+            level.orbit.base.hash(&mut hasher);
+            hasher.write_usize(level.orbit.len());
+            let mut generators: Vec<u64> = level
+                .orbit
+                .generators
+                .iter()
+                .map(|generator| {
+                    let mut hasher = Fnv1a::new();
+                    generator.hash(&mut hasher);
+                    hasher.finish()
+                })
+                .collect();
+            generators.sort_unstable();
+            hasher.write_usize(generators.len());
+            for generator in generators {
+                hasher.write_u64(generator);
+            }
+        }
+        hasher.finish()
+    }
+}
+
+/// The 64 bit FNV-1a hash. Integers are written as little endian bytes, and a
+/// `usize` as a `u64`, so the hash does not depend on the platform.
+pub struct Fnv1a(u64);
+
+impl Fnv1a {
+    /// A hasher that has not hashed anything yet.
+    pub fn new() -> Fnv1a {
+        Fnv1a(OFFSET_BASIS)
+    }
+}
+
+impl Default for Fnv1a {
+    fn default() -> Fnv1a {
+        Fnv1a::new()
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(PRIME);
+        }
+    }
+
+    fn write_u16(&mut self, n: u16) {
+        self.write(&n.to_le_bytes())
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.write(&n.to_le_bytes())
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.write(&n.to_le_bytes())
+    }
+
+    fn write_u128(&mut self, n: u128) {
+        self.write(&n.to_le_bytes())
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::permutation::Permutation;
+    use super::*;
+    use std::collections::HashMap;
+
+    fn s3(generators: Vec<Permutation>) -> Group<u64, Permutation> {
+        Group::with_base(vec![0, 1, 2], vec![], generators)
+    }
+
+    #[test]
+    fn fingerprint_should_ignore_the_order_of_strong_generators() {
+        let transposition = permute!(0, 1, 1, 0, 2, 2);
+        let rotation = permute!(0, 1, 1, 2, 2, 0);
+
+        let forward = s3(vec![transposition.clone(), rotation.clone()]);
+        let backward = s3(vec![rotation, transposition]);
+
+        assert_eq!(forward.fingerprint(), backward.fingerprint());
+    }
+
+    #[test]
+    fn fingerprint_should_not_depend_on_the_platform() {
+        let mut hasher = Fnv1a::new();
+        hasher.write(b"a");
+
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(
+            s3(vec![permute!(0, 1, 1, 2, 2, 0)]).fingerprint(),
+            2_699_153_581_329_085_261
+        );
+    }
+
+    #[test]
+    fn fingerprint_should_tell_chains_apart() {
+        let transposition = permute!(0, 1, 1, 0, 2, 2);
+        let rotation = permute!(0, 1, 1, 2, 2, 0);
+
+        let symmetric = s3(vec![transposition.clone(), rotation.clone()]);
+        let cyclic = s3(vec![rotation.clone()]);
+        let other_base = Group::with_base(vec![0, 1, 2], vec![2], vec![transposition, rotation]);
+
+        assert_ne!(symmetric.fingerprint(), cyclic.fingerprint());
+        assert_ne!(symmetric.fingerprint(), other_base.fingerprint());
+    }
+}
@@ -17,103 +17,496 @@
 //! let expected = Word::new(vec![('a', 1), ('b', 2), ('c', 1)]);
 //! assert_eq!(answer, expected);
 //! ```
+//!
+//! A `Notation` writes a word the way puzzle users write moves.
+//!
+//! ```rust
+//! # use permutation_rs::group::free::{Notation, Word};
+//! let word = Word::new(vec![('r', -2), ('t', 1)]);
+//! let names = vec![('r', String::from("R")), ('t', String::from("T"))];
+//!
+//! let notation = Notation::new().with_names(names.into_iter().collect());
+//!
+//! assert_eq!(word.format_with(&notation), "R2' T");
+//! ```
+//!
+//! A `Metric` counts the moves of a word the way cubers do. In the half turn
+//! metric a half turn counts as one move, in the quarter turn metric as two.
+//!
+//! ```rust
+//! # use permutation_rs::group::free::{HalfTurn, QuarterTurn, Word};
+//! let word = Word::new(vec![('R', 2), ('U', -1)]);
+//!
+//! assert_eq!(word.length_in(&HalfTurn::new()), 2);
+//! assert_eq!(word.length_in(&QuarterTurn::new()), 3);
+//! ```
+//!
+//! Words with `char` symbols are parsed from the way they are displayed. White
+//! space between syllables is ignored.
+//!
+//! ```rust
+//! # use permutation_rs::group::free::Word;
+//! let word: Word = "x^2y^-3 x^-2 y^3".parse().unwrap();
+//!
+//! assert_eq!(word.to_string(), "x^2y^-3x^-2y^3");
+//! ```
 use super::GroupElement;
+#[cfg(any(test, feature = "arbitrary"))]
+use arbitrary::{Arbitrary, Unstructured};
+use std::collections::{HashMap, HashSet};
+use std::error;
 use std::fmt;
 use std::fmt::Display;
+use std::hash::Hash;
+use std::iter::Peekable;
+use std::slice::Iter;
+use std::str::{CharIndices, FromStr};
 
 /// The element of a free group.
+///
+/// The symbols of a word can be of any type, e.g. `String` move names like
+/// `"R"` or `"Rw2"`. When no symbol type is specified, `char` is used.
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
-pub struct Word {
-    terms: Vec<(char, i64)>,
+pub struct Word<S = char>
+where
+    S: Eq + Hash + Clone,
+{
+    terms: Vec<(S, i64)>,
 }
 
-impl Word {
+impl<S> Word<S>
+where
+    S: Eq + Hash + Clone,
+{
     /// Create the identity element in a free group.
-    pub fn identity() -> Word {
+    pub fn identity() -> Word<S> {
         Word::new(vec![])
     }
 
     /// Constructor which creates a single generator.
-    pub fn generator(symbol: char) -> Word {
+    pub fn generator(symbol: S) -> Word<S> {
         Word::new(vec![(symbol, 1)])
     }
 
     /// Create a word with prescribed characters.
-    pub fn new(elements: Vec<(char, i64)>) -> Word {
+    pub fn new(elements: Vec<(S, i64)>) -> Word<S> {
         Word {
             terms: normalize(&elements),
         }
     }
-}
 
-fn normalize(elements: &Vec<(char, i64)>) -> Vec<(char, i64)> {
-    let mut not_normalized: Vec<(char, i64)> = vec![];
-    not_normalized.extend(elements);
-
-    if not_normalized.len() <= 1 {
-        not_normalized
-    } else {
-        let mut normalized: Vec<(char, i64)> = vec![];
-        let mut current: (char, i64) = not_normalized
-            .get(0)
-            .expect("at least two elements")
-            .clone();
-        let mut index = 1;
-        while index < not_normalized.len() {
-            let primitive = not_normalized
-                .get(index)
-                .expect("index within bound")
-                .clone();
-            if current.0 == primitive.0 {
-                current = (current.0.clone(), current.1 + primitive.1)
-            } else {
-                if current.1 != 0 {
-                    normalized.push(current)
-                } else {
-                    if !normalized.is_empty() {
-                        current = normalized.pop().expect("non-empty stack");
-                        continue;
-                    }
-                }
-                current = primitive
+    /// Cyclically reduce this word.
+    ///
+    /// Letters at the start that cancel against letters at the end are removed,
+    /// so the result is a conjugate of this word whose first and last letter
+    /// are not inverse of each other.
+    pub fn cyclically_reduce(&self) -> Word<S> {
+        let mut terms = self.terms.clone();
+        while terms.len() > 1 {
+            let last = terms.len() - 1;
+            let first_exponent = terms[0].1;
+            let last_exponent = terms[last].1;
+            if terms[0].0 != terms[last].0 || first_exponent.signum() == last_exponent.signum() {
+                break;
+            }
+            let cancelled = first_exponent.abs().min(last_exponent.abs());
+            terms[0].1 -= first_exponent.signum() * cancelled;
+            terms[last].1 -= last_exponent.signum() * cancelled;
+            if terms[last].1 == 0 {
+                terms.pop();
+            }
+            if terms[0].1 == 0 {
+                terms.remove(0);
             }
-            index += 1;
         }
-        if current.1 != 0 {
-            normalized.push(current);
+        Word { terms }
+    }
+
+    /// The word with its syllables in reverse order.
+    ///
+    /// A word written in the convention of left actions, where the rightmost
+    /// symbol acts first, reverses to the same element in the left to right
+    /// convention of this crate.
+    pub fn reverse(&self) -> Word<S> {
+        let mut terms = self.terms.clone();
+        terms.reverse();
+        Word { terms }
+    }
+
+    /// Iterate over the syllables of this word, i.e. the symbols with their
+    /// exponents.
+    pub fn iter(&self) -> Iter<'_, (S, i64)> {
+        self.terms.iter()
+    }
+
+    /// The number of syllables in this word.
+    pub fn syllable_count(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// The number of letters in this word, i.e. the sum of the absolute values
+    /// of the exponents.
+    pub fn letter_length(&self) -> u64 {
+        self.terms
+            .iter()
+            .map(|&(_, exponent)| exponent.unsigned_abs())
+            .sum()
+    }
+
+    /// The number of moves in this word, counted in `metric`.
+    pub fn length_in<M>(&self, metric: &M) -> u64
+    where
+        M: Metric<S> + ?Sized,
+    {
+        self.terms
+            .iter()
+            .map(|(symbol, exponent)| metric.cost(symbol, *exponent))
+            .sum()
+    }
+
+    /// The set of symbols that occur in this word.
+    pub fn symbols(&self) -> HashSet<S> {
+        self.terms
+            .iter()
+            .map(|(symbol, _)| symbol.clone())
+            .collect()
+    }
+
+    /// Evaluate this word in an arbitrary group by replacing each symbol with
+    /// its image, e.g. to verify a solution against the permutation it should
+    /// perform.
+    ///
+    /// Panics when a symbol has no image.
+    pub fn evaluate<H>(&self, images: &HashMap<S, H>) -> H
+    where
+        H: GroupElement + Clone,
+    {
+        self.terms
+            .iter()
+            .fold(H::identity(), |product, (symbol, exponent)| {
+                let image = images.get(symbol).expect("should have an image");
+                let factor = if *exponent < 0 {
+                    image.inverse()
+                } else {
+                    image.clone()
+                };
+                (0..exponent.abs()).fold(product, |product, _| product.times(&factor))
+            })
+    }
+
+    /// Write this word in move notation, e.g. `R2' T` instead of `R^-2T^1`.
+    /// The identity is written as the empty string.
+    pub fn format_with(&self, notation: &Notation<S>) -> String
+    where
+        S: Display,
+    {
+        self.terms
+            .iter()
+            .map(|(symbol, exponent)| notation.format_move(symbol, *exponent))
+            .collect::<Vec<String>>()
+            .join(&notation.separator)
+    }
+}
+
+/// The conventions to write a word in move notation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notation<S>
+where
+    S: Eq + Hash,
+{
+    names: HashMap<S, String>,
+    inverse_suffix: String,
+    power_before_inverse: bool,
+    separator: String,
+}
+
+impl<S> Notation<S>
+where
+    S: Eq + Hash,
+{
+    /// The notation that writes symbols as themselves, inverses with a `'`
+    /// after the power, e.g. `R2'`, and separates moves with a space.
+    pub fn new() -> Notation<S> {
+        Notation {
+            names: HashMap::new(),
+            inverse_suffix: String::from("'"),
+            power_before_inverse: true,
+            separator: String::from(" "),
         }
+    }
+
+    /// Write the symbols in `names` with their name instead.
+    pub fn with_names(mut self, names: HashMap<S, String>) -> Notation<S> {
+        self.names = names;
+        self
+    }
 
-        normalized
+    /// Denote inverses by `suffix`. Defaults to `'`.
+    pub fn with_inverse_suffix(mut self, suffix: &str) -> Notation<S> {
+        self.inverse_suffix = suffix.to_string();
+        self
+    }
+
+    /// Write the inverse suffix before the power, e.g. `R'2` instead of `R2'`.
+    pub fn with_inverse_before_power(mut self) -> Notation<S> {
+        self.power_before_inverse = false;
+        self
+    }
+
+    /// Separate moves by `separator`. Defaults to a space.
+    pub fn with_separator(mut self, separator: &str) -> Notation<S> {
+        self.separator = separator.to_string();
+        self
+    }
+
+    fn format_move(&self, symbol: &S, exponent: i64) -> String
+    where
+        S: Display,
+    {
+        let name = match self.names.get(symbol) {
+            Some(name) => name.clone(),
+            None => symbol.to_string(),
+        };
+        let suffix = if exponent < 0 {
+            self.inverse_suffix.as_str()
+        } else {
+            ""
+        };
+        let power = match exponent.abs() {
+            1 => String::new(),
+            power => power.to_string(),
+        };
+        if self.power_before_inverse {
+            format!("{}{}{}", name, power, suffix)
+        } else {
+            format!("{}{}{}", name, suffix, power)
+        }
+    }
+}
+
+impl<S> Default for Notation<S>
+where
+    S: Eq + Hash,
+{
+    fn default() -> Notation<S> {
+        Notation::new()
     }
 }
 
-impl GroupElement for Word {
+/// A way to count moves: the cost of every syllable of a word.
+pub trait Metric<S> {
+    /// The cost of `symbol` raised to `exponent`.
+    fn cost(&self, symbol: &S, exponent: i64) -> u64;
+}
+
+/// The half turn metric: every turn of an outer layer counts as one move,
+/// whatever its angle, so `R2` counts as one.
+///
+/// A turn of an inner slice counts as two, the two turns of the outer layers
+/// next to it. Symbols are outer layers unless they are named as slices.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HalfTurn<S>
+where
+    S: Eq + Hash,
+{
+    slices: HashSet<S>,
+}
+
+impl<S> HalfTurn<S>
+where
+    S: Eq + Hash,
+{
+    /// The half turn metric where every symbol turns an outer layer.
+    pub fn new() -> HalfTurn<S> {
+        HalfTurn {
+            slices: HashSet::new(),
+        }
+    }
+
+    /// Count the symbols in `slices` as turns of an inner slice.
+    pub fn with_slices(mut self, slices: Vec<S>) -> HalfTurn<S> {
+        self.slices = slices.into_iter().collect();
+        self
+    }
+}
+
+impl<S> Default for HalfTurn<S>
+where
+    S: Eq + Hash,
+{
+    fn default() -> HalfTurn<S> {
+        HalfTurn::new()
+    }
+}
+
+impl<S> Metric<S> for HalfTurn<S>
+where
+    S: Eq + Hash,
+{
+    fn cost(&self, symbol: &S, exponent: i64) -> u64 {
+        match (exponent, self.slices.contains(symbol)) {
+            (0, _) => 0,
+            (_, true) => 2,
+            (_, false) => 1,
+        }
+    }
+}
+
+/// The quarter turn metric: every quarter turn of an outer layer counts as
+/// one move, so `R2` counts as two.
+///
+/// The exponent is counted as it is written, so `R3` counts as three and `R'`
+/// as one. A turn of an inner slice counts twice, like in `HalfTurn`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuarterTurn<S>
+where
+    S: Eq + Hash,
+{
+    slices: HashSet<S>,
+}
+
+impl<S> QuarterTurn<S>
+where
+    S: Eq + Hash,
+{
+    /// The quarter turn metric where every symbol turns an outer layer.
+    pub fn new() -> QuarterTurn<S> {
+        QuarterTurn {
+            slices: HashSet::new(),
+        }
+    }
+
+    /// Count the symbols in `slices` as turns of an inner slice.
+    pub fn with_slices(mut self, slices: Vec<S>) -> QuarterTurn<S> {
+        self.slices = slices.into_iter().collect();
+        self
+    }
+}
+
+impl<S> Default for QuarterTurn<S>
+where
+    S: Eq + Hash,
+{
+    fn default() -> QuarterTurn<S> {
+        QuarterTurn::new()
+    }
+}
+
+impl<S> Metric<S> for QuarterTurn<S>
+where
+    S: Eq + Hash,
+{
+    fn cost(&self, symbol: &S, exponent: i64) -> u64 {
+        let quarters = exponent.unsigned_abs();
+        if self.slices.contains(symbol) {
+            2 * quarters
+        } else {
+            quarters
+        }
+    }
+}
+
+/// The slice turn metric: every turn of any layer, outer layer or inner
+/// slice, counts as one move, whatever its angle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SliceTurn;
+
+impl<S> Metric<S> for SliceTurn {
+    fn cost(&self, _symbol: &S, exponent: i64) -> u64 {
+        if exponent == 0 {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+/// Freely reduce a sequence of syllables.
+///
+/// The reduced syllables are kept on a stack. Each incoming syllable is either
+/// merged with the top of the stack, which is popped when it cancels, or pushed
+/// onto it. Because a pop exposes the previous syllable, cancellation cascades
+/// to any depth.
+fn normalize<S>(elements: &[(S, i64)]) -> Vec<(S, i64)>
+where
+    S: Eq + Clone,
+{
+    let mut normalized: Vec<(S, i64)> = vec![];
+    for (symbol, exponent) in elements {
+        if *exponent == 0 {
+            continue;
+        }
+        let merged = match normalized.last_mut() {
+            Some(top) if top.0 == *symbol => {
+                top.1 += exponent;
+                true
+            }
+            _ => false,
+        };
+        if !merged {
+            normalized.push((symbol.clone(), *exponent));
+        } else if normalized.last().map(|top| top.1 == 0).unwrap_or(false) {
+            normalized.pop();
+        }
+    }
+    normalized
+}
+
+/// Arbitrary words, built from arbitrary syllables that need not be reduced,
+/// for fuzzing. Exponents fit in an `i16`, so that merging syllables can not
+/// overflow.
+#[cfg(any(test, feature = "arbitrary"))]
+impl<'a, S> Arbitrary<'a> for Word<S>
+where
+    S: Eq + Hash + Clone + Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Word<S>> {
+        let elements: Vec<(S, i16)> = u.arbitrary()?;
+        let elements: Vec<(S, i64)> = elements
+            .into_iter()
+            .map(|(symbol, exponent)| (symbol, exponent as i64))
+            .collect();
+        Ok(Word::new(elements))
+    }
+}
+
+impl<S> GroupElement for Word<S>
+where
+    S: Eq + Hash + Clone,
+{
+    fn identity() -> Word<S> {
+        Word::identity()
+    }
+
     fn is_identity(&self) -> bool {
-        self.terms.len() == 0
+        self.terms.is_empty()
     }
 
-    fn times(&self, multiplicant: &Word) -> Word {
-        let mut terms: Vec<(char, i64)> = vec![];
-        terms.extend(&self.terms);
-        terms.extend(&multiplicant.terms);
+    fn times(&self, multiplicant: &Word<S>) -> Word<S> {
+        let mut terms: Vec<(S, i64)> = vec![];
+        terms.extend_from_slice(&self.terms);
+        terms.extend_from_slice(&multiplicant.terms);
         let terms = normalize(&terms);
-        Word { terms: terms }
+        Word { terms }
     }
 
-    fn inverse(&self) -> Word {
-        let mut terms: Vec<(char, i64)> = vec![];
-        terms.extend(&self.terms);
+    fn inverse(&self) -> Word<S> {
+        let mut terms: Vec<(S, i64)> = vec![];
+        terms.extend_from_slice(&self.terms);
         terms.reverse();
         for element in terms.iter_mut() {
             element.1 *= -1;
         }
-        Word { terms: terms }
+        Word { terms }
     }
 }
 
-impl Display for Word {
+impl<S> Display for Word<S>
+where
+    S: Eq + Hash + Clone + Display,
+{
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if self.terms.len() > 0 {
+        if !self.terms.is_empty() {
             for primitive in &self.terms {
                 write!(f, "{}^{}", primitive.0, primitive.1)?;
             }
@@ -124,6 +517,86 @@ impl Display for Word {
     }
 }
 
+/// Parses words like `x^2y^-3` or `a b c^-1`, and `Id` for the identity. A
+/// symbol is a letter and is raised to the first power when no exponent
+/// follows it.
+impl FromStr for Word<char> {
+    type Err = ParseWordError;
+
+    fn from_str(text: &str) -> Result<Word<char>, ParseWordError> {
+        if text.trim() == "Id" {
+            return Ok(Word::identity());
+        }
+        let mut terms = vec![];
+        let mut characters = text.char_indices().peekable();
+        while let Some((position, character)) = characters.next() {
+            if character.is_whitespace() {
+                continue;
+            }
+            if !character.is_alphabetic() {
+                return Err(ParseWordError::UnexpectedCharacter(character, position));
+            }
+            let exponent = match characters.peek() {
+                Some(&(_, '^')) => {
+                    characters.next();
+                    parse_exponent(&mut characters, position)?
+                }
+                _ => 1,
+            };
+            terms.push((character, exponent));
+        }
+        Ok(Word::new(terms))
+    }
+}
+
+/// The exponent after the `^` of the syllable at `position`.
+fn parse_exponent(
+    characters: &mut Peekable<CharIndices>,
+    position: usize,
+) -> Result<i64, ParseWordError> {
+    let mut digits = String::new();
+    if let Some(&(_, sign)) = characters.peek() {
+        if sign == '-' || sign == '+' {
+            digits.push(sign);
+            characters.next();
+        }
+    }
+    while let Some(&(_, digit)) = characters.peek() {
+        if !digit.is_ascii_digit() {
+            break;
+        }
+        digits.push(digit);
+        characters.next();
+    }
+    digits
+        .parse()
+        .map_err(|_| ParseWordError::InvalidExponent(position))
+}
+
+/// The ways parsing a word can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWordError {
+    /// This character at this byte position is not a symbol.
+    UnexpectedCharacter(char, usize),
+    /// The syllable at this byte position has a `^` without a valid exponent.
+    InvalidExponent(usize),
+}
+
+impl Display for ParseWordError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseWordError::UnexpectedCharacter(character, position) => {
+                write!(f, "unexpected '{}' at {}", character, position)
+            }
+            ParseWordError::InvalidExponent(position) => {
+                write!(f, "invalid exponent for the symbol at {}", position)
+            }
+        }
+    }
+}
+
+impl error::Error for ParseWordError {}
+
 #[cfg(test)]
 mod tests {
     use super::super::GroupElement;
@@ -135,7 +608,7 @@ mod tests {
 
         assert!(!not_identity.is_identity());
 
-        let identity = Word::identity();
+        let identity: Word = Word::identity();
 
         assert!(identity.is_identity());
     }
@@ -163,13 +636,181 @@ mod tests {
         assert!(product.is_identity());
     }
 
+    #[test]
+    fn conjugate_and_commutator_should_be_written_out() {
+        let g = Word::generator('g');
+        let h = Word::generator('h');
+
+        assert_eq!(
+            g.conjugate_by(&h),
+            Word::new(vec![('h', -1), ('g', 1), ('h', 1)])
+        );
+        assert_eq!(
+            g.commutator(&h),
+            Word::new(vec![('g', -1), ('h', -1), ('g', 1), ('h', 1)])
+        );
+        assert!(g.commutator(&g).is_identity());
+    }
+
     #[test]
     fn word_should_display_correctly() {
-        let identity = Word::identity();
+        let identity: Word = Word::identity();
 
         let word = Word::new(vec![('x', 2), ('y', -3), ('x', -2), ('y', 3)]);
 
         assert_eq!("Id", format!("{}", identity));
         assert_eq!("x^2y^-3x^-2y^3", format!("{}", word));
     }
+
+    #[test]
+    fn word_should_parse_what_it_displays() {
+        let word = Word::new(vec![('x', 2), ('y', -3), ('x', -2), ('y', 3)]);
+        let identity: Word = Word::identity();
+
+        assert_eq!("x^2y^-3x^-2y^3".parse::<Word>(), Ok(word));
+        assert_eq!(identity.to_string().parse::<Word>(), Ok(identity));
+        assert_eq!(
+            "a b c^-1 c^+2".parse::<Word>(),
+            Ok(Word::new(vec![('a', 1), ('b', 1), ('c', 1)]))
+        );
+    }
+
+    #[test]
+    fn word_should_reject_malformed_text() {
+        assert_eq!(
+            "a 2".parse::<Word>(),
+            Err(ParseWordError::UnexpectedCharacter('2', 2))
+        );
+        assert_eq!(
+            "ab^".parse::<Word>(),
+            Err(ParseWordError::InvalidExponent(1))
+        );
+        assert_eq!(
+            "a^-".parse::<Word>(),
+            Err(ParseWordError::InvalidExponent(0))
+        );
+    }
+
+    #[test]
+    fn word_should_be_freely_reduced() {
+        let trivial = Word::new(vec![('a', 0)]);
+        let cascade = Word::new(vec![
+            ('a', 1),
+            ('b', 1),
+            ('c', 2),
+            ('c', -1),
+            ('d', 0),
+            ('c', -1),
+            ('b', -1),
+            ('a', -1),
+            ('d', 1),
+        ]);
+
+        assert!(trivial.is_identity());
+        assert_eq!(cascade, Word::generator('d'));
+    }
+
+    #[test]
+    fn word_should_cyclically_reduce() {
+        let word = Word::new(vec![('a', 2), ('b', 1), ('c', 1), ('b', -1), ('a', -3)]);
+        let conjugate = Word::new(vec![('a', 1), ('b', 1), ('a', -1)]);
+        let commutator = Word::new(vec![('a', 1), ('b', 1), ('a', -1), ('b', -1)]);
+
+        assert_eq!(
+            word.cyclically_reduce(),
+            Word::new(vec![('b', 1), ('c', 1), ('b', -1), ('a', -1)])
+        );
+        assert_eq!(conjugate.cyclically_reduce(), Word::generator('b'));
+        assert_eq!(commutator.cyclically_reduce(), commutator);
+    }
+
+    #[test]
+    fn word_should_measure_its_length() {
+        let word = Word::new(vec![('x', 2), ('y', -3), ('x', -2), ('y', 3)]);
+
+        assert_eq!(word.syllable_count(), 4);
+        assert_eq!(word.letter_length(), 10);
+        assert_eq!(word.symbols(), vec!['x', 'y'].into_iter().collect());
+        assert_eq!(word.iter().next(), Some(&('x', 2)));
+    }
+
+    #[test]
+    fn metrics_should_count_slices_and_half_turns() {
+        let word = Word::new(vec![('R', 2), ('M', 1), ('U', -1)]);
+
+        assert_eq!(word.length_in(&HalfTurn::new()), 3);
+        assert_eq!(word.length_in(&HalfTurn::new().with_slices(vec!['M'])), 4);
+        assert_eq!(word.length_in(&QuarterTurn::new()), 4);
+        assert_eq!(
+            word.length_in(&QuarterTurn::new().with_slices(vec!['M'])),
+            5
+        );
+        assert_eq!(word.length_in(&SliceTurn), 3);
+    }
+
+    #[test]
+    fn reverse_should_convert_between_conventions() {
+        let g = Word::generator('g');
+        let h = Word::generator('h');
+
+        assert_eq!(g.times_left(&h), h.times(&g));
+        assert_eq!(g.times(&h).reverse(), g.times_left(&h));
+    }
+
+    #[test]
+    fn word_should_evaluate_in_other_groups() {
+        let mut images = HashMap::new();
+        images.insert('a', Word::new(vec![('x', 1), ('y', 1)]));
+        images.insert('b', Word::generator('y'));
+        let word = Word::new(vec![('a', 2), ('b', -1)]);
+
+        assert_eq!(
+            word.evaluate(&images),
+            Word::new(vec![('x', 1), ('y', 1), ('x', 1)])
+        );
+        assert!(Word::<char>::identity().evaluate(&images).is_identity());
+    }
+
+    #[test]
+    fn notation_should_follow_its_conventions() {
+        let word = Word::new(vec![('a', 1), ('b', -1), ('a', -2)]);
+
+        assert_eq!(word.format_with(&Notation::new()), "a b' a2'");
+        assert_eq!(
+            word.format_with(
+                &Notation::new()
+                    .with_inverse_suffix("i")
+                    .with_inverse_before_power()
+                    .with_separator("")
+            ),
+            "abiai2"
+        );
+        assert_eq!(Word::<char>::identity().format_with(&Notation::new()), "");
+    }
+
+    #[test]
+    fn word_should_allow_named_generators() {
+        let first = Word::new(vec![(String::from("R"), 1), (String::from("U"), 1)]);
+        let second = Word::new(vec![(String::from("U"), -1), (String::from("Rw2"), 1)]);
+
+        let product = first.times(&second);
+
+        let expected = Word::new(vec![(String::from("R"), 1), (String::from("Rw2"), 1)]);
+        assert_eq!(product, expected);
+        assert_eq!("R^1Rw2^1", format!("{}", product));
+    }
+
+    #[test]
+    fn arbitrary_words_should_be_reduced() {
+        let data: Vec<u8> = (0..=255).rev().collect();
+        let mut u = Unstructured::new(&data);
+
+        while !u.is_empty() {
+            let word: Word<bool> = Word::arbitrary(&mut u).unwrap();
+
+            let terms: Vec<&(bool, i64)> = word.iter().collect();
+            assert!(terms.iter().all(|(_, exponent)| *exponent != 0));
+            assert!(terms.windows(2).all(|pair| pair[0].0 != pair[1].0));
+        }
+    }
 }
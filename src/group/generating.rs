@@ -0,0 +1,212 @@
+//! Generating sets without redundant generators.
+//!
+//! Generators are often collected without much care: the moves of a puzzle
+//! together with their inverses, or the same move under two names. Every
+//! generator ends up in the generator list of the first level, where it slows
+//! down the orbit computations and `transversal_for`. `Group::new` and the
+//! constructions based on `Group::with_base` therefore drop identities,
+//! duplicates and inverses of earlier generators first, with
+//! `canonical_generators`.
+//!
+//! `irredundant_generators` goes further and drops every generator that is
+//! generated by the others. That takes a group construction per generator, so
+//! it is only done on request, e.g. with
+//! `GroupBuilder::with_irredundant_generators`.
+//!
+//! The Schreier-Sims algorithm adds strong generators until every level is
+//! complete, and many of them turn out to be unnecessary once later generators
+//! are known. `Group::minimize_generators` prunes the strong generators of
+//! every level to those needed to generate the stabilizer of the base points
+//! before it. Fewer generators take less memory and make sifting faster.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::GroupElement;
+//! # use permutation_rs::group::generating::{canonical_generators, irredundant_generators};
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2);
+//! let rotation = permute!(0, 1, 1, 2, 2, 0);
+//! let generators = vec![
+//!     Permutation::identity(),
+//!     transposition.clone(),
+//!     rotation.clone(),
+//!     rotation.inverse(),
+//!     transposition.times(&rotation),
+//! ];
+//!
+//! let canonical = canonical_generators(generators);
+//!
+//! assert_eq!(canonical.len(), 3);
+//! assert_eq!(irredundant_generators(&[0, 1, 2], canonical).len(), 2);
+//! # }
+//! ```
+
+use super::orbit::{schreier_vector, Orbit};
+use super::{schreier_sims, Group, GroupAction, GroupElement};
+use std::hash::Hash;
+
+/// The number of strong generators of every level, before and after
+/// `Group::minimize_generators`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GeneratorCounts {
+    /// The number of strong generators of every level before.
+    pub before: Vec<usize>,
+    /// The number of strong generators of every level after.
+    pub after: Vec<usize>,
+}
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// Prune the strong generators of every level to a subset that still
+    /// generates the stabilizer of the base points before that level. The
+    /// group, its base and its orbits stay the same.
+    ///
+    /// A generator is dropped when the others generate a stabilizer with the
+    /// same orbit lengths, which takes a construction per generator.
+    pub fn minimize_generators(&mut self) -> GeneratorCounts {
+        let base: Vec<Domain> = self
+            .levels
+            .iter()
+            .map(|level| level.orbit.base.clone())
+            .collect();
+        let gset = &self.gset;
+        let mut counts = GeneratorCounts::default();
+        for (index, level) in self.levels.iter_mut().enumerate() {
+            counts.before.push(level.orbit.generators.len());
+            let lengths = |generators: &[G]| -> Vec<usize> {
+                schreier_sims(gset, &base[index..], generators.to_vec())
+                    .iter()
+                    .map(|level| level.length())
+                    .collect()
+            };
+            let mut kept = level.orbit.generators.clone();
+            let expected = lengths(&kept);
+            let mut candidate = kept.len();
+            while candidate > 0 {
+                candidate -= 1;
+                let mut rest = kept.clone();
+                rest.remove(candidate);
+                if lengths(&rest) == expected {
+                    kept = rest;
+                }
+            }
+            let vector = schreier_vector(&level.orbit.base, &kept);
+            level.orbit = Orbit::from_parts(level.orbit.base.clone(), kept, vector);
+            counts.after.push(level.orbit.generators.len());
+        }
+        counts
+    }
+}
+
+/// `generators` without identities, duplicates and inverses of earlier
+/// generators, in their original order.
+pub fn canonical_generators<G>(generators: Vec<G>) -> Vec<G>
+where
+    G: GroupElement + PartialEq,
+{
+    let mut canonical: Vec<G> = vec![];
+    for generator in generators {
+        if generator.is_identity() {
+            continue;
+        }
+        let inverse = generator.inverse();
+        if canonical
+            .iter()
+            .all(|kept| kept != &generator && kept != &inverse)
+        {
+            canonical.push(generator);
+        }
+    }
+    canonical
+}
+
+/// The canonical `generators`, without every generator that is a member of the
+/// group generated by the generators that are kept. The result generates the
+/// same group on `gset` and none of its generators can be dropped.
+///
+/// Generators are considered in their original order, so the later of two
+/// generators that generate each other is dropped.
+pub fn irredundant_generators<Domain, G>(gset: &[Domain], generators: Vec<G>) -> Vec<G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    let mut kept = canonical_generators(generators);
+    let mut index = kept.len();
+    while index > 0 {
+        index -= 1;
+        let mut rest = kept.clone();
+        let candidate = rest.remove(index);
+        if Group::with_base(gset.to_vec(), vec![], rest.clone()).contains(&candidate) {
+            kept = rest;
+        }
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::permutation::Permutation;
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn canonical_generators_should_keep_the_first_of_a_pair() {
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+        let transposition = permute!(0, 1, 1, 0);
+
+        let canonical = canonical_generators(vec![
+            rotation.inverse(),
+            transposition.clone(),
+            rotation.clone(),
+            Permutation::identity(),
+            transposition.clone(),
+        ]);
+
+        assert_eq!(canonical, vec![rotation.inverse(), transposition]);
+    }
+
+    #[test]
+    fn minimized_generators_should_keep_the_group() {
+        let transposition = permute!(0, 1, 1, 0);
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 4, 4, 0);
+        let mut group = Group::new(vec![0, 1, 2, 3, 4], vec![transposition, rotation]);
+        let elements = group.elements();
+
+        let counts = group.minimize_generators();
+
+        assert_eq!(group.order(), 120);
+        assert!(counts.after.iter().sum::<usize>() < counts.before.iter().sum::<usize>());
+        assert!(elements.iter().all(|element| group.contains(element)));
+        for level in &group.levels {
+            assert!(level.orbit.generators.len() <= 2);
+        }
+    }
+
+    #[test]
+    fn irredundant_generators_should_generate_the_same_group() {
+        let gset: Vec<u64> = vec![0, 1, 2, 3];
+        let generators = vec![
+            permute!(0, 1, 1, 2, 2, 3, 3, 0),
+            permute!(0, 1, 1, 0),
+            permute!(1, 2, 2, 1),
+            permute!(2, 3, 3, 2),
+        ];
+
+        let irredundant = irredundant_generators(&gset, generators);
+
+        assert_eq!(irredundant.len(), 2);
+        assert_eq!(Group::new(gset.clone(), irredundant.clone()).order(), 24);
+        for index in 0..irredundant.len() {
+            let mut rest = irredundant.clone();
+            rest.remove(index);
+            assert!(Group::new(gset.clone(), rest).order() < 24);
+        }
+    }
+}
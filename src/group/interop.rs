@@ -0,0 +1,192 @@
+//! Permutations as index permutations, to reorder data.
+//!
+//! Much code outside of this crate represents a permutation of `0..n` as a
+//! `Vec<usize>` in one-line notation: index `i` maps to `indices[i]`. The
+//! lexicographic iterators of the `permutohedron` crate, for instance, work on
+//! such vectors. A `Permutation` converts to and from this representation, and
+//! can reorder a slice directly: the item at index `i` moves to the index that
+//! `i` is mapped to. That is the same convention as `apply_slice` of the
+//! `permutation` crate, with the `permutation` feature converting to and from
+//! its `Permutation` as well.
+//!
+//! Reordering data is what a model of a puzzle eventually needs: the colors of
+//! the stickers follow the stickers. `permute` works on arrays and `Vec`s,
+//! `permute_str` on the characters of a string.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use std::convert::TryFrom;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let rotation = permute!(0, 1, 1, 2, 2, 0);
+//! let mut colors = vec!['r', 'g', 'b', 'w'];
+//!
+//! rotation.apply_to_slice(&mut colors);
+//!
+//! assert_eq!(colors, vec!['b', 'r', 'g', 'w']);
+//! assert_eq!(Vec::<usize>::from(&rotation), vec![1, 2, 0]);
+//! assert_eq!(Permutation::try_from(vec![1, 2, 0]), Ok(rotation));
+//! # }
+//! ```
+
+use super::permutation::{Permutation, PermutationError};
+use std::convert::TryFrom;
+
+impl Permutation {
+    /// A copy of `items` with the item at every index `i` moved to the index
+    /// `i` maps to.
+    ///
+    /// Panics when a moved point is not an index of `items`.
+    pub fn permute<T: Clone>(&self, items: &[T]) -> Vec<T> {
+        let mut permuted = items.to_vec();
+        self.permute_in_place(&mut permuted);
+        permuted
+    }
+
+    /// Move the item at every index `i` of `items` to the index `i` maps to,
+    /// rotating the items along each cycle. Indices that are not moved keep
+    /// their item.
+    ///
+    /// Panics when a moved point is not an index of `items`.
+    pub fn permute_in_place<T>(&self, items: &mut [T]) {
+        let cycles = self.cycles();
+        if let Some(point) = cycles
+            .iter()
+            .flatten()
+            .find(|point| **point as usize >= items.len())
+        {
+            panic!(
+                "point {} is not an index of a slice of length {}",
+                point,
+                items.len()
+            );
+        }
+        for cycle in cycles {
+            let first = cycle[0] as usize;
+            for point in &cycle[1..] {
+                items.swap(first, *point as usize);
+            }
+        }
+    }
+
+    /// The characters of `text` permuted like `permute` does.
+    ///
+    /// Panics when a moved point is not an index of a character of `text`.
+    pub fn permute_str(&self, text: &str) -> String {
+        let characters: Vec<char> = text.chars().collect();
+        self.permute(&characters).into_iter().collect()
+    }
+
+    /// The same as `permute_in_place`, under the name the `permutation` crate
+    /// uses.
+    pub fn apply_to_slice<T>(&self, items: &mut [T]) {
+        self.permute_in_place(items)
+    }
+}
+
+impl<'a> From<&'a Permutation> for Vec<usize> {
+    /// The one-line notation of `permutation`, see `Permutation::to_one_line`.
+    fn from(permutation: &'a Permutation) -> Vec<usize> {
+        permutation
+            .to_one_line()
+            .into_iter()
+            .map(|image| image as usize)
+            .collect()
+    }
+}
+
+impl TryFrom<Vec<usize>> for Permutation {
+    type Error = PermutationError;
+
+    /// Fails when `indices` is not a permutation of `0..indices.len()`.
+    fn try_from(indices: Vec<usize>) -> Result<Permutation, PermutationError> {
+        let images: Vec<u64> = indices.iter().map(|index| *index as u64).collect();
+        Permutation::from_one_line(&images)
+    }
+}
+
+#[cfg(feature = "permutation")]
+impl<'a> From<&'a Permutation> for ::permutation::Permutation {
+    fn from(permutation: &'a Permutation) -> ::permutation::Permutation {
+        ::permutation::Permutation::oneline(Vec::<usize>::from(permutation))
+    }
+}
+
+#[cfg(feature = "permutation")]
+impl From<::permutation::Permutation> for Permutation {
+    fn from(permutation: ::permutation::Permutation) -> Permutation {
+        let images = (0..permutation.len())
+            .map(|index| (index as u64, permutation.apply_idx(index) as u64))
+            .collect();
+        Permutation::new_unchecked(images)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{GroupAction, GroupElement};
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn applying_should_agree_with_the_action() {
+        let permutation = permute!(0, 3, 3, 5, 5, 0, 1, 2, 2, 1);
+        let mut items: Vec<u64> = (0..7).collect();
+
+        permutation.apply_to_slice(&mut items);
+
+        for point in 0..7 {
+            assert_eq!(items[permutation.act_on(&point) as usize], point);
+        }
+    }
+
+    #[test]
+    fn permuting_should_reorder_copies_and_strings() {
+        let permutation = permute!(0, 1, 1, 2, 2, 0);
+        let mut array = [10, 20, 30, 40];
+
+        permutation.permute_in_place(&mut array);
+
+        assert_eq!(permutation.permute(&[10, 20, 30, 40]), vec![30, 10, 20, 40]);
+        assert_eq!(array, [30, 10, 20, 40]);
+        assert_eq!(permutation.permute_str("abcd"), "cabd");
+        assert_eq!(permutation.inverse().permute_str("cabd"), "abcd");
+    }
+
+    #[test]
+    fn index_permutations_should_round_trip() {
+        let sparse = permute!(2, 4, 4, 2);
+
+        let indices = Vec::<usize>::from(&sparse);
+
+        assert_eq!(indices, vec![0, 1, 4, 3, 2]);
+        assert_eq!(sparse.to_one_line(), vec![0, 1, 4, 3, 2]);
+        assert_eq!(Permutation::try_from(indices), Ok(sparse));
+        assert_eq!(
+            Permutation::try_from(vec![0, 0]),
+            Err(PermutationError::DuplicateImage(0))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "permutation")]
+    fn conversions_should_agree_with_the_permutation_crate() {
+        let permutation = permute!(0, 3, 3, 1, 1, 0);
+        let mut items = vec!['a', 'b', 'c', 'd'];
+
+        let converted = ::permutation::Permutation::from(&permutation);
+        let reordered = converted.apply_slice(&items);
+        permutation.apply_to_slice(&mut items);
+
+        assert_eq!(reordered, items);
+        assert_eq!(Permutation::from(converted), permutation);
+    }
+
+    #[test]
+    #[should_panic]
+    fn applying_to_a_short_slice_should_panic() {
+        permute!(0, 3, 3, 0).apply_to_slice(&mut [1, 2]);
+    }
+}
@@ -0,0 +1,269 @@
+//! Human labels for the points of a domain.
+//!
+//! Groups in this crate act on dense indices `0..n`, but puzzles are described
+//! with labels like `"UF"` or `"URF"`. A `Labeling` maps each label to an index
+//! and back, and translates permutations and sets of points between the two,
+//! so that no index bookkeeping is needed.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::group::GroupAction;
+//! # use permutation_rs::group::labeling::Labeling;
+//! let labeling = Labeling::new(vec!["UF", "UR", "UB", "UL"]).unwrap();
+//!
+//! let u = labeling
+//!     .permutation(&[vec!["UF", "UL", "UB", "UR"]])
+//!     .unwrap();
+//!
+//! assert_eq!(labeling.label(u.act_on(&0)), Some(&"UL"));
+//! assert_eq!(labeling.to_cycles(&u), vec![vec!["UF", "UL", "UB", "UR"]]);
+//! ```
+
+use super::permutation::Permutation;
+use super::GroupAction;
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::fmt::Display;
+use std::hash::Hash;
+
+/// A bijection between labels and the indices `0..n`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Labeling<L>
+where
+    L: Eq + Hash + Clone,
+{
+    labels: Vec<L>,
+    indices: HashMap<L, u64>,
+}
+
+impl<L> Labeling<L>
+where
+    L: Eq + Hash + Clone,
+{
+    /// Label index `i` with `labels[i]`, provided the labels are distinct.
+    pub fn new(labels: Vec<L>) -> Result<Labeling<L>, LabelingError<L>> {
+        let mut indices: HashMap<L, u64> = HashMap::with_capacity(labels.len());
+        for (index, label) in labels.iter().enumerate() {
+            if indices.insert(label.clone(), index as u64).is_some() {
+                return Err(LabelingError::DuplicateLabel(label.clone()));
+            }
+        }
+        Ok(Labeling { labels, indices })
+    }
+
+    /// The number of labels.
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Determine if there are no labels.
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// The labels, in order of their index.
+    pub fn labels(&self) -> &[L] {
+        &self.labels
+    }
+
+    /// The indices, i.e. the gset to build a group on.
+    pub fn gset(&self) -> Vec<u64> {
+        (0..self.labels.len() as u64).collect()
+    }
+
+    /// The index of `label`.
+    pub fn index(&self, label: &L) -> Option<u64> {
+        self.indices.get(label).cloned()
+    }
+
+    /// The label of `index`.
+    pub fn label(&self, index: u64) -> Option<&L> {
+        self.labels.get(index as usize)
+    }
+
+    /// The indices of `labels`, e.g. to use as a base.
+    pub fn indices_of(&self, labels: &[L]) -> Result<Vec<u64>, LabelingError<L>> {
+        labels
+            .iter()
+            .map(|label| {
+                self.index(label)
+                    .ok_or_else(|| LabelingError::UnknownLabel(label.clone()))
+            })
+            .collect()
+    }
+
+    /// The labels of `indices`, e.g. of the points of an orbit.
+    ///
+    /// Panics when an index has no label.
+    pub fn labels_of<'a, I>(&self, indices: I) -> Vec<L>
+    where
+        I: IntoIterator<Item = &'a u64>,
+    {
+        indices
+            .into_iter()
+            .map(|index| self.label(*index).expect("index should be labeled").clone())
+            .collect()
+    }
+
+    /// The permutation of the indices with the given cycles of labels. Every
+    /// labeled point not in a cycle is fixed.
+    pub fn permutation(&self, cycles: &[Vec<L>]) -> Result<Permutation, LabelingError<L>> {
+        let mut images: HashMap<u64, u64> = self.gset().into_iter().map(|i| (i, i)).collect();
+        let mut seen: Vec<u64> = vec![];
+        for cycle in cycles {
+            let points = self.indices_of(cycle)?;
+            for (position, point) in points.iter().enumerate() {
+                if seen.contains(point) {
+                    return Err(LabelingError::DuplicateLabel(cycle[position].clone()));
+                }
+                seen.push(*point);
+                images.insert(*point, points[(position + 1) % points.len()]);
+            }
+        }
+        Ok(Permutation::new_unchecked(images))
+    }
+
+    /// The images of a permutation of the indices, by label.
+    ///
+    /// # Panics
+    /// Panics when `permutation` maps a labeled index to one without a label.
+    pub fn to_images(&self, permutation: &Permutation) -> HashMap<L, L> {
+        self.labels
+            .iter()
+            .enumerate()
+            .map(|(index, label)| {
+                let image = permutation.act_on(&(index as u64));
+                (label.clone(), self.labels_of(&[image])[0].clone())
+            })
+            .collect()
+    }
+
+    /// The non trivial cycles of a permutation of the indices, in labels.
+    ///
+    /// # Panics
+    /// Panics when `permutation` moves an index without a label.
+    pub fn to_cycles(&self, permutation: &Permutation) -> Vec<Vec<L>> {
+        permutation
+            .cycles()
+            .iter()
+            .map(|cycle| self.labels_of(cycle))
+            .collect()
+    }
+}
+
+impl<L> Labeling<L>
+where
+    L: Eq + Hash + Clone + Display,
+{
+    /// Write a permutation of the indices in cycle notation over the labels,
+    /// e.g. `(UF UL UB UR)`.
+    ///
+    /// # Panics
+    /// Panics when `permutation` moves an index without a label.
+    pub fn format(&self, permutation: &Permutation) -> String {
+        let cycles = self.to_cycles(permutation);
+        if cycles.is_empty() {
+            return String::from("Id");
+        }
+        cycles
+            .iter()
+            .map(|cycle| {
+                let labels: Vec<String> = cycle.iter().map(|label| label.to_string()).collect();
+                format!("({})", labels.join(" "))
+            })
+            .collect()
+    }
+}
+
+/// The ways translating between labels and indices can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LabelingError<L> {
+    /// The label occurs more than once.
+    DuplicateLabel(L),
+    /// The label is not part of the labeling.
+    UnknownLabel(L),
+}
+
+impl<L> Display for LabelingError<L>
+where
+    L: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LabelingError::DuplicateLabel(ref label) => {
+                write!(f, "label '{}' occurs more than once", label)
+            }
+            LabelingError::UnknownLabel(ref label) => write!(f, "unknown label '{}'", label),
+        }
+    }
+}
+
+impl<L> error::Error for LabelingError<L> where L: fmt::Debug + Display {}
+
+#[cfg(test)]
+mod tests {
+    use super::super::orbit::Orbit;
+    use super::super::GroupElement;
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Corner {
+        Urf,
+        Ufl,
+        Ulb,
+    }
+
+    #[test]
+    fn labeling_should_accept_enums() {
+        let labeling = Labeling::new(vec![Corner::Urf, Corner::Ufl, Corner::Ulb]).unwrap();
+        let twist = labeling
+            .permutation(&[vec![Corner::Urf, Corner::Ulb]])
+            .unwrap();
+
+        let images = labeling.to_images(&twist);
+
+        assert_eq!(images[&Corner::Urf], Corner::Ulb);
+        assert_eq!(images[&Corner::Ufl], Corner::Ufl);
+        let orbit = Orbit::new(0u64, vec![twist]);
+        let mut points = labeling.labels_of(orbit.iter());
+        points.sort_by_key(|corner| labeling.index(corner));
+        assert_eq!(points, vec![Corner::Urf, Corner::Ulb]);
+    }
+
+    #[test]
+    fn labeling_should_reject_duplicate_and_unknown_labels() {
+        assert_eq!(
+            Labeling::new(vec!["a", "b", "a"]),
+            Err(LabelingError::DuplicateLabel("a"))
+        );
+        let labeling = Labeling::new(vec!["a", "b", "c"]).unwrap();
+
+        assert_eq!(
+            labeling.permutation(&[vec!["a", "b"], vec!["b", "c"]]),
+            Err(LabelingError::DuplicateLabel("b"))
+        );
+        assert_eq!(
+            labeling.permutation(&[vec!["a", "d"]]),
+            Err(LabelingError::UnknownLabel("d"))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "index should be labeled")]
+    fn to_images_should_panic_for_unlabeled_images() {
+        let labeling = Labeling::new(vec!["a", "b"]).unwrap();
+        let outside = Permutation::new((0..3).map(|i| (i, (i + 1) % 3)).collect());
+
+        labeling.to_images(&outside);
+    }
+
+    #[test]
+    fn format_should_write_cycles_of_labels() {
+        let labeling = Labeling::new(vec!["UF", "UR", "UB", "UL"]).unwrap();
+        let swap = labeling.permutation(&[vec!["UR", "UL"]]).unwrap();
+
+        assert_eq!(labeling.format(&swap), "(UR UL)");
+        assert_eq!(labeling.format(&swap.times_left(&swap)), "Id");
+    }
+}
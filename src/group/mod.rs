@@ -8,28 +8,115 @@
 //!    for all _g_ in _G_.
 //! 3. For each element _g_ in _G_ there is an inverse. I.e. an element _h_ in
 //!    _G_ such that _g_ * _h_ = _e_, the identity element in _G_.
+//!
+//! # Conventions
+//! Products are composed from left to right, and groups act on the right: the
+//! image of _x_ under _gh_ is the image under _h_ of the image under _g_, i.e.
+//! _x_^(_gh_) = (_x_^_g_)^_h_. This is the convention of GAP and of puzzle
+//! notation, where `R U` means first `R`, then `U`. Every part of this crate
+//! follows it: `strip` divides out transversals on the right, a transversal
+//! maps the base point to its point by acting on the right, and a `Morphism`
+//! maps the product _gh_ to the product of the images in the same order.
+//!
+//! Texts that let permutations act on the left compose from right to left:
+//! (_σ_ ∘ _τ_)(_x_) = _σ_(_τ_(_x_)). Use `GroupElement::times_left` to form
+//! such products, and `free::Word::reverse` to read a word written in that
+//! convention. The image of a point, `act_on`, is the same in both.
+
+#[macro_use]
+pub mod permutation;
 
+pub mod abelian;
+pub mod array;
+pub mod automorphism;
+pub mod base;
+pub mod blocks;
+pub mod builder;
 pub mod calculation;
+pub mod cayley;
+pub mod centralizer;
+pub mod character;
+pub mod composition;
+pub mod conjugacy;
+pub mod constituent;
+pub mod coset;
+pub mod counting;
+pub mod encoding;
+pub mod fingerprint;
 pub mod free;
-pub mod permutation;
+pub mod generating;
+pub mod interop;
+pub mod labeling;
+pub mod normalizer;
+pub mod orbit;
+#[cfg(feature = "parallel")]
+mod parallel;
+pub mod peephole;
+pub mod presentation;
+pub mod quotient;
+pub mod random;
+pub mod recognition;
+pub mod rewriting;
 pub mod special;
+pub mod statistics;
+pub mod sylow;
+pub mod testing;
 pub mod tree;
+pub mod verification;
 
+use num_bigint::BigUint;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::collections::VecDeque;
-use std::fmt::{Display, Error, Formatter};
+use std::convert::TryFrom;
+use std::fmt::{Debug, Display, Error, Formatter};
 use std::hash::Hash;
+use std::slice;
 
-use self::calculation::identity;
+use self::free::Word;
+use self::generating::canonical_generators;
+use self::orbit::{schreier_vector, transversal_for, Orbit, SchreierVector};
 
 /// The contract for a group element.
 pub trait GroupElement {
+    /// The identity element, the neutral element of `times`.
+    fn identity() -> Self
+    where
+        Self: Sized;
     /// Determine if the group element is the identity.
     fn is_identity(&self) -> bool;
-    /// The associated operation of the Group.
+    /// The associated operation of the Group. The product acts as `self`
+    /// followed by `multiplicant`.
     fn times(&self, multiplicant: &Self) -> Self;
     /// Returns the inverse of the group element.
     fn inverse(&self) -> Self;
+
+    /// The product in the convention of left actions, i.e. `self` ∘
+    /// `multiplicant`, which acts as `multiplicant` followed by `self`.
+    fn times_left(&self, multiplicant: &Self) -> Self
+    where
+        Self: Sized,
+    {
+        multiplicant.times(self)
+    }
+
+    /// The conjugate of this element by `h`, i.e. _h_^-1 * `self` * _h_.
+    fn conjugate_by(&self, h: &Self) -> Self
+    where
+        Self: Sized,
+    {
+        h.inverse().times(self).times(h)
+    }
+
+    /// The commutator of this element with `h`, i.e.
+    /// `self`^-1 * _h_^-1 * `self` * _h_.
+    fn commutator(&self, h: &Self) -> Self
+    where
+        Self: Sized,
+    {
+        self.inverse().times(&h.inverse()).times(self).times(h)
+    }
 }
 
 /// A group can _act_ on a set. (See [Group Action](https://en.wikipedia.org/wiki/Group_action)).
@@ -37,8 +124,39 @@ pub trait GroupAction {
     /// The set the group acts on.
     type Domain;
 
-    /// The action that the group has on the domain.
+    /// The action that the group has on the domain. It is a right action, so
+    /// acting with `g.times(&h)` equals acting with `g` and then with `h`.
     fn act_on(&self, element: &Self::Domain) -> Self::Domain;
+
+    /// The image of `element` under the right action, _x_^`self`. It is
+    /// `act_on`, named after the convention for code that mixes conventions.
+    fn act_on_right(&self, element: &Self::Domain) -> Self::Domain {
+        self.act_on(element)
+    }
+}
+
+/// An action that is only defined on part of a set, e.g. the moves of a
+/// bandaged puzzle, which are blocked in some states.
+///
+/// Every `GroupAction` is a partial action that is defined everywhere.
+pub trait PartialGroupAction {
+    /// The set that is partially acted on.
+    type Domain;
+
+    /// The image of `element`, or `None` when the action is not defined on
+    /// `element`.
+    fn try_act_on(&self, element: &Self::Domain) -> Option<Self::Domain>;
+}
+
+impl<G> PartialGroupAction for G
+where
+    G: GroupAction,
+{
+    type Domain = G::Domain;
+
+    fn try_act_on(&self, element: &G::Domain) -> Option<G::Domain> {
+        Some(self.act_on(element))
+    }
 }
 
 /// The actual group.
@@ -47,6 +165,7 @@ where
     Domain: Eq + Hash + Clone,
     G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
 {
+    gset: Vec<Domain>,
     levels: Vec<BaseStrongGeneratorLevel<Domain, G>>,
 }
 
@@ -56,57 +175,751 @@ where
     G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
 {
     /// Creates a group with a given set of generators on a certain gset.
+    ///
+    /// Identities, duplicates and inverses of earlier generators are dropped
+    /// first. Generators that move no point of the gset, including an empty
+    /// list of generators, result in the trivial group.
     pub fn new(gset: Vec<Domain>, generators: Vec<G>) -> Group<Domain, G> {
         let mut levels = vec![];
-        let mut gs = generators;
-        while gs.len() > 0 {
-            let base: Domain = find_base(&gset, &gs).expect("generators should move something");
+        let mut gs = canonical_generators(generators);
+        while let Some(base) = find_base(&gset, &gs) {
             let (level, stabilizers) = BaseStrongGeneratorLevel::new(base, gs);
             levels.push(level);
             gs = stabilizers;
         }
-        Group { levels: levels }
+        Group { gset, levels }
     }
 
     /// The order of the group, i.e. the number of elements this group has.
+    #[deprecated(note = "use `order`, which does not overflow a `usize`")]
     pub fn size(&self) -> usize {
+        usize::try_from(self.order()).expect("the order of the group should fit in a usize")
+    }
+
+    /// The order of the group, i.e. the number of elements this group has.
+    ///
+    /// # Panics
+    /// Panics when the order does not fit in a `u128`, like the orders of the
+    /// groups of the 4x4x4 cube and the megaminx. Use `big_order` for those.
+    pub fn order(&self) -> u128 {
+        self.levels.iter().fold(1u128, |acc, level| {
+            acc.checked_mul(level.length() as u128)
+                .expect("the order of the group should fit in a u128, use big_order")
+        })
+    }
+
+    /// The order of the group as a `BigUint`, for groups too big for `order`,
+    /// like the groups of the 4x4x4 cube and the megaminx.
+    pub fn big_order(&self) -> BigUint {
+        self.levels
+            .iter()
+            .fold(BigUint::from(1u32), |acc, level| acc * level.length())
+    }
+
+    /// The prime factorization of the order of the group, as pairs of a prime
+    /// and its exponent in increasing order of the primes. It follows from the
+    /// orbit lengths, so it does not overflow for big groups.
+    pub fn order_factored(&self) -> Vec<(u64, u32)> {
+        let mut exponents: BTreeMap<u64, u32> = BTreeMap::new();
+        for level in &self.levels {
+            for (prime, exponent) in factor(level.length() as u64) {
+                *exponents.entry(prime).or_insert(0) += exponent;
+            }
+        }
+        exponents.into_iter().collect()
+    }
+
+    /// Determine if `n` divides the order of the group.
+    pub fn order_divisible_by(&self, n: u64) -> bool {
+        if n == 0 {
+            return false;
+        }
+        let factored = self.order_factored();
+        factor(n).into_iter().all(|(prime, exponent)| {
+            factored
+                .iter()
+                .any(|&(candidate, available)| candidate == prime && available >= exponent)
+        })
+    }
+
+    /// A summary of every level of the stabilizer chain, so the balance of the
+    /// chain can be inspected.
+    pub fn chain_info(&self) -> Vec<LevelInfo<Domain>> {
         self.levels
             .iter()
-            .fold(1usize, |acc, ref level| acc * level.length())
+            .map(|level| LevelInfo {
+                base: level.orbit.base.clone(),
+                orbit_size: level.orbit.len(),
+                generators: level.orbit.generators.len(),
+            })
+            .collect()
+    }
+
+    /// The levels of the stabilizer chain, starting with the level of the
+    /// first base point.
+    pub fn levels(&self) -> impl Iterator<Item = &BaseStrongGeneratorLevel<Domain, G>> {
+        self.levels.iter()
     }
 
     /// Determine if a group element is a member of this group.
     pub fn is_member(&self, element: G) -> bool {
-        let candidate = self.strip(element);
-        candidate.is_identity()
+        self.contains(&element)
+    }
+
+    /// Determine if a group element is a member of this group, without taking
+    /// ownership of it.
+    pub fn contains(&self, element: &G) -> bool {
+        match sift_borrowed(&self.levels, element) {
+            Some(residue) => residue.is_identity(),
+            None => element.is_identity(),
+        }
     }
 
     /// Strip element with current group
+    ///
+    /// At each level the transversal for the element is divided out on the
+    /// right, so a member equals the residue times the transversals in reverse
+    /// order.
     pub fn strip(&self, element: G) -> G {
-        let mut candidate = element;
+        sift(&self.levels, element).0
+    }
+
+    /// Strip element with current group, recording the transversal used at
+    /// each level and the level where stripping stopped.
+    pub fn strip_with_trace(&self, element: G) -> StripTrace<G> {
+        let (residue, level, transversals) = sift_with_trace(&self.levels, element);
+        StripTrace {
+            residue,
+            transversals,
+            level,
+        }
+    }
+
+    /// Prove that `element` is a member of this group, or explain why it is
+    /// not.
+    ///
+    /// A member is factored into the transversals used to strip it. For an
+    /// element that is not a member, the `Residue` holds what remains of the
+    /// element and the level at which stripping failed.
+    pub fn membership_certificate(&self, element: G) -> Result<Factorization<G>, Residue<G>> {
+        let (residue, level, steps) = sift_with_trace(&self.levels, element);
+        let transversals = steps
+            .into_iter()
+            .map(|(_, transversal)| transversal)
+            .collect();
+        if residue.is_identity() {
+            Ok(Factorization { transversals })
+        } else {
+            Err(Residue {
+                element: residue,
+                level,
+            })
+        }
+    }
+}
+
+/// How an element is stripped, level by level.
+///
+/// The element equals the residue times the transversals in reverse order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StripTrace<G> {
+    /// What remains of the element after stripping.
+    pub residue: G,
+    /// The index of each level that moved the element, with the transversal
+    /// that was divided out there. Levels that fix the element are skipped.
+    pub transversals: Vec<(usize, G)>,
+    /// The level at which stripping stopped. This equals the number of levels
+    /// when every level could be stripped.
+    pub level: usize,
+}
+
+/// A summary of a level of the stabilizer chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelInfo<Domain> {
+    /// The base point of the level.
+    pub base: Domain,
+    /// The length of the orbit of the base point.
+    pub orbit_size: usize,
+    /// The number of strong generators of the level.
+    pub generators: usize,
+}
+
+/// The transversals that factor a member of a group.
+///
+/// The member equals the product of the transversals in reverse order, i.e. the
+/// transversal of the last level comes first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Factorization<G> {
+    /// The transversals, in the order of the levels they are taken from.
+    pub transversals: Vec<G>,
+}
+
+/// What remains of an element that is not a member of a group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Residue<G> {
+    /// The element after stripping all levels it could.
+    pub element: G,
+    /// The level at which stripping failed. When this equals the number of
+    /// levels, the residue fixes every base point but is not the identity.
+    pub level: usize,
+}
+
+impl<G> Display for Residue<G>
+where
+    G: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(
+            f,
+            "stripping failed at level {} with residue {}",
+            self.level, self.element
+        )
+    }
+}
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// Creates a group with a given set of generators on a certain gset, using
+    /// `base` as the first base points.
+    ///
+    /// Strong generators are found with the Schreier-Sims algorithm, sifting
+    /// each Schreier generator through the lower levels. Only the generators
+    /// that do not sift are kept. The base is extended when necessary.
+    ///
+    /// The construction is deterministic: the same gset, base and generators,
+    /// in the same order, always result in the same stabilizer chain.
+    pub fn with_base(gset: Vec<Domain>, base: Vec<Domain>, generators: Vec<G>) -> Group<Domain, G> {
+        let levels = schreier_sims(&gset, &base, generators);
+        Group { gset, levels }
+    }
+
+    /// Strip a borrowed element with current group.
+    ///
+    /// The element is only cloned when no level changes it.
+    pub fn strip_ref(&self, element: &G) -> G {
+        sift_borrowed(&self.levels, element).unwrap_or_else(|| element.clone())
+    }
+
+    /// Determine if `word` equals the identity when every symbol is replaced
+    /// by its image, e.g. whether `(R U R' U')^6` is a relator of a cube group.
+    ///
+    /// Panics when a symbol of `word` has no image.
+    pub fn satisfies_relator<S>(&self, word: &Word<S>, images: &HashMap<S, G>) -> bool
+    where
+        S: Eq + Hash + Clone,
+    {
+        word.evaluate(images).is_identity()
+    }
+
+    /// Creates a group with a given set of generators on a certain gset, that
+    /// is known to have `order` elements.
+    ///
+    /// The Schreier-Sims algorithm stops as soon as the product of the orbit
+    /// lengths equals `order`, instead of sifting all remaining Schreier
+    /// generators. The orbit lengths never exceed those of the group, so an
+    /// `order` that is too large only loses the speed up. An `order` that is
+    /// too small can result in a group that is too small when it divides the
+    /// real order.
+    pub fn new_with_order(gset: Vec<Domain>, generators: Vec<G>, order: u128) -> Group<Domain, G> {
+        let mut monitor = KnownOrder::new(order);
+        let levels = schreier_sims_with(&Sequential, &mut monitor, &gset, &[], generators)
+            .expect("known order should not interrupt");
+        Group { gset, levels }
+    }
+
+    /// Determine a small base with the greedy algorithm.
+    ///
+    /// Repeatedly pick a point from the largest orbit of the stabilizer of the
+    /// points chosen so far, until that stabilizer is trivial. The resulting base
+    /// is irredundant and its length is the number of levels a group created with
+    /// `Group::with_base` will have.
+    pub fn greedy_minimal_base(&self) -> Vec<Domain> {
+        let generators = self.generators();
+        let mut base: Vec<Domain> = vec![];
+        loop {
+            let levels = schreier_sims(&self.gset, &base, generators.clone());
+            let stabilizers: &[G] = levels
+                .get(base.len())
+                .map(|level| level.orbit.generators.as_slice())
+                .unwrap_or(&[]);
+            if stabilizers.is_empty() {
+                return base;
+            }
+            let largest = orbits(&self.gset, stabilizers).into_iter().fold(
+                vec![],
+                |largest: Vec<Domain>, orbit| {
+                    if orbit.len() > largest.len() {
+                        orbit
+                    } else {
+                        largest
+                    }
+                },
+            );
+            base.push(largest[0].clone());
+        }
+    }
+
+    /// Change the base of this group so that it starts with `base`.
+    ///
+    /// The new strong generators are products of the generators of this group.
+    /// So when these carry a witness, e.g. an `SLPPermutation`, the new strong
+    /// generators record how they decompose over the original generators.
+    pub fn change_base(&self, base: Vec<Domain>) -> Group<Domain, G> {
+        Group::with_base(self.gset.clone(), base, self.generators())
+    }
+
+    /// The subgroup of elements that fix `point`.
+    ///
+    /// Like `change_base`, the generators of the stabilizer are expressed in
+    /// the generators of this group.
+    pub fn stabilizer(&self, point: Domain) -> Group<Domain, G> {
+        let mut changed = self.change_base(vec![point]);
+        changed.levels.remove(0);
+        changed
+    }
+
+    /// All elements of this group.
+    ///
+    /// Every element is a product of one transversal per level, so this is
+    /// only feasible for small groups.
+    pub fn elements(&self) -> Vec<G> {
+        let mut elements: Vec<G> = vec![G::identity()];
         for level in &self.levels {
-            if level.has_transversal_for(&candidate) {
-                let transversal = level
-                    .transversal_for(&candidate)
-                    .expect("should have transversal");
-                let inverse = transversal.inverse();
-                candidate = candidate.times(&inverse);
-            } else {
-                break;
+            let transversals: Vec<G> = level
+                .orbit
+                .iter()
+                .map(|point| level.orbit.representative(point).expect("point in orbit"))
+                .collect();
+            elements = transversals
+                .iter()
+                .flat_map(|t| elements.iter().map(move |e| t.times(e)))
+                .collect();
+        }
+        elements
+    }
+
+    fn generators(&self) -> Vec<G> {
+        self.levels
+            .first()
+            .map(|level| level.orbit.generators.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// The prime factorization of `n` by trial division, in increasing order of
+/// the primes.
+fn factor(mut n: u64) -> Vec<(u64, u32)> {
+    let mut factors = vec![];
+    let mut candidate = 2;
+    while candidate <= n / candidate {
+        let mut exponent = 0;
+        while n.is_multiple_of(candidate) {
+            n /= candidate;
+            exponent += 1;
+        }
+        if exponent > 0 {
+            factors.push((candidate, exponent));
+        }
+        candidate += 1;
+    }
+    if n > 1 {
+        factors.push((n, 1));
+    }
+    factors
+}
+
+fn sift<Domain, G>(levels: &[BaseStrongGeneratorLevel<Domain, G>], element: G) -> (G, usize)
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
+{
+    let (candidate, depth, _) = sift_with_trace(levels, element);
+    (candidate, depth)
+}
+
+/// Sift a borrowed element. Returns `None` when no level changes the element,
+/// in which case the element itself is the residue.
+fn sift_borrowed<Domain, G>(
+    levels: &[BaseStrongGeneratorLevel<Domain, G>],
+    element: &G,
+) -> Option<G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
+{
+    for (depth, level) in levels.iter().enumerate() {
+        if element.act_on(&level.orbit.base) == level.orbit.base {
+            continue;
+        }
+        let transversal = level.transversal_for(element)?;
+        let candidate = element.times(&transversal.inverse());
+        return Some(sift(&levels[(depth + 1)..], candidate).0);
+    }
+    None
+}
+
+fn sift_with_trace<Domain, G>(
+    levels: &[BaseStrongGeneratorLevel<Domain, G>],
+    element: G,
+) -> (G, usize, Vec<(usize, G)>)
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
+{
+    let mut candidate = element;
+    let mut transversals = vec![];
+    for (depth, level) in levels.iter().enumerate() {
+        if candidate.act_on(&level.orbit.base) == level.orbit.base {
+            continue;
+        }
+        if level.has_transversal_for(&candidate) {
+            let transversal = level
+                .transversal_for(&candidate)
+                .expect("should have transversal");
+            let inverse = transversal.inverse();
+            candidate = candidate.times(&inverse);
+            transversals.push((depth, transversal));
+        } else {
+            return (candidate, depth, transversals);
+        }
+    }
+    (candidate, levels.len(), transversals)
+}
+
+/// How the Schreier-Sims algorithm computes orbits and searches for Schreier
+/// generators that do not sift.
+trait Strategy<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
+{
+    fn schreier_vector(&self, base: &Domain, generators: &[G]) -> SchreierVector<Domain>;
+
+    fn non_sifting_schreier_generator(
+        &self,
+        gset: &[Domain],
+        levels: &[BaseStrongGeneratorLevel<Domain, G>],
+        index: usize,
+    ) -> Option<(G, usize)>;
+}
+
+/// Follows the progress of the Schreier-Sims algorithm and decides whether it
+/// may continue.
+trait Monitor {
+    /// The orbit of the base point of `level` has `size` points.
+    fn orbit_size(&mut self, _level: usize, _size: usize) {}
+
+    /// The stabilizer at `level` has `count` strong generators.
+    fn stabilizer_count(&mut self, _level: usize, _count: usize) {}
+
+    /// Every Schreier generator of `level` sifts through the levels below it.
+    fn level_complete(&mut self, _level: usize) {}
+
+    /// A Schreier generator of `level` does not sift: it stops at `depth`,
+    /// which gains it as a strong generator.
+    fn sift_failed(&mut self, _level: usize, _depth: usize) {}
+
+    /// Whether the algorithm should continue.
+    fn proceed(&mut self) -> bool {
+        true
+    }
+
+    /// Whether the levels found so far are known to be complete.
+    fn is_complete(&self) -> bool {
+        false
+    }
+}
+
+/// A monitor that knows the order of the group, and with it when the product
+/// of the orbit lengths shows that the stabilizer chain is complete.
+struct KnownOrder {
+    order: u128,
+    sizes: Vec<usize>,
+}
+
+impl KnownOrder {
+    fn new(order: u128) -> KnownOrder {
+        KnownOrder {
+            order,
+            sizes: vec![],
+        }
+    }
+}
+
+impl Monitor for KnownOrder {
+    fn orbit_size(&mut self, level: usize, size: usize) {
+        if self.sizes.len() <= level {
+            self.sizes.resize(level + 1, 1);
+        }
+        self.sizes[level] = size;
+    }
+
+    fn is_complete(&self) -> bool {
+        self.sizes
+            .iter()
+            .try_fold(1u128, |product, &size| product.checked_mul(size as u128))
+            == Some(self.order)
+    }
+}
+
+/// A monitor that ignores the progress and never interrupts.
+struct Silent;
+
+impl Monitor for Silent {}
+
+/// A monitor that emits a `tracing` event for every step it follows, and
+/// passes the step on to the monitor it wraps.
+#[cfg(feature = "tracing")]
+struct Traced<'m, M>(&'m mut M);
+
+#[cfg(feature = "tracing")]
+impl<'m, M> Monitor for Traced<'m, M>
+where
+    M: Monitor,
+{
+    fn orbit_size(&mut self, level: usize, size: usize) {
+        ::tracing::debug!(level, size, "orbit computed");
+        self.0.orbit_size(level, size)
+    }
+
+    fn stabilizer_count(&mut self, level: usize, count: usize) {
+        ::tracing::debug!(level, count, "strong generator added");
+        self.0.stabilizer_count(level, count)
+    }
+
+    fn level_complete(&mut self, level: usize) {
+        ::tracing::debug!(level, "level complete");
+        self.0.level_complete(level)
+    }
+
+    fn sift_failed(&mut self, level: usize, depth: usize) {
+        ::tracing::debug!(level, depth, "Schreier generator does not sift");
+        self.0.sift_failed(level, depth)
+    }
+
+    fn proceed(&mut self) -> bool {
+        self.0.proceed()
+    }
+
+    fn is_complete(&self) -> bool {
+        self.0.is_complete()
+    }
+}
+
+/// Perform every step of the Schreier-Sims algorithm on the current thread.
+struct Sequential;
+
+impl<Domain, G> Strategy<Domain, G> for Sequential
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    fn schreier_vector(&self, base: &Domain, generators: &[G]) -> SchreierVector<Domain> {
+        schreier_vector(base, generators)
+    }
+
+    fn non_sifting_schreier_generator(
+        &self,
+        gset: &[Domain],
+        levels: &[BaseStrongGeneratorLevel<Domain, G>],
+        index: usize,
+    ) -> Option<(G, usize)> {
+        non_sifting_schreier_generator(gset, levels, index)
+    }
+}
+
+fn schreier_sims<Domain, G>(
+    gset: &[Domain],
+    base: &[Domain],
+    generators: Vec<G>,
+) -> Vec<BaseStrongGeneratorLevel<Domain, G>>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    schreier_sims_with(&Sequential, &mut Silent, gset, base, generators)
+        .expect("silent monitor should not interrupt")
+}
+
+/// The Schreier-Sims algorithm, or `None` when `monitor` interrupts it.
+fn schreier_sims_with<Domain, G, S, M>(
+    strategy: &S,
+    monitor: &mut M,
+    gset: &[Domain],
+    base: &[Domain],
+    generators: Vec<G>,
+) -> Option<Vec<BaseStrongGeneratorLevel<Domain, G>>>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+    S: Strategy<Domain, G> + ?Sized,
+    M: Monitor,
+{
+    #[cfg(feature = "tracing")]
+    let _span = ::tracing::debug_span!("schreier_sims", points = gset.len()).entered();
+    #[cfg(feature = "tracing")]
+    let monitor = &mut Traced(monitor);
+    let mut levels = initial_levels(strategy, monitor, gset, base, generators)?;
+    let mut current = levels.len();
+    while current > 0 && !monitor.is_complete() {
+        if !monitor.proceed() {
+            return None;
+        }
+        let index = current - 1;
+        #[cfg(feature = "tracing")]
+        let _span = ::tracing::debug_span!("level", index).entered();
+        match strategy.non_sifting_schreier_generator(gset, &levels, index) {
+            Some((residue, depth)) => {
+                monitor.sift_failed(index, depth);
+                if depth == levels.len() {
+                    let point = find_base(gset, slice::from_ref(&residue))
+                        .expect("residue should move something");
+                    levels.push(BaseStrongGeneratorLevel::with_generators(point, vec![]));
+                }
+                for (offset, level) in levels[(index + 1)..=depth].iter_mut().enumerate() {
+                    let orbit = &mut level.orbit;
+                    orbit.generators.push(residue.clone());
+                    orbit.vector = strategy.schreier_vector(&orbit.base, &orbit.generators);
+                    monitor.stabilizer_count(index + 1 + offset, orbit.generators.len());
+                    monitor.orbit_size(index + 1 + offset, orbit.len());
+                }
+                current = depth + 1;
+            }
+            None => {
+                monitor.level_complete(index);
+                current -= 1;
             }
         }
-        candidate
     }
+    Some(levels)
 }
 
-fn find_base<Domain, G>(gset: &Vec<Domain>, generators: &Vec<G>) -> Option<Domain>
+/// The levels of the base points in `base`, extended with a point for every
+/// generator that fixes the base, each with the generators that fix the points
+/// before it. Returns `None` when `monitor` interrupts.
+fn initial_levels<Domain, G, S, M>(
+    strategy: &S,
+    monitor: &mut M,
+    gset: &[Domain],
+    base: &[Domain],
+    generators: Vec<G>,
+) -> Option<Vec<BaseStrongGeneratorLevel<Domain, G>>>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+    S: Strategy<Domain, G> + ?Sized,
+    M: Monitor,
+{
+    let generators = canonical_generators(generators);
+    let mut points: Vec<Domain> = base.to_vec();
+    for generator in &generators {
+        if points.iter().all(|point| &generator.act_on(point) == point) {
+            if let Some(point) = find_base(gset, slice::from_ref(generator)) {
+                points.push(point);
+            }
+        }
+    }
+    let mut levels: Vec<BaseStrongGeneratorLevel<Domain, G>> = vec![];
+    for (index, point) in points.iter().enumerate() {
+        if !monitor.proceed() {
+            return None;
+        }
+        let strong: Vec<G> = generators
+            .iter()
+            .filter(|g| points[0..index].iter().all(|p| &g.act_on(p) == p))
+            .cloned()
+            .collect();
+        let vector = strategy.schreier_vector(point, &strong);
+        let orbit = Orbit::from_parts(point.clone(), strong, vector);
+        monitor.stabilizer_count(index, orbit.generators.len());
+        monitor.orbit_size(index, orbit.len());
+        levels.push(BaseStrongGeneratorLevel { orbit });
+    }
+    Some(levels)
+}
+
+fn non_sifting_schreier_generator<Domain, G>(
+    gset: &[Domain],
+    levels: &[BaseStrongGeneratorLevel<Domain, G>],
+    index: usize,
+) -> Option<(G, usize)>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    let level = &levels[index];
+    if level.orbit.generators.is_empty() {
+        return None;
+    }
+    gset.iter()
+        .filter(|p| level.orbit.contains(p))
+        .find_map(|point| non_sifting_schreier_generator_at(levels, index, point))
+}
+
+/// Search the Schreier generators that belong to `point` of level `index` for
+/// one that does not sift through the lower levels.
+fn non_sifting_schreier_generator_at<Domain, G>(
+    levels: &[BaseStrongGeneratorLevel<Domain, G>],
+    index: usize,
+    point: &Domain,
+) -> Option<(G, usize)>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    let level = &levels[index];
+    let to = level.orbit.representative(point).expect("point in orbit");
+    for generator in &level.orbit.generators {
+        let image = generator.act_on(point);
+        let fro = level
+            .orbit
+            .representative(&image)
+            .expect("image in orbit")
+            .inverse();
+        let schreier_generator = to.times(generator).times(&fro);
+        if !schreier_generator.is_identity() {
+            let (residue, depth) = sift(&levels[(index + 1)..], schreier_generator);
+            if !residue.is_identity() {
+                return Some((residue, index + 1 + depth));
+            }
+        }
+    }
+    None
+}
+
+fn orbits<Domain, G>(gset: &[Domain], generators: &[G]) -> Vec<Vec<Domain>>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain>,
+{
+    let mut result: Vec<Vec<Domain>> = vec![];
+    let mut visited: HashSet<Domain> = HashSet::new();
+    for start in gset {
+        if !visited.contains(start) {
+            let orbit = schreier_vector(start, generators);
+            let points: Vec<Domain> = gset
+                .iter()
+                .filter(|p| orbit.contains_key(p))
+                .cloned()
+                .collect();
+            visited.extend(points.iter().cloned());
+            result.push(points);
+        }
+    }
+    result
+}
+
+fn find_base<Domain, G>(gset: &[Domain], generators: &[G]) -> Option<Domain>
 where
     Domain: Eq + Hash + Clone,
     G: GroupElement + GroupAction<Domain = Domain>,
 {
     for original in gset {
         for generator in generators {
-            let image = generator.act_on(&original);
+            let image = generator.act_on(original);
             if &image != original {
                 return Some(image.clone());
             }
@@ -115,35 +928,65 @@ where
     None
 }
 
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone + Display,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
+{
+    /// The Schreier trees of all levels in the DOT language, each level in a
+    /// cluster of its own. Nodes are named by level and point.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph chain {\n");
+        for (index, level) in self.levels.iter().enumerate() {
+            dot.push_str(&format!("  subgraph cluster_{} {{\n", index));
+            dot.push_str(&format!("    label=\"level {}\";\n", index));
+            for statement in level.orbit.dot_statements(&format!("{}:", index)) {
+                dot.push_str(&format!("    {}\n", statement));
+            }
+            dot.push_str("  }\n");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl<Domain, G> Debug for Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone + Debug,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
+{
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.debug_struct("Group")
+            .field("gset", &self.gset)
+            .field("levels", &self.chain_info())
+            .finish()
+    }
+}
+
 impl<Domain, G> Display for Group<Domain, G>
 where
     Domain: Eq + Hash + Clone + Display,
     G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Display,
 {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        write!(f, "<\n")?;
+        writeln!(f, "<")?;
         for level in &self.levels {
             level.fmt(f)?;
         }
-        write!(f, ">\n")
+        writeln!(f, ">")
     }
 }
 
 /// A level in the Schreier-Sims Base Strong generator algorithm.
 ///
-/// It basically is a SchreierVector with some extra book-keeping.
+/// It basically is the `Orbit` of the base point under the strong generators of
+/// this level.
 pub struct BaseStrongGeneratorLevel<Domain, G>
 where
     Domain: Eq + Hash + Clone,
     G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
 {
-    /// The base element for this level.
-    base: Domain,
-    /// Generators that act on the base to form the orbit.
-    generators: Vec<G>,
-    /// A [Schreier vector](https://en.wikipedia.org/wiki/Schreier_vector) for
-    /// this base and generators.
-    indices: HashMap<Domain, isize>,
+    orbit: Orbit<Domain, G>,
 }
 
 impl<Domain, G> BaseStrongGeneratorLevel<Domain, G>
@@ -154,10 +997,9 @@ where
     /// Create a BaseStrongGeneratorLevel with a known base and generators.
     pub fn new(base: Domain, generators: Vec<G>) -> (Self, Vec<G>) {
         let mut to_visit: VecDeque<Domain> = VecDeque::new();
-        let mut indices: HashMap<Domain, isize> = HashMap::new();
+        let mut indices: SchreierVector<Domain> = SchreierVector::new(base.clone());
         let mut stabilizers: Vec<G> = vec![];
         to_visit.push_back(base.clone());
-        indices.insert(base.clone(), -1);
         while !to_visit.is_empty() {
             let element = to_visit.pop_front().unwrap();
             for (index, generator) in generators.iter().enumerate() {
@@ -170,7 +1012,7 @@ where
                     let fro = transversal_for(&image, &generators, &indices)
                         .unwrap()
                         .inverse();
-                    let stabilizer = to.times(&generator).times(&fro);
+                    let stabilizer = to.times(generator).times(&fro);
                     if add_to_stabilizers(&stabilizer, &stabilizers) {
                         stabilizers.push(stabilizer);
                     }
@@ -179,38 +1021,79 @@ where
         }
         (
             BaseStrongGeneratorLevel {
-                base,
-                generators,
-                indices,
+                orbit: Orbit::from_parts(base, generators, indices),
             },
             stabilizers,
         )
     }
 
+    /// Create a BaseStrongGeneratorLevel with a known base and generators,
+    /// without calculating stabilizers.
+    pub fn with_generators(base: Domain, generators: Vec<G>) -> Self {
+        BaseStrongGeneratorLevel {
+            orbit: Orbit::new(base, generators),
+        }
+    }
+
     /// Determine if this levels base is acted upon by `g` in a way compatible for this level.
     pub fn has_transversal_for(&self, g: &G) -> bool {
-        let image = g.act_on(&self.base);
-        self.indices.contains_key(&image)
+        let image = g.act_on(&self.orbit.base);
+        self.orbit.contains(&image)
     }
 
     /// The transversal corresponding with `g`.
     pub fn transversal_for(&self, g: &G) -> Option<G> {
-        let image = g.act_on(&self.base);
-        transversal_for(&image, &self.generators, &self.indices)
+        let image = g.act_on(&self.orbit.base);
+        self.orbit.representative(&image)
     }
 
     /// Length of the orbit
     pub fn length(&self) -> usize {
-        self.indices.len()
+        self.orbit.len()
+    }
+
+    /// The base point of this level.
+    pub fn base(&self) -> &Domain {
+        self.orbit.base()
+    }
+
+    /// The strong generators of this level. They fix the base points of the
+    /// levels before it.
+    pub fn generators(&self) -> &[G] {
+        self.orbit.generators()
+    }
+
+    /// The points of the orbit of the base point, in the order they were
+    /// found.
+    pub fn orbit_points(&self) -> impl Iterator<Item = &Domain> {
+        self.orbit.iter()
+    }
+
+    /// The transversal that maps the base point to `point`, or `None` when
+    /// `point` is not in the orbit.
+    pub fn transversal(&self, point: &Domain) -> Option<G> {
+        self.orbit.representative(point)
+    }
+}
+
+impl<Domain, G> BaseStrongGeneratorLevel<Domain, G>
+where
+    Domain: Eq + Hash + Clone + Display,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
+{
+    /// The Schreier tree of this level in the DOT language, see
+    /// `Orbit::to_dot`.
+    pub fn to_dot(&self) -> String {
+        self.orbit.to_dot()
     }
 }
 
-fn add_to_stabilizers<Domain, G>(stabilizer: &G, stabilizers: &Vec<G>) -> bool
+fn add_to_stabilizers<Domain, G>(stabilizer: &G, stabilizers: &[G]) -> bool
 where
     Domain: Eq + Hash + Clone,
     G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
 {
-    !stabilizer.is_identity() && !stabilizers.contains(&stabilizer)
+    !stabilizer.is_identity() && !stabilizers.contains(stabilizer)
 }
 
 impl<Domain, G> Display for BaseStrongGeneratorLevel<Domain, G>
@@ -219,42 +1102,15 @@ where
     G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Display,
 {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        write!(f, "[{};<", self.base)?;
-        for g in &self.generators {
+        write!(f, "[{};<", self.orbit.base)?;
+        for g in &self.orbit.generators {
             write!(f, " {}", g)?;
         }
         write!(f, " >;")?;
-        for (domain, index) in &self.indices {
+        for (domain, index) in self.orbit.vector.iter() {
             write!(f, " {}: {}", domain, index)?;
         }
-        write!(f, "]\n")
-    }
-}
-
-fn transversal_for<Domain, G>(
-    start: &Domain,
-    generators: &Vec<G>,
-    indices: &HashMap<Domain, isize>,
-) -> Option<G>
-where
-    Domain: Eq + Hash + Clone,
-    G: GroupElement + GroupAction<Domain = Domain>,
-{
-    let mut image = start.clone();
-
-    if indices.contains_key(&image) {
-        let mut transversal = identity(&generators);
-        let mut index = indices.get(&image).unwrap();
-        while *index != (-1 as isize) {
-            let generator = &generators[(*index as usize)];
-            let inverse = generator.inverse();
-            image = inverse.act_on(&image);
-            transversal = transversal.times(&inverse);
-            index = indices.get(&image).unwrap();
-        }
-        Some(transversal.inverse())
-    } else {
-        None
+        writeln!(f, "]")
     }
 }
 
@@ -288,22 +1144,54 @@ where
 {
     /// Create a new morphism with a given set of images
     pub fn new(generator_images: HashMap<G, H>) -> Morphism<G, H> {
-        Morphism {
-            generator_images: generator_images,
-        }
+        Morphism { generator_images }
     }
 
     /// maps an G-element to the corresponding H-element.
+    ///
+    /// A morphism only knows the images of the generators. `SLP::transform`
+    /// maps products of generators in the same order: _gh_ maps to the image
+    /// of _g_ times the image of _h_.
+    ///
+    /// # Panics
+    /// Panics when `element` is not one of the generators, see `try_transform`.
     pub fn transform(&self, element: &G) -> H {
-        self.generator_images
-            .get(element)
-            .expect("should have an image")
-            .clone()
+        self.try_transform(element).expect("should have an image")
+    }
+
+    /// The image of `element` when it is one of the generators, or `None`
+    /// otherwise.
+    pub fn try_transform(&self, element: &G) -> Option<H> {
+        self.generator_images.get(element).cloned()
+    }
+
+    /// The images of the generators.
+    pub fn images(&self) -> &HashMap<G, H> {
+        &self.generator_images
+    }
+
+    /// The morphism that maps the images back to their generators, or `None`
+    /// when two generators share an image.
+    pub fn inverse(&self) -> Option<Morphism<H, G>>
+    where
+        G: Clone,
+    {
+        let generator_images: HashMap<H, G> = self
+            .generator_images
+            .iter()
+            .map(|(g, h)| (h.clone(), g.clone()))
+            .collect();
+        if generator_images.len() == self.generator_images.len() {
+            Some(Morphism::new(generator_images))
+        } else {
+            None
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::super::puzzle::models;
     use super::permutation::Permutation;
     use super::*;
     use std::collections::HashMap;
@@ -328,6 +1216,69 @@ mod tests {
     }
 
     #[test]
+    fn conjugate_should_relabel_cycles() {
+        let cycle = permute!(0u64, 1u64, 1u64, 2u64, 2u64, 0u64, 3u64, 3u64);
+        let relabel = permute!(0u64, 3u64, 1u64, 1u64, 2u64, 2u64, 3u64, 0u64);
+
+        let conjugate = cycle.conjugate_by(&relabel);
+
+        assert_eq!(
+            conjugate,
+            permute!(0u64, 0u64, 1u64, 2u64, 2u64, 3u64, 3u64, 1u64)
+        );
+    }
+
+    #[test]
+    fn commutator_of_overlapping_transpositions_should_be_a_three_cycle() {
+        let a = permute!(0u64, 1u64, 1u64, 0u64, 2u64, 2u64);
+        let b = permute!(0u64, 0u64, 1u64, 2u64, 2u64, 1u64);
+
+        let commutator = a.commutator(&b);
+
+        assert_eq!(commutator.support_size(), 3);
+        assert!(a.commutator(&a).is_identity());
+    }
+
+    #[test]
+    fn chain_info_should_summarize_levels() {
+        let transposition = permute!(0u64, 1u64, 1u64, 0u64, 2u64, 2u64);
+        let rotation = permute!(0u64, 1u64, 1u64, 2u64, 2u64, 0u64);
+        let group = Group::with_base(
+            vec![0u64, 1u64, 2u64],
+            vec![0u64],
+            vec![transposition, rotation],
+        );
+
+        let info = group.chain_info();
+
+        assert_eq!(info.len(), 2);
+        assert_eq!(info[0].base, 0u64);
+        assert_eq!(info[0].orbit_size, 3);
+        assert_eq!(info[0].generators, 2);
+        assert_eq!(info[1].orbit_size, 2);
+        assert!(format!("{:?}", group).starts_with("Group { gset: [0, 1, 2], levels: [LevelInfo"));
+    }
+
+    #[test]
+    fn to_dot_should_cluster_levels() {
+        let transposition = permute!(0u64, 1u64, 1u64, 0u64, 2u64, 2u64);
+        let rotation = permute!(0u64, 1u64, 1u64, 2u64, 2u64, 0u64);
+        let group = Group::with_base(
+            vec![0u64, 1u64, 2u64],
+            vec![],
+            vec![transposition, rotation],
+        );
+
+        let dot = group.to_dot();
+
+        assert!(dot.contains("subgraph cluster_0 {"));
+        assert!(dot.contains("subgraph cluster_1 {"));
+        assert!(dot.contains("\"0:1\" -> \"0:2\" [label=\"1\"];"));
+        assert_eq!(dot.matches(" -> ").count(), 3);
+    }
+
+    #[test]
+    #[allow(deprecated)]
     fn group_should_have_a_size() {
         let group = d3();
         println!("{}", group);
@@ -335,6 +1286,68 @@ mod tests {
         assert_eq!(group.size(), 6);
     }
 
+    #[test]
+    fn group_should_have_an_order() {
+        let group = d3();
+
+        assert_eq!(group.order(), 6);
+    }
+
+    fn s40() -> Group<u64, Permutation> {
+        let transposition = permute!(0u64, 1u64, 1u64, 0u64);
+        let cycle = Permutation::new((0..40).map(|point| (point, (point + 1) % 40)).collect());
+        Group::with_base((0..40).collect(), vec![], vec![transposition, cycle])
+    }
+
+    #[test]
+    fn big_order_should_exceed_u128() {
+        let group = s40();
+
+        let factorial = (1..=40u32).fold(BigUint::from(1u32), |product, factor| product * factor);
+
+        assert_eq!(group.big_order(), factorial);
+    }
+
+    #[test]
+    #[should_panic(expected = "use big_order")]
+    fn order_should_panic_beyond_u128() {
+        s40().order();
+    }
+
+    #[test]
+    fn order_should_be_factored_from_orbit_lengths() {
+        let group = d3();
+
+        assert_eq!(group.order_factored(), vec![(2, 1), (3, 1)]);
+        assert!(group.order_divisible_by(6));
+        assert!(group.order_divisible_by(1));
+        assert!(!group.order_divisible_by(4));
+        assert_eq!(factor(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(
+            factor(u64::MAX),
+            vec![
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65537, 1),
+                (6700417, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn group_should_display_deterministically() {
+        let rotation = permute!(0u64, 1u64, 1u64, 2u64, 2u64, 3u64, 3u64, 0u64);
+        let group = Group::with_base(vec![0u64, 1u64, 2u64, 3u64], vec![], vec![rotation]);
+
+        assert_eq!(
+            format!("{}", group),
+            "<\n[1;< (0 1 2 3) >; 1: -1 2: 0 3: 0 0: 0]\n>\n"
+        );
+    }
+
     #[test]
     fn group_should_determine_if_an_element_is_a_member() {
         let mut transposition_images = HashMap::new();
@@ -348,6 +1361,219 @@ mod tests {
         assert!(group.is_member(transposition));
     }
 
+    #[test]
+    fn contains_should_borrow_the_element() {
+        let group = d3();
+        let member = permute!(0u64, 1u64, 1u64, 2u64, 2u64, 0u64);
+        let non_member = permute!(0u64, 1u64, 1u64, 0u64, 2u64, 2u64, 3u64, 4u64, 4u64, 3u64);
+
+        assert!(group.contains(&member));
+        assert!(!group.contains(&non_member));
+        assert!(group.strip_ref(&member).is_identity());
+        assert_eq!(
+            group.strip_ref(&non_member),
+            group.strip(non_member.clone())
+        );
+    }
+
+    #[test]
+    fn satisfies_relator_should_evaluate_words() {
+        let group = d3();
+        let mut images = HashMap::new();
+        images.insert('r', permute!(0u64, 1u64, 1u64, 2u64, 2u64, 0u64));
+        images.insert('t', permute!(0u64, 0u64, 1u64, 2u64, 2u64, 1u64));
+
+        assert!(group.satisfies_relator(&"r^3".parse().unwrap(), &images));
+        assert!(group.satisfies_relator(&"t r t r".parse().unwrap(), &images));
+        assert!(!group.satisfies_relator(&"r t".parse().unwrap(), &images));
+    }
+
+    #[test]
+    fn membership_certificate_should_factor_members() {
+        let group = d3();
+        let element = permute!(0u64, 2u64, 1u64, 1u64, 2u64, 0u64);
+
+        let factorization = group
+            .membership_certificate(element.clone())
+            .expect("should be a member");
+
+        let product = factorization
+            .transversals
+            .iter()
+            .rev()
+            .fold(Permutation::identity(), |acc, t| acc.times(t));
+        assert_eq!(product, element);
+    }
+
+    #[test]
+    fn membership_certificate_should_explain_non_members() {
+        let swap = permute!(0u64, 1u64, 1u64, 0u64, 2u64, 2u64, 3u64, 3u64);
+        let group = Group::with_base(vec![0u64, 1u64, 2u64, 3u64], vec![], vec![swap]);
+        let element = permute!(0u64, 0u64, 1u64, 3u64, 2u64, 2u64, 3u64, 1u64);
+
+        let residue = group
+            .membership_certificate(element.clone())
+            .expect_err("should not be a member");
+
+        assert_eq!(residue.level, 0);
+        assert_eq!(residue.element, element);
+    }
+
+    #[test]
+    fn levels_should_expose_the_chain() {
+        let group = d3();
+
+        for level in group.levels() {
+            assert_eq!(level.orbit_points().count(), level.length());
+            for point in level.orbit_points() {
+                let transversal = level.transversal(point).unwrap();
+                assert_eq!(&transversal.act_on(level.base()), point);
+            }
+            assert!(level.generators().iter().all(|g| group.contains(g)));
+        }
+        assert_eq!(group.levels().count(), 2);
+        assert_eq!(group.levels().next().unwrap().transversal(&7u64), None);
+    }
+
+    #[test]
+    fn strip_with_trace_should_record_the_transversals() {
+        let transposition = permute!(0u64, 1u64, 1u64, 0u64, 2u64, 2u64, 3u64, 3u64);
+        let rotation = permute!(0u64, 1u64, 1u64, 2u64, 2u64, 3u64, 3u64, 0u64);
+        let group = Group::with_base(
+            vec![0u64, 1u64, 2u64, 3u64],
+            vec![],
+            vec![transposition, rotation],
+        );
+        let element = permute!(0u64, 2u64, 1u64, 3u64, 2u64, 0u64, 3u64, 1u64);
+
+        let trace = group.strip_with_trace(element.clone());
+
+        assert!(trace.residue.is_identity());
+        assert_eq!(trace.level, group.levels.len());
+        let product = trace
+            .transversals
+            .iter()
+            .rev()
+            .fold(trace.residue.clone(), |product, (_, t)| product.times(t));
+        assert_eq!(product, element);
+    }
+
+    #[test]
+    fn strip_with_trace_should_tell_where_stripping_stopped() {
+        let rotation = permute!(0u64, 1u64, 1u64, 2u64, 2u64, 3u64, 3u64, 0u64);
+        let group = Group::with_base(vec![0u64, 1u64, 2u64, 3u64], vec![], vec![rotation]);
+        let transposition = permute!(0u64, 1u64, 1u64, 0u64, 2u64, 2u64, 3u64, 3u64);
+
+        let trace = group.strip_with_trace(transposition.clone());
+
+        assert_eq!(trace.level, 1);
+        assert_eq!(trace.transversals.len(), 1);
+        let (level, ref transversal) = trace.transversals[0];
+        assert_eq!(level, 0);
+        assert_eq!(trace.residue, transposition.times(&transversal.inverse()));
+        assert!(!trace.residue.is_identity());
+    }
+
+    #[test]
+    fn elements_should_list_every_element_once() {
+        let group = d3();
+
+        let elements = group.elements();
+
+        assert_eq!(elements.len(), 6);
+        for (index, element) in elements.iter().enumerate() {
+            assert!(group.contains(element));
+            assert!(!elements[(index + 1)..].contains(element));
+        }
+    }
+
+    #[test]
+    fn generators_that_move_nothing_should_generate_the_trivial_group() {
+        let identity = permute!(0, 0, 1, 1, 2, 2);
+        let transposition = permute!(0, 1, 1, 0, 2, 2);
+
+        let trivial = Group::new(vec![0, 1, 2], vec![identity.clone()]);
+        let empty: Group<u64, Permutation> = Group::new(vec![0, 1, 2], vec![]);
+        let with_base = Group::with_base(vec![0, 1, 2], vec![], vec![identity.clone()]);
+
+        for group in &[trivial, empty, with_base] {
+            assert_eq!(group.order(), 1);
+            assert!(group.is_member(identity.clone()));
+            assert!(!group.is_member(transposition.clone()));
+        }
+    }
+
+    #[test]
+    fn elements_without_generators_should_be_the_identity() {
+        let group: Group<u64, Permutation> = Group::with_base(vec![0, 1, 2], vec![], vec![]);
+
+        assert_eq!(group.elements(), vec![Permutation::identity()]);
+        assert!(super::random::RandomElementStream::new(group.generators())
+            .next()
+            .unwrap()
+            .is_identity());
+    }
+
+    #[test]
+    fn group_with_base_should_have_the_same_size() {
+        let transposition = permute!(0u64, 1u64, 1u64, 0u64, 2u64, 2u64, 3u64, 3u64);
+        let rotation = permute!(0u64, 1u64, 1u64, 2u64, 2u64, 3u64, 3u64, 0u64);
+        let gset = vec![0u64, 1u64, 2u64, 3u64];
+
+        let group = Group::with_base(gset, vec![3u64], vec![transposition, rotation]);
+
+        assert_eq!(group.order(), 24);
+        assert_eq!(group.levels[0].orbit.base, 3u64);
+        assert!(group.is_member(permute!(0u64, 2u64, 1u64, 3u64, 2u64, 0u64, 3u64, 1u64)));
+    }
+
+    #[test]
+    fn group_with_known_order_should_be_complete() {
+        let puzzle = models::cube2();
+
+        let group = Group::new_with_order(puzzle.gset(), puzzle.generators(), 3_674_160);
+        let unknown = Group::new_with_order(puzzle.gset(), puzzle.generators(), 0);
+
+        assert_eq!(group.order(), 3_674_160);
+        assert_eq!(group.verify(), Ok(()));
+        assert_eq!(unknown.order(), 3_674_160);
+    }
+
+    #[test]
+    fn greedy_minimal_base_should_be_irredundant() {
+        let group = d3();
+
+        assert_eq!(group.greedy_minimal_base().len(), 2);
+
+        let a = permute!(0u64, 1u64, 1u64, 0u64, 2u64, 3u64, 3u64, 2u64);
+        let b = permute!(0u64, 2u64, 1u64, 3u64, 2u64, 0u64, 3u64, 1u64);
+        let gset = vec![0u64, 1u64, 2u64, 3u64];
+        let klein = Group::new(gset.clone(), vec![a.clone(), b.clone()]);
+
+        let base = klein.greedy_minimal_base();
+
+        assert_eq!(base, vec![0u64]);
+        assert_eq!(Group::with_base(gset, base, vec![a, b]).order(), 4);
+    }
+
+    #[test]
+    fn stabilizer_should_fix_the_point() {
+        let transposition = permute!(0u64, 1u64, 1u64, 0u64, 2u64, 2u64, 3u64, 3u64);
+        let rotation = permute!(0u64, 1u64, 1u64, 2u64, 2u64, 3u64, 3u64, 0u64);
+        let gset = vec![0u64, 1u64, 2u64, 3u64];
+        let group = Group::new(gset, vec![transposition, rotation]);
+
+        let changed = group.change_base(vec![2u64, 1u64]);
+        let stabilizer = group.stabilizer(2u64);
+
+        assert_eq!(changed.order(), 24);
+        assert_eq!(changed.levels[0].orbit.base, 2u64);
+        assert_eq!(changed.levels[1].orbit.base, 1u64);
+        assert_eq!(stabilizer.order(), 6);
+        assert!(stabilizer.is_member(permute!(0u64, 1u64, 1u64, 3u64, 2u64, 2u64, 3u64, 0u64)));
+        assert!(!stabilizer.is_member(permute!(0u64, 2u64, 1u64, 1u64, 2u64, 0u64, 3u64, 3u64)));
+    }
+
     #[test]
     fn transversal_for_should_correctly_determine_transversal() {
         let image = 4u64;
@@ -368,8 +1594,7 @@ mod tests {
         b_image.insert(5u64, 5u64);
         let b = Permutation::new(b_image);
         let generators = vec![a.clone(), b.clone()];
-        let mut indices: HashMap<u64, isize> = HashMap::new();
-        indices.insert(0u64, -1isize);
+        let mut indices: SchreierVector<u64> = SchreierVector::new(0u64);
         indices.insert(1u64, 0isize);
         indices.insert(2u64, 0isize);
         indices.insert(3u64, 1isize);
@@ -381,4 +1606,57 @@ mod tests {
         let expected = b.times(&a);
         assert_eq!(transversal, expected);
     }
+
+    #[test]
+    fn inverse_morphism_should_map_images_back() {
+        let mut images = HashMap::new();
+        images.insert(free::Word::generator('a'), free::Word::generator('x'));
+        images.insert(free::Word::generator('b'), free::Word::generator('y'));
+        let morphism = Morphism::new(images);
+
+        let inverse = morphism.inverse().unwrap();
+
+        assert_eq!(
+            inverse.transform(&free::Word::generator('y')),
+            free::Word::generator('b')
+        );
+        assert_eq!(inverse.try_transform(&free::Word::generator('b')), None);
+        assert_eq!(inverse.images().len(), 2);
+    }
+
+    #[test]
+    fn inverse_morphism_should_need_distinct_images() {
+        let mut images = HashMap::new();
+        images.insert(free::Word::generator('a'), free::Word::generator('x'));
+        images.insert(free::Word::generator('b'), free::Word::generator('x'));
+        let morphism = Morphism::new(images);
+
+        assert!(morphism.inverse().is_none());
+    }
+
+    #[test]
+    fn factorization_should_follow_the_convention() {
+        let group = d3();
+        let element = permute!(0u64, 2u64, 1u64, 1u64, 2u64, 0u64);
+
+        let one = element.times(&element.inverse());
+        let factorization = group.membership_certificate(element.clone()).unwrap();
+
+        let right_to_left = factorization
+            .transversals
+            .iter()
+            .fold(one.clone(), |product, t| product.times_left(t));
+        let left_to_right = factorization
+            .transversals
+            .iter()
+            .rev()
+            .fold(one, |product, t| product.times(t));
+        assert_eq!(right_to_left, element);
+        assert_eq!(left_to_right, element);
+        let rotation = permute!(0u64, 1u64, 1u64, 2u64, 2u64, 0u64);
+        assert_eq!(
+            element.times(&rotation).act_on_right(&0),
+            rotation.act_on_right(&element.act_on_right(&0))
+        );
+    }
 }
@@ -0,0 +1,255 @@
+//! The normalizer of a subgroup.
+//!
+//! The normalizer of _H_ in _G_ consists of the elements _g_ of _G_ with
+//! _g_^-1 _H_ _g_ = _H_. It is found by a backtrack search over the stabilizer
+//! chain of _G_, one level at a time from the bottom up, like the centralizer.
+//! The elements found so far, together with the elements of _H_ that lie in
+//! _G_, prune the base images that need a search.
+//!
+//! An element that normalizes _H_ maps every orbit of _H_ onto an orbit of _H_.
+//! So the orbits of _H_ form a system of blocks for the normalizer, and a
+//! partial choice of base images that maps two points of an orbit into
+//! different orbits, or into an orbit of another size, can not be completed.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let s4 = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation.clone()]);
+//! let cyclic = Group::with_base(vec![0, 1, 2, 3], vec![], vec![rotation]);
+//!
+//! assert_eq!(s4.normalizer(&cyclic).order(), 8);
+//! # }
+//! ```
+
+use super::orbit::Orbit;
+use super::{Group, GroupAction, GroupElement};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// The elements of this group that conjugate `subgroup` onto itself.
+    pub fn normalizer(&self, subgroup: &Group<Domain, G>) -> Group<Domain, G> {
+        let base = self.base();
+        let conjugated = subgroup.generators();
+        let orbits = Orbits::new(&self.gset, &conjugated);
+        let mut found: Vec<G> = conjugated
+            .iter()
+            .filter(|h| self.contains(h))
+            .cloned()
+            .collect();
+        for index in (0..self.levels.len()).rev() {
+            let level = &self.levels[index];
+            let stabilizing: Vec<G> = found
+                .iter()
+                .filter(|g| base[0..index].iter().all(|point| &g.act_on(point) == point))
+                .cloned()
+                .collect();
+            let mut known = Orbit::new(base[index].clone(), stabilizing);
+            let mut fixed = Assignment::new();
+            for point in &base[0..index] {
+                fixed.assign(&orbits, point, point);
+            }
+            for point in level.orbit.iter() {
+                if known.contains(point) {
+                    continue;
+                }
+                let mut assignment = fixed.clone();
+                if !assignment.assign(&orbits, &base[index], point) {
+                    continue;
+                }
+                let transversal = level.orbit.representative(point).expect("point in orbit");
+                if let Some(g) =
+                    self.normalizing_element(subgroup, &orbits, index + 1, transversal, &assignment)
+                {
+                    found.push(g);
+                    let stabilizing: Vec<G> = found
+                        .iter()
+                        .filter(|g| base[0..index].iter().all(|point| &g.act_on(point) == point))
+                        .cloned()
+                        .collect();
+                    known = Orbit::new(base[index].clone(), stabilizing);
+                }
+            }
+        }
+        Group::with_base(self.gset.clone(), base, found)
+    }
+
+    /// Extend `prefix`, a product of transversals of the levels before
+    /// `index` with the orbits assigned in `assignment`, to an element that
+    /// normalizes `subgroup`.
+    fn normalizing_element(
+        &self,
+        subgroup: &Group<Domain, G>,
+        orbits: &Orbits<Domain>,
+        index: usize,
+        prefix: G,
+        assignment: &Assignment,
+    ) -> Option<G> {
+        if index == self.levels.len() {
+            let normalizes = subgroup
+                .generators()
+                .iter()
+                .all(|h| subgroup.contains(&h.conjugate_by(&prefix)));
+            return if normalizes { Some(prefix) } else { None };
+        }
+        let level = &self.levels[index];
+        for point in level.orbit.iter() {
+            let image = prefix.act_on(point);
+            let mut extended = assignment.clone();
+            if !extended.assign(orbits, &level.orbit.base, &image) {
+                continue;
+            }
+            let transversal = level.orbit.representative(point).expect("point in orbit");
+            let found = self.normalizing_element(
+                subgroup,
+                orbits,
+                index + 1,
+                transversal.times(&prefix),
+                &extended,
+            );
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+}
+
+/// The orbits of a subgroup, by index.
+struct Orbits<Domain>
+where
+    Domain: Eq + Hash + Clone,
+{
+    index: HashMap<Domain, usize>,
+    sizes: Vec<usize>,
+}
+
+impl<Domain> Orbits<Domain>
+where
+    Domain: Eq + Hash + Clone,
+{
+    fn new<G>(gset: &[Domain], generators: &[G]) -> Orbits<Domain>
+    where
+        G: GroupElement + GroupAction<Domain = Domain> + Clone,
+    {
+        let mut index = HashMap::new();
+        let mut sizes = vec![];
+        for point in gset {
+            if index.contains_key(point) {
+                continue;
+            }
+            let orbit = Orbit::new(point.clone(), generators.to_vec());
+            for member in orbit.iter() {
+                index.insert(member.clone(), sizes.len());
+            }
+            sizes.push(orbit.len());
+        }
+        Orbits { index, sizes }
+    }
+
+    fn of(&self, point: &Domain) -> Option<usize> {
+        self.index.get(point).cloned()
+    }
+}
+
+/// The orbit of the subgroup that an orbit is mapped onto so far.
+#[derive(Clone)]
+struct Assignment {
+    images: HashMap<usize, usize>,
+    preimages: HashMap<usize, usize>,
+}
+
+impl Assignment {
+    fn new() -> Assignment {
+        Assignment {
+            images: HashMap::new(),
+            preimages: HashMap::new(),
+        }
+    }
+
+    /// Record that `point` maps to `image`. Returns whether that maps the
+    /// orbit of `point` onto an orbit of the same size, consistent with the
+    /// points recorded before.
+    fn assign<Domain>(&mut self, orbits: &Orbits<Domain>, point: &Domain, image: &Domain) -> bool
+    where
+        Domain: Eq + Hash + Clone,
+    {
+        let (from, to) = match (orbits.of(point), orbits.of(image)) {
+            (Some(from), Some(to)) => (from, to),
+            (None, None) => return true,
+            _ => return false,
+        };
+        if orbits.sizes[from] != orbits.sizes[to] {
+            return false;
+        }
+        match (self.images.get(&from), self.preimages.get(&to)) {
+            (None, None) => {
+                self.images.insert(from, to);
+                self.preimages.insert(to, from);
+                true
+            }
+            (Some(&known), _) => known == to,
+            (None, Some(_)) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::permutation::Permutation;
+    use super::super::testing::s4;
+    use super::*;
+
+    #[test]
+    fn normalizer_of_a_three_cycle_should_be_a_point_stabilizer() {
+        let group = s4();
+        let cyclic = Group::with_base(
+            vec![0, 1, 2, 3],
+            vec![],
+            vec![permute!(0, 1, 1, 2, 2, 0, 3, 3)],
+        );
+
+        let normalizer = group.normalizer(&cyclic);
+
+        assert_eq!(normalizer.order(), 6);
+        assert!(normalizer.contains(&permute!(0, 1, 1, 0, 2, 2, 3, 3)));
+    }
+
+    #[test]
+    fn normal_subgroups_should_be_normalized_by_everything() {
+        let group = s4();
+        let klein = Group::with_base(
+            vec![0, 1, 2, 3],
+            vec![],
+            vec![
+                permute!(0, 1, 1, 0, 2, 3, 3, 2),
+                permute!(0, 2, 1, 3, 2, 0, 3, 1),
+            ],
+        );
+
+        assert_eq!(group.normalizer(&klein).order(), 24);
+        assert_eq!(group.normalizer(&group).order(), 24);
+    }
+
+    #[test]
+    fn normalizer_of_a_transposition_should_be_its_centralizer() {
+        let group = s4();
+        let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+        let subgroup = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition.clone()]);
+
+        assert_eq!(
+            group.normalizer(&subgroup).order(),
+            group.centralizer(&transposition).order()
+        );
+    }
+}
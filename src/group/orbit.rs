@@ -0,0 +1,409 @@
+//! The orbit of a point under a set of generators.
+//!
+//! Besides the points of the orbit, an `Orbit` keeps a
+//! [Schreier vector](https://en.wikipedia.org/wiki/Schreier_vector). With it, a
+//! representative can be found for every point: a group element that maps the
+//! base point to it.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::GroupAction;
+//! # use permutation_rs::group::orbit::Orbit;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let rotation = permute!(0u64, 1u64, 1u64, 2u64, 2u64, 0u64, 3u64, 3u64);
+//! let orbit = Orbit::new(0u64, vec![rotation]);
+//!
+//! assert_eq!(orbit.len(), 3);
+//! assert!(!orbit.contains(&3u64));
+//! assert_eq!(orbit.representative(&2u64).unwrap().act_on(&0u64), 2u64);
+//! # }
+//! ```
+
+use super::{Group, GroupAction, GroupElement};
+use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet};
+use std::error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+
+/// The orbit of a base point under a set of generators.
+pub struct Orbit<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain>,
+{
+    /// The point the orbit starts from.
+    pub(super) base: Domain,
+    /// Generators that act on the base to form the orbit.
+    pub(super) generators: Vec<G>,
+    /// How each point of the orbit is reached.
+    pub(super) vector: SchreierVector<Domain>,
+    /// The representative of every point, when they are stored.
+    pub(super) transversals: Option<HashMap<Domain, G>>,
+}
+
+impl<Domain, G> Orbit<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain>,
+{
+    /// Determine the orbit of `base` under `generators`.
+    pub fn new(base: Domain, generators: Vec<G>) -> Orbit<Domain, G> {
+        let vector = schreier_vector(&base, &generators);
+        Orbit::from_parts(base, generators, vector)
+    }
+
+    pub(super) fn from_parts(
+        base: Domain,
+        generators: Vec<G>,
+        vector: SchreierVector<Domain>,
+    ) -> Orbit<Domain, G> {
+        Orbit {
+            base,
+            generators,
+            vector,
+            transversals: None,
+        }
+    }
+
+    /// Store the representative of every point, so that `representative`
+    /// looks it up instead of multiplying it out along the Schreier vector.
+    pub(super) fn store_transversals(&mut self) {
+        let transversals = self
+            .vector
+            .points
+            .iter()
+            .map(|point| {
+                let transversal = transversal_for(point, &self.generators, &self.vector)
+                    .expect("point should be in the orbit");
+                (point.clone(), transversal)
+            })
+            .collect();
+        self.transversals = Some(transversals);
+    }
+
+    /// The point the orbit starts from.
+    pub fn base(&self) -> &Domain {
+        &self.base
+    }
+
+    /// The generators that form the orbit.
+    pub fn generators(&self) -> &[G] {
+        &self.generators
+    }
+
+    /// Determine if `point` is in the orbit.
+    pub fn contains(&self, point: &Domain) -> bool {
+        self.vector.contains_key(point)
+    }
+
+    /// The number of points in the orbit.
+    pub fn len(&self) -> usize {
+        self.vector.len()
+    }
+
+    /// An orbit always contains its base, so it is never empty.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// A group element that maps the base to `point`, or `None` when `point`
+    /// is not in the orbit.
+    pub fn representative(&self, point: &Domain) -> Option<G> {
+        match self.transversals {
+            // `G` need not be `Clone`, so the stored transversal is copied by
+            // a product with the identity.
+            Some(ref transversals) => transversals
+                .get(point)
+                .map(|transversal| G::identity().times(transversal)),
+            None => transversal_for(point, &self.generators, &self.vector),
+        }
+    }
+
+    /// The points of the orbit, in the order they were found.
+    pub fn iter(&self) -> impl Iterator<Item = &Domain> {
+        self.vector.points.iter()
+    }
+}
+
+impl<Domain, G> Orbit<Domain, G>
+where
+    Domain: Eq + Hash + Clone + Display,
+    G: GroupElement + GroupAction<Domain = Domain>,
+{
+    /// The Schreier tree of this orbit in the
+    /// [DOT](https://graphviz.org/doc/info/lang.html) language. There is a node
+    /// for every point and an edge, labeled by the index of its generator, from
+    /// every point but the base to the point it is reached from.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph schreier {\n");
+        for line in self.dot_statements("") {
+            dot.push_str(&format!("  {}\n", line));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// The DOT statements of the Schreier tree, with node names starting with
+    /// `prefix`.
+    pub(super) fn dot_statements(&self, prefix: &str) -> Vec<String> {
+        let inverses: Vec<G> = self.generators.iter().map(|g| g.inverse()).collect();
+        let mut statements = vec![];
+        for point in self.iter() {
+            statements.push(format!("\"{}{}\" [label=\"{}\"];", prefix, point, point));
+        }
+        for (point, index) in self.vector.iter() {
+            if index >= 0 {
+                let from = inverses[index as usize].act_on(point);
+                statements.push(format!(
+                    "\"{}{}\" -> \"{}{}\" [label=\"{}\"];",
+                    prefix, from, prefix, point, index
+                ));
+            }
+        }
+        statements
+    }
+}
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
+{
+    /// The orbit of `seed` under this group, where `act` tells how an element
+    /// acts on a state, in the order the states are found.
+    ///
+    /// The states can be anything, e.g. sets or tuples of points, or the
+    /// positions of a part of a puzzle. `act` should be a right action. The
+    /// search stops with an error when the orbit has more than `limit` states.
+    pub fn orbit_of<T, F>(
+        &self,
+        seed: T,
+        act: F,
+        limit: usize,
+    ) -> Result<Vec<T>, OrbitLimitExceeded>
+    where
+        T: Eq + Hash + Clone,
+        F: Fn(&G, &T) -> T,
+    {
+        let generators: &[G] = self
+            .levels
+            .first()
+            .map_or(&[], |level| &level.orbit.generators);
+        let mut seen: HashSet<T> = HashSet::new();
+        let mut states = vec![];
+        let mut to_visit: VecDeque<T> = VecDeque::new();
+        seen.insert(seed.clone());
+        to_visit.push_back(seed);
+        while let Some(state) = to_visit.pop_front() {
+            for generator in generators {
+                let image = act(generator, &state);
+                if seen.insert(image.clone()) {
+                    if seen.len() > limit {
+                        return Err(OrbitLimitExceeded { limit });
+                    }
+                    to_visit.push_back(image);
+                }
+            }
+            states.push(state);
+        }
+        if states.len() > limit {
+            return Err(OrbitLimitExceeded { limit });
+        }
+        Ok(states)
+    }
+}
+
+/// An orbit that has more states than it was allowed to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrbitLimitExceeded {
+    /// The number of states the orbit was allowed to have.
+    pub limit: usize,
+}
+
+impl Display for OrbitLimitExceeded {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "orbit has more than {} states", self.limit)
+    }
+}
+
+impl error::Error for OrbitLimitExceeded {}
+
+/// A [Schreier vector](https://en.wikipedia.org/wiki/Schreier_vector) that
+/// remembers the order in which the points of the orbit were found, so that
+/// iterating over it is deterministic.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct SchreierVector<Domain>
+where
+    Domain: Eq + Hash + Clone,
+{
+    points: Vec<Domain>,
+    indices: HashMap<Domain, isize>,
+}
+
+impl<Domain> SchreierVector<Domain>
+where
+    Domain: Eq + Hash + Clone,
+{
+    /// A Schreier vector containing only `base`.
+    pub(super) fn new(base: Domain) -> SchreierVector<Domain> {
+        let mut indices = HashMap::new();
+        indices.insert(base.clone(), -1);
+        SchreierVector {
+            points: vec![base],
+            indices,
+        }
+    }
+
+    /// Record that `point` is reached with the generator at `index`.
+    pub(super) fn insert(&mut self, point: Domain, index: isize) {
+        if self.indices.insert(point.clone(), index).is_none() {
+            self.points.push(point);
+        }
+    }
+
+    pub(super) fn contains_key(&self, point: &Domain) -> bool {
+        self.indices.contains_key(point)
+    }
+
+    pub(super) fn get(&self, point: &Domain) -> Option<&isize> {
+        self.indices.get(point)
+    }
+
+    fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// The points of the orbit with their index, in the order they were found.
+    pub(super) fn iter(&self) -> impl Iterator<Item = (&Domain, isize)> {
+        self.points
+            .iter()
+            .map(move |point| (point, self.indices[point]))
+    }
+}
+
+pub(super) fn schreier_vector<Domain, G>(base: &Domain, generators: &[G]) -> SchreierVector<Domain>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain>,
+{
+    let mut to_visit: VecDeque<Domain> = VecDeque::new();
+    let mut indices: SchreierVector<Domain> = SchreierVector::new(base.clone());
+    to_visit.push_back(base.clone());
+    while let Some(element) = to_visit.pop_front() {
+        for (index, generator) in generators.iter().enumerate() {
+            let image = generator.act_on(&element);
+            if !indices.contains_key(&image) {
+                indices.insert(image.clone(), index as isize);
+                to_visit.push_back(image);
+            }
+        }
+    }
+    indices
+}
+
+pub(super) fn transversal_for<Domain, G>(
+    start: &Domain,
+    generators: &[G],
+    indices: &SchreierVector<Domain>,
+) -> Option<G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain>,
+{
+    let mut image = start.clone();
+
+    if indices.contains_key(&image) {
+        let mut transversal = G::identity();
+        let mut index = indices.get(&image).unwrap();
+        while *index != -1_isize {
+            let generator = &generators[*index as usize];
+            let inverse = generator.inverse();
+            image = inverse.act_on(&image);
+            transversal = transversal.times(&inverse);
+            index = indices.get(&image).unwrap();
+        }
+        Some(transversal.inverse())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::permutation::Permutation;
+    use super::*;
+
+    #[test]
+    fn orbit_should_list_points_in_the_order_they_are_found() {
+        let a = permute!(0u64, 1u64, 1u64, 0u64, 2u64, 2u64, 3u64, 3u64);
+        let b = permute!(0u64, 0u64, 1u64, 2u64, 2u64, 3u64, 3u64, 1u64);
+
+        let orbit = Orbit::new(0u64, vec![a, b]);
+
+        let points: Vec<u64> = orbit.iter().cloned().collect();
+        assert_eq!(points, vec![0u64, 1u64, 2u64, 3u64]);
+    }
+
+    #[test]
+    fn representative_should_map_base_to_point() {
+        let a = permute!(0u64, 1u64, 1u64, 0u64, 2u64, 2u64, 3u64, 3u64);
+        let b = permute!(0u64, 0u64, 1u64, 2u64, 2u64, 3u64, 3u64, 1u64);
+        let orbit = Orbit::new(0u64, vec![a, b]);
+
+        for point in 0u64..4u64 {
+            let representative = orbit.representative(&point).unwrap();
+
+            assert_eq!(representative.act_on(&0u64), point);
+        }
+    }
+
+    #[test]
+    fn representative_should_not_exist_outside_the_orbit() {
+        let a = permute!(0u64, 1u64, 1u64, 0u64, 2u64, 2u64);
+        let orbit = Orbit::new(0u64, vec![a]);
+
+        assert_eq!(orbit.representative(&2u64), None);
+    }
+
+    #[test]
+    fn orbit_of_should_act_on_states() {
+        let group = Group::with_base(
+            vec![0u64, 1u64, 2u64, 3u64],
+            vec![],
+            vec![
+                permute!(0u64, 1u64, 1u64, 0u64, 2u64, 2u64, 3u64, 3u64),
+                permute!(0u64, 1u64, 1u64, 2u64, 2u64, 3u64, 3u64, 0u64),
+            ],
+        );
+        let act = |g: &Permutation, pair: &Vec<u64>| {
+            let mut image: Vec<u64> = pair.iter().map(|point| g.act_on(point)).collect();
+            image.sort_unstable();
+            image
+        };
+
+        let pairs = group.orbit_of(vec![0u64, 1u64], act, 10).unwrap();
+
+        assert_eq!(pairs.len(), 6);
+        assert_eq!(pairs[0], vec![0u64, 1u64]);
+        assert_eq!(
+            group.orbit_of(vec![0u64, 1u64], act, 5),
+            Err(OrbitLimitExceeded { limit: 5 })
+        );
+    }
+
+    #[test]
+    fn dot_should_draw_the_schreier_tree() {
+        let rotation = permute!(0u64, 1u64, 1u64, 2u64, 2u64, 0u64);
+        let orbit = Orbit::new(0u64, vec![rotation]);
+
+        assert_eq!(
+            orbit.to_dot(),
+            "digraph schreier {\n  \"0\" [label=\"0\"];\n  \"1\" [label=\"1\"];\n  \"2\" [label=\"2\"];\n  \"0\" -> \"1\" [label=\"0\"];\n  \"1\" -> \"2\" [label=\"0\"];\n}\n"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Construct groups on all cores with [rayon](https://docs.rs/rayon).
+//!
+//! The orbit of a base point is expanded one frontier at a time, computing the
+//! images of the frontier in parallel. The Schreier generators of a level are
+//! formed and sifted in parallel as well. Both steps keep the order of the
+//! sequential algorithm, so the resulting group is the same as the group
+//! `Group::with_base` creates.
+
+use super::{
+    non_sifting_schreier_generator_at, schreier_sims_with, BaseStrongGeneratorLevel, Group,
+    GroupAction, GroupElement, SchreierVector, Silent, Strategy,
+};
+use rayon::prelude::*;
+use std::hash::Hash;
+
+/// Perform the expensive steps of the Schreier-Sims algorithm in parallel.
+pub(super) struct Parallel;
+
+impl<Domain, G> Strategy<Domain, G> for Parallel
+where
+    Domain: Eq + Hash + Clone + Send + Sync,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone + Send + Sync,
+{
+    fn schreier_vector(&self, base: &Domain, generators: &[G]) -> SchreierVector<Domain> {
+        let mut indices: SchreierVector<Domain> = SchreierVector::new(base.clone());
+        let mut frontier: Vec<Domain> = vec![base.clone()];
+        while !frontier.is_empty() {
+            let images: Vec<Vec<Domain>> = frontier
+                .par_iter()
+                .map(|point| generators.iter().map(|g| g.act_on(point)).collect())
+                .collect();
+            let mut next: Vec<Domain> = vec![];
+            for image in images {
+                for (index, point) in image.into_iter().enumerate() {
+                    if !indices.contains_key(&point) {
+                        indices.insert(point.clone(), index as isize);
+                        next.push(point);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        indices
+    }
+
+    fn non_sifting_schreier_generator(
+        &self,
+        gset: &[Domain],
+        levels: &[BaseStrongGeneratorLevel<Domain, G>],
+        index: usize,
+    ) -> Option<(G, usize)> {
+        let level = &levels[index];
+        if level.orbit.generators.is_empty() {
+            return None;
+        }
+        let points: Vec<&Domain> = gset.iter().filter(|p| level.orbit.contains(p)).collect();
+        points
+            .par_iter()
+            .find_map_first(|point| non_sifting_schreier_generator_at(levels, index, point))
+    }
+}
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone + Send + Sync,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone + Send + Sync,
+{
+    /// Creates a group with a given set of generators on a certain gset, using
+    /// all cores.
+    ///
+    /// The result is the same as the result of `Group::with_base` with an empty
+    /// base, only faster for large generator sets.
+    pub fn new_parallel(gset: Vec<Domain>, generators: Vec<G>) -> Group<Domain, G> {
+        let levels = schreier_sims_with(&Parallel, &mut Silent, &gset, &[], generators)
+            .expect("silent monitor should not interrupt");
+        Group { gset, levels }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::models;
+    use super::super::permutation::Permutation;
+    use super::super::tree::SyncSLPFactory;
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn new_parallel_should_create_the_same_group() {
+        let puzzle = models::cube2();
+
+        let sequential = Group::with_base(puzzle.gset(), vec![], puzzle.generators());
+        let parallel = Group::new_parallel(puzzle.gset(), puzzle.generators());
+
+        assert_eq!(parallel.order(), 3_674_160);
+        assert_eq!(parallel.levels.len(), sequential.levels.len());
+        for (left, right) in parallel.levels.iter().zip(sequential.levels.iter()) {
+            assert_eq!(left.orbit.base, right.orbit.base);
+            assert_eq!(left.orbit.generators, right.orbit.generators);
+            assert_eq!(left.orbit.vector, right.orbit.vector);
+        }
+    }
+
+    #[test]
+    fn new_parallel_should_record_sync_slp_words() {
+        let factory = SyncSLPFactory::new();
+        let generators = vec![
+            factory.generator(permute!(0, 1, 1, 2, 2, 3, 3, 0)),
+            factory.generator(permute!(0, 1, 1, 0, 2, 2, 3, 3)),
+        ];
+
+        let group = Group::new_parallel(vec![0, 1, 2, 3], generators);
+
+        assert_eq!(group.order(), 24);
+    }
+}
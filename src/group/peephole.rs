@@ -0,0 +1,286 @@
+//! Shorten sequences of moves by merging the moves that meet.
+//!
+//! Solutions from a stabilizer chain are products of many short words, so
+//! they are full of moves that cancel: `R R` is `R2`, `U U'` is nothing at all.
+//! Free reduction only merges moves that are written next to each other. Two
+//! more facts of a puzzle shorten a word further. A move has an order, so on a
+//! cube `R3` is `R'` and `R2 R2` is nothing. And moves that commute can change
+//! places, so in `U D U` the turns of `U` meet and form `U2 D`.
+//!
+//! `MoveRules` holds the orders of the moves and the pairs of moves that
+//! commute, and `optimize_moves` merges moves with them until nothing changes.
+//! A `Puzzle` derives its rules from its moves.
+//!
+//! Words that only differ in the order of commuting moves are equal, like
+//! `U D` and `D U`. `Word::normal_form_with_commutations` picks one of them: of
+//! the moves that can be brought to the front, it writes the one that was
+//! declared first, and repeats with the rest of the word. Two words are equal
+//! under the rules exactly when their normal forms are.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::group::free::Word;
+//! # use permutation_rs::group::peephole::{optimize_moves, MoveRules};
+//! let rules = MoveRules::new()
+//!     .with_order('U', 4)
+//!     .with_order('D', 4)
+//!     .with_commuting('U', 'D');
+//! let word = Word::new(vec![('U', 1), ('D', 1), ('U', 2), ('D', -1)]);
+//!
+//! assert_eq!(optimize_moves(&word, &rules), Word::new(vec![('U', -1)]));
+//!
+//! let swapped = Word::new(vec![('D', 1), ('U', 1)]);
+//! assert_eq!(
+//!     swapped.normal_form_with_commutations(&rules),
+//!     Word::new(vec![('U', 1), ('D', 1)])
+//! );
+//! ```
+
+use super::free::Word;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// The orders of moves and the pairs of moves that commute.
+///
+/// The moves are ordered by their first declaration, which decides the order
+/// of commuting moves in a normal form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveRules<S>
+where
+    S: Eq + Hash,
+{
+    symbols: Vec<S>,
+    orders: HashMap<S, u64>,
+    commuting: HashSet<(S, S)>,
+}
+
+impl<S> MoveRules<S>
+where
+    S: Eq + Hash + Clone,
+{
+    /// Rules without orders and without commuting moves, which only allow free
+    /// reduction.
+    pub fn new() -> MoveRules<S> {
+        MoveRules {
+            symbols: vec![],
+            orders: HashMap::new(),
+            commuting: HashSet::new(),
+        }
+    }
+
+    /// Declare that `symbol` has order `order`, i.e. that `order` of them in a
+    /// row do nothing. An order of 0 means that no power does nothing.
+    pub fn with_order(mut self, symbol: S, order: u64) -> MoveRules<S> {
+        self.declare(&symbol);
+        self.orders.insert(symbol, order);
+        self
+    }
+
+    /// Declare that `left` and `right` commute.
+    pub fn with_commuting(mut self, left: S, right: S) -> MoveRules<S> {
+        self.declare(&left);
+        self.declare(&right);
+        self.commuting.insert((right.clone(), left.clone()));
+        self.commuting.insert((left, right));
+        self
+    }
+
+    /// The order of `symbol`, when it is known.
+    pub fn order(&self, symbol: &S) -> Option<u64> {
+        self.orders.get(symbol).cloned().filter(|order| *order > 0)
+    }
+
+    /// Determine if `left` and `right` commute. Every move commutes with
+    /// itself.
+    pub fn commute(&self, left: &S, right: &S) -> bool {
+        left == right || self.commuting.contains(&(left.clone(), right.clone()))
+    }
+
+    /// The position of `symbol` among the declared moves.
+    pub fn rank(&self, symbol: &S) -> Option<usize> {
+        self.symbols.iter().position(|declared| declared == symbol)
+    }
+
+    fn declare(&mut self, symbol: &S) {
+        if self.rank(symbol).is_none() {
+            self.symbols.push(symbol.clone());
+        }
+    }
+
+    /// The exponent of `symbol` that has the same effect as `exponent` and is
+    /// as small as possible. Of two equally small exponents the positive one
+    /// is chosen, so a half turn is written `R2`.
+    pub fn reduce_exponent(&self, symbol: &S, exponent: i64) -> i64 {
+        match self.order(symbol) {
+            Some(order) => {
+                let order = order as i64;
+                let exponent = exponent.rem_euclid(order);
+                if 2 * exponent > order {
+                    exponent - order
+                } else {
+                    exponent
+                }
+            }
+            None => exponent,
+        }
+    }
+}
+
+impl<S> Default for MoveRules<S>
+where
+    S: Eq + Hash + Clone,
+{
+    fn default() -> MoveRules<S> {
+        MoveRules::new()
+    }
+}
+
+/// Shorten `word` by merging moves that meet, possibly after moving them past
+/// moves they commute with, and by reducing exponents modulo the order of
+/// their move. Merging repeats until nothing changes.
+pub fn optimize_moves<S>(word: &Word<S>, rules: &MoveRules<S>) -> Word<S>
+where
+    S: Eq + Hash + Clone,
+{
+    let mut syllables: Vec<(S, i64)> = word.iter().cloned().collect();
+    loop {
+        let merged = merge(&syllables, rules);
+        if merged == syllables {
+            return Word::new(merged);
+        }
+        syllables = merged;
+    }
+}
+
+impl<S> Word<S>
+where
+    S: Eq + Hash + Clone,
+{
+    /// The normal form of this word under `rules`. Moves are merged like
+    /// `optimize_moves` does, after which commuting moves are ordered by their
+    /// declaration: the first move written is the first declared move that can
+    /// be brought to the front of the word.
+    ///
+    /// Words that are equal under the rules have the same normal form.
+    pub fn normal_form_with_commutations(&self, rules: &MoveRules<S>) -> Word<S> {
+        let mut rest: Vec<(S, i64)> = optimize_moves(self, rules).iter().cloned().collect();
+        let mut ordered = vec![];
+        while !rest.is_empty() {
+            let first = (0..rest.len())
+                .filter(|&index| {
+                    let symbol = &rest[index].0;
+                    rest[..index]
+                        .iter()
+                        .all(|(other, _)| other != symbol && rules.commute(other, symbol))
+                })
+                .min_by_key(|&index| rules.rank(&rest[index].0).unwrap_or(usize::MAX))
+                .expect("the first move can always be brought to the front");
+            ordered.push(rest.remove(first));
+        }
+        Word::new(ordered)
+    }
+}
+
+/// One pass of merging. Every syllable looks back past the syllables it
+/// commutes with for a syllable of the same move to merge with.
+fn merge<S>(syllables: &[(S, i64)], rules: &MoveRules<S>) -> Vec<(S, i64)>
+where
+    S: Eq + Hash + Clone,
+{
+    let mut merged: Vec<(S, i64)> = vec![];
+    for (symbol, exponent) in syllables {
+        let partner = merged
+            .iter()
+            .rposition(|(other, _)| other == symbol || !rules.commute(other, symbol))
+            .filter(|index| merged[*index].0 == *symbol);
+        match partner {
+            Some(index) => {
+                let exponent = rules.reduce_exponent(symbol, merged[index].1 + exponent);
+                if exponent == 0 {
+                    merged.remove(index);
+                } else {
+                    merged[index].1 = exponent;
+                }
+            }
+            None => {
+                let exponent = rules.reduce_exponent(symbol, *exponent);
+                if exponent != 0 {
+                    merged.push((symbol.clone(), exponent));
+                }
+            }
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube_rules() -> MoveRules<char> {
+        "UDLRFB"
+            .chars()
+            .fold(MoveRules::new(), |rules, face| rules.with_order(face, 4))
+            .with_commuting('U', 'D')
+            .with_commuting('L', 'R')
+    }
+
+    #[test]
+    fn adjacent_moves_should_merge_modulo_their_order() {
+        let rules = cube_rules();
+
+        let word = Word::new(vec![('R', 1), ('U', 1), ('U', -1), ('R', 1)]);
+        let turns = Word::new(vec![('R', 3), ('F', 2), ('F', 2)]);
+
+        assert_eq!(optimize_moves(&word, &rules), Word::new(vec![('R', 2)]));
+        assert_eq!(optimize_moves(&turns, &rules), Word::new(vec![('R', -1)]));
+    }
+
+    #[test]
+    fn moves_should_merge_past_commuting_moves_only() {
+        let rules = cube_rules();
+
+        let commuting = Word::new(vec![('U', 1), ('D', 1), ('U', 1)]);
+        let blocked = Word::new(vec![('U', 1), ('R', 1), ('U', 1)]);
+
+        assert_eq!(
+            optimize_moves(&commuting, &rules),
+            Word::new(vec![('U', 2), ('D', 1)])
+        );
+        assert_eq!(optimize_moves(&blocked, &rules), blocked);
+    }
+
+    #[test]
+    fn equal_words_should_have_the_same_normal_form() {
+        let rules = cube_rules();
+
+        let word = Word::new(vec![('D', 1), ('R', 1), ('L', 1), ('U', 1), ('D', 1)]);
+        let other = Word::new(vec![('D', 1), ('L', 1), ('R', 1), ('D', 1), ('U', 1)]);
+        let different = Word::new(vec![('D', 1), ('L', 1), ('U', 1), ('R', 1), ('D', 1)]);
+
+        let normal = word.normal_form_with_commutations(&rules);
+
+        assert_eq!(
+            normal,
+            Word::new(vec![('D', 1), ('L', 1), ('R', 1), ('U', 1), ('D', 1)])
+        );
+        assert_eq!(other.normal_form_with_commutations(&rules), normal);
+        assert_ne!(different.normal_form_with_commutations(&rules), normal);
+    }
+
+    #[test]
+    fn cancellation_should_let_the_surrounding_moves_meet() {
+        let rules = cube_rules();
+
+        let word = Word::new(vec![
+            ('R', 1),
+            ('U', 1),
+            ('D', 1),
+            ('U', -1),
+            ('D', -1),
+            ('R', 1),
+        ]);
+
+        assert_eq!(optimize_moves(&word, &rules), Word::new(vec![('R', 2)]));
+    }
+}
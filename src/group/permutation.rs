@@ -50,12 +50,30 @@
 //! );
 //! # }
 //! ```
+//!
+//! Permutations of 0..n can also be read from and written to one-line
+//! notation, the list of images in order.
+//!
+//! ```rust
+//! # use permutation_rs::group::permutation::Permutation;
+//! let left = Permutation::from_one_line(&[1, 0, 2]).unwrap();
+//!
+//! assert_eq!(left.to_one_line_one_based(), vec![2, 1, 3]);
+//! ```
 
+use super::statistics::lcm;
 use super::{GroupAction, GroupElement};
+#[cfg(any(test, feature = "arbitrary"))]
+use arbitrary::{Arbitrary, Unstructured};
+#[cfg(feature = "nalgebra")]
+use nalgebra::DMatrix;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::error;
 use std::fmt;
 use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+use std::iter;
 
 /// Creates a permutation by specifiying images
 ///
@@ -83,60 +101,404 @@ macro_rules! permute {
     }
 }
 
-/// A permutation of the set 0..n for a suitable choice of n.
-#[derive(Debug, PartialEq, Clone)]
+/// A permutation of its domain: the points it is given an image for.
+///
+/// The domain does not have to be of the form 0..n, e.g. a permutation can be
+/// defined on only `5` and `6`. Points outside the domain are fixed. The product
+/// of two permutations is defined on the union of their domains.
+///
+/// Two permutations are equal when they move the same points to the same
+/// images, regardless of the fixed points in their domains. Hashing agrees with
+/// this, so permutations can be used as keys.
+#[derive(Debug, Clone)]
 pub struct Permutation {
-    n: usize,
+    domain: Vec<u64>,
     images: HashMap<u64, u64>,
 }
 
 impl Permutation {
     /// Create an permutation with a given image.
+    ///
+    /// Panics when the images are not a bijection of the keys, see `try_new`.
     pub fn new(images: HashMap<u64, u64>) -> Permutation {
-        let n = images.len();
-        Permutation {
-            images: images,
-            n: n,
+        match Permutation::try_new(images) {
+            Ok(permutation) => permutation,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Create a permutation with a given image, provided the images are a
+    /// bijection of the keys.
+    pub fn try_new(images: HashMap<u64, u64>) -> Result<Permutation, PermutationError> {
+        let mut seen: HashSet<u64> = HashSet::with_capacity(images.len());
+        for image in images.values() {
+            if !images.contains_key(image) {
+                return Err(PermutationError::ImageOutsideDomain(*image));
+            }
+            if !seen.insert(*image) {
+                return Err(PermutationError::DuplicateImage(*image));
+            }
+        }
+        Ok(Permutation::new_unchecked(images))
+    }
+
+    /// Create a permutation with a given image without checking that the
+    /// images are a bijection of the keys. Use this only for images that are
+    /// known to be valid.
+    pub fn new_unchecked(images: HashMap<u64, u64>) -> Permutation {
+        let mut domain: Vec<u64> = images.keys().cloned().collect();
+        domain.sort_unstable();
+        Permutation { domain, images }
+    }
+
+    /// Create the permutation of 0..n in one-line notation: the point `i` maps
+    /// to `images[i]`.
+    pub fn from_one_line(images: &[u64]) -> Result<Permutation, PermutationError> {
+        let images: HashMap<u64, u64> = images
+            .iter()
+            .enumerate()
+            .map(|(point, image)| (point as u64, *image))
+            .collect();
+        Permutation::try_new(images)
+    }
+
+    /// Create a permutation from the one-based one-line notation of the
+    /// literature, e.g. `[2, 3, 1]`, relabeling the points 1..=n to 0..n.
+    pub fn from_one_line_one_based(images: &[u64]) -> Result<Permutation, PermutationError> {
+        let mut zero_based = Vec::with_capacity(images.len());
+        for image in images {
+            if *image == 0 {
+                return Err(PermutationError::ImageOutsideDomain(0));
+            }
+            zero_based.push(image - 1);
+        }
+        Permutation::from_one_line(&zero_based).map_err(|error| match error {
+            PermutationError::DuplicateImage(image) => PermutationError::DuplicateImage(image + 1),
+            PermutationError::ImageOutsideDomain(image) => {
+                PermutationError::ImageOutsideDomain(image + 1)
+            }
+        })
+    }
+
+    /// The one-line notation on `0..=max`, where `max` is the largest point
+    /// of the domain: the image of every point in increasing order. Points
+    /// outside the domain are fixed.
+    pub fn to_one_line(&self) -> Vec<u64> {
+        let length = self.domain.last().map_or(0, |point| point + 1);
+        (0..length)
+            .map(|point| *self.images.get(&point).unwrap_or(&point))
+            .collect()
+    }
+
+    /// The one-line notation on `0..n`.
+    ///
+    /// Panics when the permutation moves a point outside `0..n`.
+    fn to_one_line_of(&self, n: u64) -> Vec<u64> {
+        self.assert_acts_below(n);
+        (0..n)
+            .map(|point| *self.images.get(&point).unwrap_or(&point))
+            .collect()
+    }
+
+    /// The one-based one-line notation, i.e. `to_one_line` with every point
+    /// increased by one.
+    pub fn to_one_line_one_based(&self) -> Vec<u64> {
+        self.to_one_line().iter().map(|image| image + 1).collect()
+    }
+
+    /// All permutations of 0..n in lexicographic order of their one-line
+    /// notation, starting with the identity.
+    pub fn all(n: u64) -> impl Iterator<Item = Permutation> {
+        let identity = Permutation::new_unchecked((0..n).map(|point| (point, point)).collect());
+        iter::successors(Some(identity), move |permutation| {
+            permutation.next_permutation(n)
+        })
+    }
+
+    /// The permutation of 0..n that follows this one in lexicographic order
+    /// of the one-line notation, or `None` when this is the last one.
+    ///
+    /// Panics when the permutation moves a point outside `0..n`.
+    pub fn next_permutation(&self, n: u64) -> Option<Permutation> {
+        let mut line = self.to_one_line_of(n);
+        let pivot = (1..line.len()).rev().find(|&i| line[i - 1] < line[i])? - 1;
+        let successor = (pivot + 1..line.len())
+            .rev()
+            .find(|&i| line[i] > line[pivot])
+            .expect("a larger point follows the pivot");
+        line.swap(pivot, successor);
+        line[pivot + 1..].reverse();
+        Some(Permutation::new_unchecked(
+            (0..n).zip(line.iter().cloned()).collect(),
+        ))
+    }
+
+    /// The position of this permutation among the permutations of 0..n in
+    /// lexicographic order, computed from its Lehmer code. The identity has
+    /// rank 0. Points outside the domain are fixed, so equal permutations have
+    /// equal ranks.
+    ///
+    /// Panics when the permutation moves a point outside `0..n`, and for more
+    /// than 34 points, whose ranks do not fit in a `u128`.
+    pub fn rank(&self, n: u64) -> u128 {
+        let line = self.to_one_line_of(n);
+        let n = line.len();
+        (0..n).fold(0u128, |rank, i| {
+            let smaller = line[i + 1..]
+                .iter()
+                .filter(|&&later| later < line[i])
+                .count();
+            rank.checked_mul((n - i) as u128)
+                .and_then(|rank| rank.checked_add(smaller as u128))
+                .expect("the rank should fit in a u128")
+        })
+    }
+
+    /// The permutation of 0..n with `rank`, the inverse of `rank`, or `None`
+    /// when `rank` is not less than _n_!.
+    pub fn unrank(n: u64, rank: u128) -> Option<Permutation> {
+        let mut digits = vec![0u128; n as usize];
+        let mut rest = rank;
+        for (index, digit) in digits.iter_mut().enumerate().rev() {
+            let radix = (n as usize - index) as u128;
+            *digit = rest % radix;
+            rest /= radix;
+        }
+        if rest != 0 {
+            return None;
+        }
+        let mut available: Vec<u64> = (0..n).collect();
+        let line: Vec<u64> = digits
+            .iter()
+            .map(|&digit| available.remove(digit as usize))
+            .collect();
+        Some(Permutation::from_one_line(&line).expect("Lehmer code gives a permutation"))
+    }
+
+    /// The points this permutation is defined on, in increasing order.
+    pub fn domain(&self) -> &[u64] {
+        &self.domain
+    }
+
+    /// The canonical form of this permutation: the same permutation with the
+    /// fixed points dropped from its domain.
+    pub fn canonical(&self) -> Permutation {
+        let images: HashMap<u64, u64> = self.moved_images().collect();
+        Permutation::new_unchecked(images)
+    }
+
+    /// The points that are not mapped onto themselves, with their images, in
+    /// increasing order of the points.
+    fn moved_images<'a>(&'a self) -> impl Iterator<Item = (u64, u64)> + 'a {
+        self.domain
+            .iter()
+            .map(move |point| (*point, self.images[point]))
+            .filter(|(point, image)| point != image)
+    }
+
+    /// The points that are not mapped onto themselves.
+    pub fn moved_points(&self) -> HashSet<u64> {
+        self.images
+            .iter()
+            .filter(|(original, image)| original != image)
+            .map(|(original, _)| *original)
+            .collect()
+    }
+
+    /// The number of points that are not mapped onto themselves.
+    pub fn support_size(&self) -> usize {
+        self.images
+            .iter()
+            .filter(|(original, image)| original != image)
+            .count()
+    }
+
+    /// The non trivial cycles of this permutation, each starting with its
+    /// smallest point, in order of that point.
+    pub fn cycles(&self) -> Vec<Vec<u64>> {
+        cycles(&self.domain, &self.images)
+    }
+
+    /// The order of this permutation, i.e. the least common multiple of the
+    /// lengths of its cycles.
+    pub fn order(&self) -> u64 {
+        self.cycles()
+            .iter()
+            .fold(1, |order, cycle| lcm(order, cycle.len() as u64))
+    }
+
+    /// The number of points this permutation is defined on.
+    pub fn degree(&self) -> usize {
+        self.domain.len()
+    }
+
+    /// The `n` by `n` permutation matrix of the action on `0..n`. The entry in
+    /// the row of a point and the column of its image is 1, all other entries
+    /// are 0. Points outside the domain are fixed, so equal permutations have
+    /// equal matrices.
+    ///
+    /// Permutations act on row vectors, so the matrix of a product is the
+    /// product of the matrices in the same order.
+    ///
+    /// Panics when the permutation moves a point outside `0..n`.
+    pub fn to_matrix(&self, n: usize) -> Vec<Vec<u8>> {
+        self.assert_acts_below(n as u64);
+        (0..n as u64)
+            .map(|point| {
+                let mut row = vec![0; n];
+                row[*self.images.get(&point).unwrap_or(&point) as usize] = 1;
+                row
+            })
+            .collect()
+    }
+
+    /// The trace of the `n` by `n` permutation matrix, i.e. the number of
+    /// fixed points in `0..n`. This is the value of the permutation character.
+    ///
+    /// Panics when the permutation moves a point outside `0..n`.
+    pub fn trace(&self, n: usize) -> usize {
+        self.assert_acts_below(n as u64);
+        n - self.support_size()
+    }
+
+    fn assert_acts_below(&self, n: u64) {
+        assert!(
+            self.moved_images().all(|(point, _)| point < n),
+            "moved points should be below the dimension {}",
+            n
+        );
+    }
+
+    /// The `n` by `n` permutation matrix as an [nalgebra](https://nalgebra.org)
+    /// matrix, see `to_matrix`.
+    #[cfg(feature = "nalgebra")]
+    pub fn to_dmatrix(&self, n: usize) -> DMatrix<f64> {
+        let matrix = self.to_matrix(n);
+        DMatrix::from_fn(n, n, |row, column| f64::from(matrix[row][column]))
+    }
+}
+
+impl PartialEq for Permutation {
+    fn eq(&self, other: &Permutation) -> bool {
+        self.moved_images().eq(other.moved_images())
+    }
+}
+
+impl Eq for Permutation {}
+
+impl Hash for Permutation {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        for (point, image) in self.moved_images() {
+            point.hash(state);
+            image.hash(state);
         }
     }
 }
 
-impl GroupElement for Permutation {
-    fn is_identity(&self) -> bool {
-        for i in 0..self.n {
-            let original = i as u64;
-            let image = self.images.get(&original).unwrap_or(&original).clone();
-            if image != original {
-                return false;
+/// The ways images can fail to describe a permutation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PermutationError {
+    /// More than one point maps onto this point.
+    DuplicateImage(u64),
+    /// This image is not a point of the domain.
+    ImageOutsideDomain(u64),
+}
+
+impl Display for PermutationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PermutationError::DuplicateImage(point) => {
+                write!(f, "{} is the image of more than one point", point)
             }
+            PermutationError::ImageOutsideDomain(point) => {
+                write!(f, "image {} is not in the domain", point)
+            }
+        }
+    }
+}
+
+impl error::Error for PermutationError {}
+
+/// Arbitrary permutations of at most 64 points, for fuzzing.
+#[cfg(any(test, feature = "arbitrary"))]
+impl<'a> Arbitrary<'a> for Permutation {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Permutation> {
+        let degree = u.int_in_range(0..=64u64)?;
+        let mut images: Vec<u64> = (0..degree).collect();
+        for index in (1..images.len()).rev() {
+            let other = u.int_in_range(0..=index)?;
+            images.swap(index, other);
         }
-        true
+        let images: HashMap<u64, u64> = images
+            .into_iter()
+            .enumerate()
+            .map(|(point, image)| (point as u64, image))
+            .collect();
+        Ok(Permutation::new_unchecked(images))
+    }
+}
+
+impl GroupElement for Permutation {
+    fn identity() -> Permutation {
+        Permutation {
+            domain: vec![],
+            images: HashMap::new(),
+        }
+    }
+
+    fn is_identity(&self) -> bool {
+        self.images
+            .iter()
+            .all(|(original, image)| original == image)
     }
 
     fn times(&self, multiplicant: &Permutation) -> Permutation {
-        let max_n = if self.n > multiplicant.n {
-            self.n
-        } else {
-            multiplicant.n
-        };
-        let mut images = HashMap::new();
-        for i in 0..max_n {
-            let original = i as u64;
-            let mut image = self.images.get(&original).unwrap_or(&original).clone();
-            image = multiplicant.images.get(&image).unwrap_or(&image).clone();
-            images.insert(original, image);
+        let mut domain: Vec<u64> = Vec::with_capacity(self.domain.len());
+        let mut images = HashMap::with_capacity(self.domain.len());
+        let (mut left, mut right) = (
+            self.domain.iter().peekable(),
+            multiplicant.domain.iter().peekable(),
+        );
+        loop {
+            let original = match (left.peek(), right.peek()) {
+                (Some(&&a), Some(&&b)) if a == b => {
+                    left.next();
+                    right.next();
+                    a
+                }
+                (Some(&&a), Some(&&b)) if a < b => {
+                    left.next();
+                    a
+                }
+                (Some(&&a), None) => {
+                    left.next();
+                    a
+                }
+                (_, Some(&&b)) => {
+                    right.next();
+                    b
+                }
+                (None, None) => break,
+            };
+            domain.push(original);
+            images.insert(original, multiplicant.act_on(&self.act_on(&original)));
         }
-        Permutation::new(images)
+        Permutation { domain, images }
     }
 
     fn inverse(&self) -> Permutation {
-        let mut images = HashMap::new();
-        for i in 0..self.n {
-            let original = i as u64;
-            let image = self.images.get(&original).unwrap_or(&original).clone();
-            images.insert(image, original);
+        let images = self
+            .images
+            .iter()
+            .map(|(original, image)| (*image, *original))
+            .collect();
+        Permutation {
+            domain: self.domain.clone(),
+            images,
         }
-        Permutation::new(images)
     }
 }
 
@@ -144,14 +506,14 @@ impl GroupAction for Permutation {
     type Domain = u64;
 
     fn act_on(&self, original: &u64) -> u64 {
-        self.images.get(&original).unwrap_or(&original).clone()
+        *self.images.get(original).unwrap_or(original)
     }
 }
 
 impl Display for Permutation {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let cycles: Vec<Vec<u64>> = cycles(self.n, &self.images);
-        if cycles.len() > 0 {
+        let cycles: Vec<Vec<u64>> = cycles(&self.domain, &self.images);
+        if !cycles.is_empty() {
             for cycle in cycles {
                 let representations: Vec<String> = cycle
                     .into_iter()
@@ -169,19 +531,18 @@ impl Display for Permutation {
     }
 }
 
-fn cycles(n: usize, images: &HashMap<u64, u64>) -> Vec<Vec<u64>> {
+fn cycles(domain: &[u64], images: &HashMap<u64, u64>) -> Vec<Vec<u64>> {
     let mut cycles = vec![];
     let mut visited = HashSet::new();
-    for i in 0..n {
-        let original = i as u64;
+    for &original in domain {
         if !visited.contains(&original) {
-            visited.insert(original.clone());
-            let mut cycle = vec![original.clone()];
-            let mut image = images.get(&original).unwrap_or(&original).clone();
+            visited.insert(original);
+            let mut cycle = vec![original];
+            let mut image = *images.get(&original).unwrap_or(&original);
             while !visited.contains(&image) {
-                visited.insert(image.clone());
-                cycle.push(image.clone());
-                image = images.get(&image).unwrap_or(&image).clone();
+                visited.insert(image);
+                cycle.push(image);
+                image = *images.get(&image).unwrap_or(&image);
             }
             if cycle.len() > 1 {
                 cycles.push(cycle);
@@ -197,6 +558,158 @@ mod tests {
     use super::*;
     use std::collections::HashMap;
 
+    #[test]
+    fn one_line_notation_should_round_trip() {
+        let permutation = Permutation::from_one_line_one_based(&[2, 3, 1, 5, 4]).unwrap();
+
+        assert_eq!(permutation, permute!(0, 1, 1, 2, 2, 0, 3, 4, 4, 3));
+        assert_eq!(permutation.to_one_line(), vec![1, 2, 0, 4, 3]);
+        assert_eq!(permutation.to_one_line_one_based(), vec![2, 3, 1, 5, 4]);
+        assert_eq!(
+            Permutation::from_one_line(&permutation.to_one_line()),
+            Ok(permutation)
+        );
+    }
+
+    #[test]
+    fn permutations_should_be_listed_in_lexicographic_order() {
+        let lines: Vec<Vec<u64>> = Permutation::all(3).map(|p| p.to_one_line()).collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                vec![0, 1, 2],
+                vec![0, 2, 1],
+                vec![1, 0, 2],
+                vec![1, 2, 0],
+                vec![2, 0, 1],
+                vec![2, 1, 0],
+            ]
+        );
+        assert_eq!(Permutation::all(0).count(), 1);
+    }
+
+    #[test]
+    fn rank_should_be_the_lexicographic_position() {
+        for (position, permutation) in Permutation::all(4).enumerate() {
+            assert_eq!(permutation.rank(4), position as u128);
+            assert_eq!(Permutation::unrank(4, position as u128), Some(permutation));
+        }
+        assert_eq!(Permutation::unrank(4, 24), None);
+    }
+
+    #[test]
+    fn rank_should_round_trip_for_any_domain() {
+        let sparse = permute!(5, 7, 7, 5);
+        let short = permute!(0, 1, 1, 0);
+        let long = permute!(0, 1, 1, 0, 2, 2);
+
+        assert_eq!(Permutation::unrank(8, sparse.rank(8)), Some(sparse));
+        assert_eq!(short.rank(3), long.rank(3));
+        assert_eq!(Permutation::unrank(3, long.rank(3)), Some(long.clone()));
+        assert_eq!(
+            short.next_permutation(3),
+            Permutation::unrank(3, long.rank(3) + 1)
+        );
+    }
+
+    #[test]
+    fn one_line_notation_should_fix_the_points_below_the_domain() {
+        let permutation = permute!(5, 7, 7, 5);
+
+        assert_eq!(permutation.to_one_line(), vec![0, 1, 2, 3, 4, 7, 6, 5]);
+        assert_eq!(
+            Permutation::from_one_line(&permutation.to_one_line()),
+            Ok(permutation)
+        );
+        assert_eq!(Permutation::identity().to_one_line(), Vec::<u64>::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "the rank should fit in a u128")]
+    fn rank_should_panic_beyond_u128() {
+        let line: Vec<u64> = (0..35).map(|point| (point + 34) % 35).collect();
+
+        Permutation::from_one_line(&line).unwrap().rank(35);
+    }
+
+    #[test]
+    fn one_line_notation_should_reject_non_permutations() {
+        assert_eq!(
+            Permutation::from_one_line(&[0, 0]),
+            Err(PermutationError::DuplicateImage(0))
+        );
+        assert_eq!(
+            Permutation::from_one_line_one_based(&[1, 3]),
+            Err(PermutationError::ImageOutsideDomain(3))
+        );
+        assert_eq!(
+            Permutation::from_one_line_one_based(&[0, 1]),
+            Err(PermutationError::ImageOutsideDomain(0))
+        );
+    }
+
+    #[test]
+    fn matrix_of_a_product_should_be_the_product_of_matrices() {
+        let a = permute!(0, 1, 1, 2, 2, 0);
+        let b = permute!(0, 1, 1, 0, 2, 2);
+        let product = |left: &[Vec<u8>], right: &[Vec<u8>]| -> Vec<Vec<u8>> {
+            (0..3)
+                .map(|r| {
+                    (0..3)
+                        .map(|c| (0..3).map(|k| left[r][k] * right[k][c]).sum())
+                        .collect()
+                })
+                .collect()
+        };
+
+        assert_eq!(
+            a.to_matrix(3),
+            vec![vec![0, 1, 0], vec![0, 0, 1], vec![1, 0, 0]]
+        );
+        assert_eq!(
+            a.times(&b).to_matrix(3),
+            product(&a.to_matrix(3), &b.to_matrix(3))
+        );
+        assert_eq!(a.trace(3), 0);
+        assert_eq!(b.trace(3), 1);
+    }
+
+    #[test]
+    fn matrix_should_not_depend_on_the_domain() {
+        let short = permute!(0, 1, 1, 0);
+        let long = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+
+        assert_eq!(short, long);
+        assert_eq!(short.to_matrix(4), long.to_matrix(4));
+        assert_eq!(short.trace(4), 2);
+        assert_eq!(long.trace(4), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "should be below the dimension")]
+    fn matrix_should_panic_when_points_are_moved_outside() {
+        permute!(0, 1, 1, 2, 2, 0).to_matrix(2);
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn dmatrix_should_match_the_matrix() {
+        let a = permute!(0, 1, 1, 2, 2, 0);
+
+        let matrix = a.to_dmatrix(3);
+
+        assert_eq!(matrix[(0, 1)], 1.0);
+        assert_eq!(matrix.trace(), 0.0);
+        assert_eq!(&matrix * &matrix * &matrix, DMatrix::identity(3, 3));
+    }
+
+    #[test]
+    fn order_should_be_the_lcm_of_the_cycle_lengths() {
+        assert_eq!(permute!(0, 1, 1, 0, 2, 3, 3, 4, 4, 2).order(), 6);
+        assert_eq!(Permutation::identity().order(), 1);
+    }
+
     #[test]
     fn permutaion_should_know_when_it_is_the_identity() {
         let mut not_identity_images = HashMap::new();
@@ -285,4 +798,88 @@ mod tests {
         assert_eq!("Id", format!("{}", identity));
         assert_eq!("(0 1 2)(3 4)", format!("{}", permutation));
     }
+
+    #[test]
+    fn sparse_permutations_should_keep_points_beyond_their_size() {
+        let swap = permute!(5u64, 6u64, 6u64, 5u64);
+        let cycle = permute!(0u64, 1u64, 1u64, 0u64);
+
+        assert!(!swap.is_identity());
+        assert_eq!(swap.inverse().act_on(&5u64), 6u64);
+        let product = swap.times(&cycle);
+        assert_eq!(product.act_on(&6u64), 5u64);
+        assert_eq!(product.act_on(&1u64), 0u64);
+        assert_eq!(format!("{}", product), "(0 1)(5 6)");
+    }
+
+    #[test]
+    fn moved_points_should_ignore_fixed_points() {
+        let permutation = permute!(0u64, 0u64, 1u64, 2u64, 2u64, 1u64, 3u64, 3u64);
+
+        let expected: HashSet<u64> = vec![1u64, 2u64].into_iter().collect();
+        assert_eq!(permutation.moved_points(), expected);
+        assert_eq!(permutation.support_size(), 2);
+        assert_eq!(permutation.degree(), 4);
+    }
+
+    #[test]
+    fn partially_specified_permutations_should_combine_domains() {
+        let low = permute!(0u64, 1u64, 1u64, 0u64);
+        let high = permute!(7u64, 9u64, 9u64, 7u64);
+
+        let product = low.times(&high);
+
+        assert_eq!(product.domain(), &[0u64, 1u64, 7u64, 9u64]);
+        assert_eq!(product.inverse().domain(), product.domain());
+        assert!(product.times(&product).is_identity());
+        assert_eq!(high.act_on(&8u64), 8u64);
+        assert_eq!(format!("{}", high), "(7 9)");
+    }
+
+    #[test]
+    fn try_new_should_reject_non_bijections() {
+        let duplicate =
+            Permutation::try_new(vec![(0u64, 1u64), (1u64, 1u64)].into_iter().collect());
+        let outside = Permutation::try_new(vec![(5u64, 6u64)].into_iter().collect());
+
+        assert_eq!(duplicate, Err(PermutationError::DuplicateImage(1u64)));
+        assert_eq!(outside, Err(PermutationError::ImageOutsideDomain(6u64)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_should_panic_on_non_bijections() {
+        permute!(0u64, 1u64, 1u64, 1u64);
+    }
+
+    #[test]
+    fn fixed_points_should_not_matter_for_equality() {
+        let small = permute!(0u64, 1u64, 1u64, 0u64);
+        let large = permute!(0u64, 1u64, 1u64, 0u64, 2u64, 2u64, 3u64, 3u64);
+
+        assert_eq!(small, large);
+        assert_eq!(large.canonical().domain(), &[0u64, 1u64]);
+        let mut seen = HashSet::new();
+        seen.insert(small);
+        assert!(seen.contains(&large));
+        assert!(!seen.contains(&permute!(0u64, 0u64, 1u64, 1u64)));
+    }
+
+    #[test]
+    fn arbitrary_permutations_should_be_bijections() {
+        let data: Vec<u8> = (0..=255).collect();
+        let mut u = Unstructured::new(&data);
+
+        while !u.is_empty() {
+            let permutation = Permutation::arbitrary(&mut u).unwrap();
+
+            let images: HashSet<u64> = permutation
+                .domain()
+                .iter()
+                .map(|point| permutation.act_on(point))
+                .collect();
+            assert_eq!(images.len(), permutation.degree());
+            assert!(permutation.times(&permutation.inverse()).is_identity());
+        }
+    }
 }
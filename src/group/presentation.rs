@@ -0,0 +1,227 @@
+//! A presentation of a group by generators and relators.
+//!
+//! A presentation describes a group abstractly: the words in the generators
+//! modulo the relators, words that are declared to be the identity. The
+//! stabilizer chain of a group provides one. Let _u_p_ be the word of the
+//! Schreier tree for the transversal that maps the base point of a level to
+//! _p_. For every point _p_ of the orbit and every strong generator _s_ of the
+//! level, the element _u_p_ _s_ _u_q_^-1, with _q_ the image of _p_ under _s_,
+//! fixes the base point. Sifting it through the deeper levels writes it as a
+//! word _w_ in the deeper strong generators, which gives the relator
+//! _u_p_ _s_ _u_q_^-1 _w_^-1. Together these relators define the group, as
+//! Sims showed.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::{Group, GroupElement};
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2);
+//! let rotation = permute!(0, 1, 1, 2, 2, 0);
+//! let s3 = Group::with_base(vec![0, 1, 2], vec![], vec![transposition, rotation]);
+//!
+//! let presentation = s3.presentation();
+//! let images: HashMap<usize, Permutation> =
+//!     presentation.generators().iter().cloned().enumerate().collect();
+//!
+//! for relator in presentation.relators() {
+//!     assert!(relator.evaluate(&images).is_identity());
+//! }
+//! # }
+//! ```
+
+use super::free::Word;
+use super::rewriting::RewritingSystem;
+use super::{BaseStrongGeneratorLevel, Group, GroupAction, GroupElement};
+use std::hash::Hash;
+
+/// Generators and relators that define a group.
+///
+/// The relators are words over the indices of the generators.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Presentation<G> {
+    generators: Vec<G>,
+    relators: Vec<Word<usize>>,
+}
+
+impl<G> Presentation<G> {
+    /// The generators, symbol `i` of a relator stands for the generator at
+    /// index `i`.
+    pub fn generators(&self) -> &[G] {
+        &self.generators
+    }
+
+    /// The relators, words that equal the identity.
+    pub fn relators(&self) -> &[Word<usize>] {
+        &self.relators
+    }
+
+    /// A rewriting system for this presentation. Complete it to find normal
+    /// forms of the words in the generators.
+    pub fn rewriting_system(&self) -> RewritingSystem<usize> {
+        RewritingSystem::new((0..self.generators.len()).collect(), self.relators.clone())
+    }
+}
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// A presentation of this group on its strong generators.
+    pub fn presentation(&self) -> Presentation<G> {
+        let mut generators: Vec<G> = vec![];
+        let symbols: Vec<Vec<usize>> = self
+            .levels
+            .iter()
+            .map(|level| {
+                level
+                    .orbit
+                    .generators
+                    .iter()
+                    .map(|g| match generators.iter().position(|known| known == g) {
+                        Some(index) => index,
+                        None => {
+                            generators.push(g.clone());
+                            generators.len() - 1
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut relators = vec![];
+        for (index, level) in self.levels.iter().enumerate() {
+            for point in level.orbit.iter() {
+                let u = transversal_word(level, &symbols[index], point);
+                let t = level.orbit.representative(point).expect("point in orbit");
+                for (s, generator) in symbols[index].iter().zip(&level.orbit.generators) {
+                    let image = generator.act_on(point);
+                    let v = transversal_word(level, &symbols[index], &image);
+                    let residue = t.times(generator).times(
+                        &level
+                            .orbit
+                            .representative(&image)
+                            .expect("image in orbit")
+                            .inverse(),
+                    );
+                    let w = self.sift_word(&symbols, index + 1, residue);
+                    let relator = u
+                        .times(&Word::generator(*s))
+                        .times(&v.inverse())
+                        .times(&w.inverse());
+                    if !relator.is_identity() && !relators.contains(&relator) {
+                        relators.push(relator);
+                    }
+                }
+            }
+        }
+        Presentation {
+            generators,
+            relators,
+        }
+    }
+
+    /// The word in the strong generators of the levels from `start` on that
+    /// equals `element`, an element of the stabilizer of the earlier base
+    /// points.
+    fn sift_word(&self, symbols: &[Vec<usize>], start: usize, element: G) -> Word<usize> {
+        let mut residue = element;
+        let mut word = Word::identity();
+        for (index, level) in self.levels.iter().enumerate().skip(start) {
+            let point = residue.act_on(&level.orbit.base);
+            let t = level
+                .orbit
+                .representative(&point)
+                .expect("element in group");
+            word = transversal_word(level, &symbols[index], &point).times(&word);
+            residue = residue.times(&t.inverse());
+        }
+        word
+    }
+}
+
+/// The word along the Schreier tree of `level` from its base point to `point`.
+fn transversal_word<Domain, G>(
+    level: &BaseStrongGeneratorLevel<Domain, G>,
+    symbols: &[usize],
+    point: &Domain,
+) -> Word<usize>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
+{
+    let mut terms = vec![];
+    let mut current = point.clone();
+    let mut index = *level.orbit.vector.get(&current).expect("point in orbit");
+    while index != -1 {
+        let generator = &level.orbit.generators[index as usize];
+        terms.push((symbols[index as usize], 1));
+        current = generator.inverse().act_on(&current);
+        index = *level.orbit.vector.get(&current).expect("point in orbit");
+    }
+    terms.reverse();
+    Word::new(terms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::permutation::Permutation;
+    use super::*;
+    use std::collections::{HashMap, HashSet};
+
+    fn s3() -> Group<u64, Permutation> {
+        Group::with_base(
+            vec![0, 1, 2],
+            vec![],
+            vec![permute!(0, 1, 1, 0, 2, 2), permute!(0, 1, 1, 2, 2, 0)],
+        )
+    }
+
+    #[test]
+    fn relators_should_hold_in_the_group() {
+        let group = Group::with_base(
+            vec![0, 1, 2, 3],
+            vec![],
+            vec![
+                permute!(0, 1, 1, 0, 2, 2, 3, 3),
+                permute!(0, 1, 1, 2, 2, 3, 3, 0),
+            ],
+        );
+
+        let presentation = group.presentation();
+        let images: HashMap<usize, Permutation> = presentation
+            .generators()
+            .iter()
+            .cloned()
+            .enumerate()
+            .collect();
+
+        assert!(!presentation.relators().is_empty());
+        for relator in presentation.relators() {
+            assert!(relator.evaluate(&images).is_identity());
+        }
+    }
+
+    #[test]
+    fn presentation_should_define_the_group() {
+        let presentation = s3().presentation();
+        let mut system = presentation.rewriting_system();
+        assert!(system.complete(100));
+
+        let n = presentation.generators().len();
+        let mut words = vec![Word::identity()];
+        for _ in 0..4 {
+            let longer: Vec<Word<usize>> = words
+                .iter()
+                .flat_map(|w: &Word<usize>| (0..n).map(move |s| w.times(&Word::generator(s))))
+                .collect();
+            words.extend(longer);
+        }
+        let normal_forms: HashSet<Word<usize>> = words.iter().map(|w| system.reduce(w)).collect();
+
+        assert_eq!(normal_forms.len(), 6);
+    }
+}
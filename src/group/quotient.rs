@@ -0,0 +1,152 @@
+//! The [quotient](https://en.wikipedia.org/wiki/Quotient_group) of a group by
+//! a normal subgroup.
+//!
+//! A group _G_ acts on the right cosets _Nx_ of a subgroup _N_ by right
+//! multiplication. When _N_ is normal, the kernel of this action is exactly
+//! _N_, so the permutations of the cosets form a group isomorphic to _G_/_N_.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let s4 = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation]);
+//! let klein = Group::with_base(
+//!     vec![0, 1, 2, 3],
+//!     vec![],
+//!     vec![permute!(0, 1, 1, 0, 2, 3, 3, 2), permute!(0, 2, 1, 3, 2, 0, 3, 1)],
+//! );
+//!
+//! let quotient = s4.quotient(&klein);
+//!
+//! assert_eq!(quotient.group().order(), 6);
+//! # }
+//! ```
+
+use super::coset::Cosets;
+use super::permutation::Permutation;
+use super::{Group, GroupAction, GroupElement};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A quotient group together with the projection onto it.
+pub struct Quotient<'a, Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
+{
+    group: Group<u64, Permutation>,
+    cosets: Cosets<'a, Domain, G>,
+}
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// The quotient of this group by `normal`, which should be a normal
+    /// subgroup of this group. The quotient acts on the cosets of `normal`.
+    pub fn quotient<'a>(&self, normal: &'a Group<Domain, G>) -> Quotient<'a, Domain, G> {
+        let cosets = self.right_cosets(normal);
+        let gset: Vec<u64> = (0..cosets.len() as u64).collect();
+        let generators: Vec<Permutation> = self
+            .generators()
+            .iter()
+            .map(|g| project(&cosets, g))
+            .filter(|image| !image.is_identity())
+            .collect();
+        let group = Group::with_base(gset, vec![], generators);
+        Quotient { group, cosets }
+    }
+}
+
+impl<'a, Domain, G> Quotient<'a, Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// The quotient as a permutation group on the indices of the cosets.
+    pub fn group(&self) -> &Group<u64, Permutation> {
+        &self.group
+    }
+
+    /// The cosets the quotient acts on.
+    pub fn cosets(&self) -> &Cosets<'a, Domain, G> {
+        &self.cosets
+    }
+
+    /// The image of `g` under the projection onto the quotient.
+    pub fn project(&self, g: &G) -> Permutation {
+        project(&self.cosets, g)
+    }
+}
+
+/// The permutation of the right cosets by right multiplication with `g`.
+fn project<Domain, G>(cosets: &Cosets<Domain, G>, g: &G) -> Permutation
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    let images: HashMap<u64, u64> = cosets
+        .representatives()
+        .iter()
+        .enumerate()
+        .map(|(index, representative)| {
+            let image = cosets
+                .coset_of(&representative.times(g))
+                .expect("product should be a member");
+            (index as u64, image as u64)
+        })
+        .collect();
+    Permutation::new(images)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::testing::s4;
+    use super::*;
+
+    #[test]
+    fn projection_should_be_a_homomorphism() {
+        let group = s4();
+        let alternating = group.derived_subgroup();
+        let quotient = group.quotient(&alternating);
+        let elements = group.elements();
+
+        assert_eq!(quotient.group().order(), 2);
+        for g in &elements {
+            for h in &elements {
+                assert_eq!(
+                    quotient.project(&g.times(h)),
+                    quotient.project(g).times(&quotient.project(h))
+                );
+            }
+            assert_eq!(quotient.project(g).is_identity(), alternating.contains(g));
+        }
+    }
+
+    #[test]
+    fn quotient_by_the_whole_group_should_be_trivial() {
+        let group = s4();
+
+        let quotient = group.quotient(&group);
+
+        assert_eq!(quotient.group().order(), 1);
+        assert_eq!(quotient.cosets().len(), 1);
+    }
+
+    #[test]
+    fn quotient_of_the_trivial_group_should_be_trivial() {
+        let trivial: Group<u64, Permutation> = Group::with_base(vec![0, 1, 2, 3], vec![], vec![]);
+
+        let quotient = trivial.quotient(&trivial);
+
+        assert_eq!(quotient.group().order(), 1);
+        assert_eq!(quotient.cosets().len(), 1);
+        assert_eq!(trivial.left_cosets(&trivial).len(), 1);
+    }
+}
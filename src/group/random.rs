@@ -0,0 +1,398 @@
+//! Random elements of a group from its generators alone.
+//!
+//! The [product replacement algorithm](https://doi.org/10.1080/00927879508825496)
+//! keeps a few slots of group elements, initially copies of the generators.
+//! Every step replaces a random slot by its product with another slot or its
+//! inverse, and multiplies an accumulator with the result, a variant known as
+//! _rattle_. After a number of warm up steps the accumulator behaves like a
+//! random element of the group.
+//!
+//! Unlike products of random transversals, this does not need a stabilizer
+//! chain, so it can feed a randomized construction of one, or sample groups
+//! that are too big to build. The elements are not exactly uniformly
+//! distributed; more slots and a longer warm up get them closer.
+//!
+//! Every randomized algorithm of this crate draws its random numbers from a
+//! `RandomSource`. The default source is `XorShift`, a small generator that
+//! produces the same numbers from the same seed on every machine, so results
+//! can be reproduced. A stream can be given another source with `with_source`,
+//! and `Group::random_element` takes one explicitly.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # use permutation_rs::group::random::RandomElementStream;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let generators = vec![transposition, rotation];
+//! let group = Group::with_base(vec![0, 1, 2, 3], vec![], generators.clone());
+//!
+//! let stream = RandomElementStream::new(generators).with_seed(37);
+//!
+//! for element in stream.take(10) {
+//!     assert!(group.contains(&element));
+//! }
+//! # }
+//! ```
+
+use super::{
+    find_base, initial_levels, sift, BaseStrongGeneratorLevel, Group, GroupAction, GroupElement,
+    Monitor, Strategy,
+};
+use std::hash::Hash;
+use std::slice;
+
+/// The number of slots when none is specified.
+pub const DEFAULT_SLOTS: usize = 10;
+/// The number of warm up steps when none is specified.
+pub const DEFAULT_WARM_UP: usize = 50;
+
+/// A source of pseudo-random numbers for the randomized algorithms.
+pub trait RandomSource {
+    /// The next pseudo-random number.
+    fn next_u64(&mut self) -> u64;
+
+    /// A pseudo-random number in `0..bound`. Panics when `bound` is zero.
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+}
+
+impl<R> RandomSource for &mut R
+where
+    R: RandomSource + ?Sized,
+{
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+}
+
+/// A xorshift generator, the default `RandomSource`. The same seed gives the
+/// same numbers on every machine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// A generator that starts from `seed`.
+    pub fn new(seed: u64) -> XorShift {
+        XorShift {
+            state: seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1,
+        }
+    }
+}
+
+impl RandomSource for XorShift {
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+}
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// A uniformly distributed random element drawn with `random`: a product
+    /// of a random transversal of every level.
+    pub fn random_element<R>(&self, random: &mut R) -> G
+    where
+        R: RandomSource + ?Sized,
+    {
+        let mut element = G::identity();
+        for level in self.levels.iter().rev() {
+            let points: Vec<&Domain> = level.orbit.iter().collect();
+            let point = points[random.below(points.len() as u64) as usize];
+            let transversal = level
+                .orbit
+                .representative(point)
+                .expect("point should be in orbit");
+            element = element.times(&transversal);
+        }
+        element
+    }
+}
+
+/// An endless stream of pseudo-random elements of the group generated by some
+/// generators, with random choices drawn from a `RandomSource`.
+pub struct RandomElementStream<G, R = XorShift>
+where
+    G: GroupElement + Clone,
+{
+    generators: Vec<G>,
+    slots: Vec<G>,
+    accumulator: Option<G>,
+    slot_count: usize,
+    warm_up: usize,
+    random: R,
+}
+
+impl<G> RandomElementStream<G>
+where
+    G: GroupElement + Clone,
+{
+    /// A stream of elements of the group generated by `generators`, starting
+    /// from seed 0. Without generators every element is the identity.
+    pub fn new(generators: Vec<G>) -> RandomElementStream<G> {
+        RandomElementStream {
+            generators,
+            slots: vec![],
+            accumulator: None,
+            slot_count: DEFAULT_SLOTS,
+            warm_up: DEFAULT_WARM_UP,
+            random: XorShift::new(0),
+        }
+    }
+
+    /// Start over from `seed`, so that the stream can be reproduced.
+    pub fn with_seed(self, seed: u64) -> RandomElementStream<G> {
+        self.with_source(XorShift::new(seed))
+    }
+}
+
+impl<G, R> RandomElementStream<G, R>
+where
+    G: GroupElement + Clone,
+    R: RandomSource,
+{
+    /// Start over, drawing the random choices from `source`.
+    pub fn with_source<S>(self, source: S) -> RandomElementStream<G, S>
+    where
+        S: RandomSource,
+    {
+        RandomElementStream {
+            generators: self.generators,
+            slots: vec![],
+            accumulator: None,
+            slot_count: self.slot_count,
+            warm_up: self.warm_up,
+            random: source,
+        }
+    }
+
+    /// Use `slots` slots, or as many as there are generators if that is more.
+    pub fn with_slots(mut self, slots: usize) -> RandomElementStream<G, R> {
+        self.slot_count = slots;
+        self.reset();
+        self
+    }
+
+    /// Take `steps` steps before yielding the first element.
+    pub fn with_warm_up(mut self, steps: usize) -> RandomElementStream<G, R> {
+        self.warm_up = steps;
+        self.reset();
+        self
+    }
+
+    /// Start over with the slots, the random choices continue.
+    fn reset(&mut self) {
+        self.slots.clear();
+        self.accumulator = None;
+    }
+
+    fn start(&mut self) {
+        let count = self.slot_count.max(self.generators.len()).max(2);
+        self.slots = self
+            .generators
+            .iter()
+            .cycle()
+            .take(count)
+            .cloned()
+            .collect();
+        self.accumulator = Some(G::identity());
+        for _ in 0..self.warm_up {
+            self.step();
+        }
+    }
+
+    /// Replace a random slot and multiply it into the accumulator. Without
+    /// generators the accumulator stays the identity.
+    fn step(&mut self) {
+        if self.slots.is_empty() {
+            return;
+        }
+        let count = self.slots.len() as u64;
+        let i = self.random.below(count) as usize;
+        let j = (i + 1 + self.random.below(count - 1) as usize) % self.slots.len();
+        let other = if self.random.below(2) == 0 {
+            self.slots[j].clone()
+        } else {
+            self.slots[j].inverse()
+        };
+        self.slots[i] = if self.random.below(2) == 0 {
+            self.slots[i].times(&other)
+        } else {
+            other.times(&self.slots[i])
+        };
+        let accumulator = self.accumulator.take().expect("stream has started");
+        self.accumulator = Some(accumulator.times(&self.slots[i]));
+    }
+}
+
+impl<G, R> Iterator for RandomElementStream<G, R>
+where
+    G: GroupElement + Clone,
+    R: RandomSource,
+{
+    type Item = G;
+
+    fn next(&mut self) -> Option<G> {
+        if self.accumulator.is_none() {
+            self.start();
+        }
+        self.step();
+        self.accumulator.clone()
+    }
+}
+
+/// The randomized Schreier-Sims algorithm, or `None` when `monitor`
+/// interrupts it.
+///
+/// Instead of sifting every Schreier generator, random elements of the group
+/// are sifted. A residue that is not the identity is added as a strong
+/// generator. The construction stops once `rounds` consecutive elements sift,
+/// or once `monitor` knows the levels are complete. Without a known order the
+/// result can be a proper subgroup, with a probability that shrinks
+/// exponentially with `rounds`.
+pub(super) fn random_schreier_sims<Domain, G, S, M>(
+    strategy: &S,
+    monitor: &mut M,
+    gset: &[Domain],
+    base: &[Domain],
+    generators: Vec<G>,
+    seed: u64,
+    rounds: usize,
+) -> Option<Vec<BaseStrongGeneratorLevel<Domain, G>>>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+    S: Strategy<Domain, G> + ?Sized,
+    M: Monitor,
+{
+    let mut levels = initial_levels(strategy, monitor, gset, base, generators.clone())?;
+    if levels.is_empty() {
+        return Some(levels);
+    }
+    let mut stream = RandomElementStream::new(generators).with_seed(seed);
+    let mut sifted = 0;
+    while sifted < rounds && !monitor.is_complete() {
+        if !monitor.proceed() {
+            return None;
+        }
+        let element = stream.next().expect("stream should be endless");
+        let (residue, depth) = sift(&levels, element);
+        if residue.is_identity() {
+            sifted += 1;
+            continue;
+        }
+        sifted = 0;
+        monitor.sift_failed(0, depth);
+        if depth == levels.len() {
+            let point =
+                find_base(gset, slice::from_ref(&residue)).expect("residue should move something");
+            levels.push(BaseStrongGeneratorLevel::with_generators(point, vec![]));
+        }
+        for (offset, level) in levels[1..=depth].iter_mut().enumerate() {
+            let orbit = &mut level.orbit;
+            orbit.generators.push(residue.clone());
+            orbit.vector = strategy.schreier_vector(&orbit.base, &orbit.generators);
+            monitor.stabilizer_count(1 + offset, orbit.generators.len());
+            monitor.orbit_size(1 + offset, orbit.len());
+        }
+    }
+    Some(levels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::permutation::Permutation;
+    use super::*;
+    use std::collections::{HashMap, HashSet};
+
+    fn generators() -> Vec<Permutation> {
+        vec![
+            permute!(0, 1, 1, 0, 2, 2, 3, 3),
+            permute!(0, 1, 1, 2, 2, 3, 3, 0),
+        ]
+    }
+
+    #[test]
+    fn stream_should_reach_every_element() {
+        let elements: HashSet<Permutation> =
+            RandomElementStream::new(generators()).take(500).collect();
+
+        assert_eq!(elements.len(), 24);
+    }
+
+    #[test]
+    fn same_seed_should_give_same_stream() {
+        let first: Vec<Permutation> = RandomElementStream::new(generators())
+            .with_seed(5)
+            .take(10)
+            .collect();
+        let second: Vec<Permutation> = RandomElementStream::new(generators())
+            .with_slots(3)
+            .with_seed(5)
+            .with_slots(DEFAULT_SLOTS)
+            .take(10)
+            .collect();
+        let other: Vec<Permutation> = RandomElementStream::new(generators())
+            .with_seed(6)
+            .take(10)
+            .collect();
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn random_elements_should_follow_their_source() {
+        let group = Group::with_base(vec![0, 1, 2, 3], vec![], generators());
+        let mut left = XorShift::new(3);
+        let mut right = XorShift::new(3);
+
+        let first: Vec<Permutation> = (0..10).map(|_| group.random_element(&mut left)).collect();
+        let second: Vec<Permutation> = (0..10).map(|_| group.random_element(&mut right)).collect();
+
+        assert_eq!(first, second);
+        assert!(first.iter().all(|element| group.contains(element)));
+    }
+
+    #[test]
+    fn stream_should_draw_from_any_source() {
+        struct Counter(u64);
+
+        impl RandomSource for Counter {
+            fn next_u64(&mut self) -> u64 {
+                self.0 += 7;
+                self.0
+            }
+        }
+
+        let group = Group::with_base(vec![0, 1, 2, 3], vec![], generators());
+        let seeded: Vec<Permutation> = RandomElementStream::new(generators())
+            .with_seed(5)
+            .take(10)
+            .collect();
+        let sourced: Vec<Permutation> = RandomElementStream::new(generators())
+            .with_source(XorShift::new(5))
+            .take(10)
+            .collect();
+
+        assert_eq!(seeded, sourced);
+        for element in RandomElementStream::new(generators())
+            .with_source(Counter(0))
+            .take(10)
+        {
+            assert!(group.contains(&element));
+        }
+    }
+}
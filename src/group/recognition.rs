@@ -0,0 +1,295 @@
+//! Recognize small groups by name.
+//!
+//! A group is first compared with the symmetric and alternating groups in
+//! their natural action: a group that is transitive on the _m_ points it moves
+//! and has order _m_! or _m_!/2 is _S_m or _A_m. Larger groups are not
+//! recognized otherwise. Small abelian groups are told apart by their abelian
+//! invariants. Other small groups are compared with the dihedral, quaternion,
+//! symmetric and alternating groups of the same order by the number of
+//! elements of each order. That last comparison is a heuristic: different
+//! groups can share these statistics, although none of the named candidates of
+//! the same order do.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # use permutation_rs::group::recognition::GroupName;
+//! # fn main() {
+//! let reflection = permute!(0, 0, 1, 3, 2, 2, 3, 1);
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let square = Group::with_base(vec![0, 1, 2, 3], vec![], vec![reflection, rotation]);
+//!
+//! assert_eq!(square.identify(), Some(GroupName::Dihedral(4)));
+//! assert_eq!(format!("{}", GroupName::Dihedral(4)), "D4");
+//! # }
+//! ```
+
+use super::calculation::fact;
+use super::orbit::Orbit;
+use super::permutation::Permutation;
+use super::{Group, GroupAction, GroupElement};
+use num_bigint::BigUint;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::fmt::Display;
+use std::hash::Hash;
+
+/// Groups larger than this are only recognized in their natural action.
+const ELEMENT_LIMIT: u128 = 10_000;
+
+/// The name of a well known group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GroupName {
+    /// The group with one element.
+    Trivial,
+    /// The cyclic group of the given order.
+    Cyclic(u64),
+    /// The Klein four-group _C_2 × _C_2.
+    KleinFour,
+    /// The symmetries of a regular polygon with the given number of sides,
+    /// of order twice that number. The symmetries of a triangle are named
+    /// `Symmetric(3)` instead.
+    Dihedral(u64),
+    /// The quaternion group of order 8.
+    Quaternion,
+    /// The permutations of the given number of points.
+    Symmetric(u64),
+    /// The even permutations of the given number of points.
+    Alternating(u64),
+}
+
+impl Display for GroupName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GroupName::Trivial => write!(f, "1"),
+            GroupName::Cyclic(n) => write!(f, "C{}", n),
+            GroupName::KleinFour => write!(f, "V4"),
+            GroupName::Dihedral(n) => write!(f, "D{}", n),
+            GroupName::Quaternion => write!(f, "Q8"),
+            GroupName::Symmetric(n) => write!(f, "S{}", n),
+            GroupName::Alternating(n) => write!(f, "A{}", n),
+        }
+    }
+}
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// The name of this group, or `None` when it is not recognized.
+    pub fn identify(&self) -> Option<GroupName> {
+        if self.levels.is_empty() {
+            return Some(GroupName::Trivial);
+        }
+        if let Some(name) = self.natural_name() {
+            return Some(name);
+        }
+        if self.big_order() > BigUint::from(ELEMENT_LIMIT) {
+            return None;
+        }
+        if self.is_abelian() {
+            return match self.abelian_invariants().as_slice() {
+                [n] => Some(GroupName::Cyclic(*n)),
+                [2, 2] => Some(GroupName::KleinFour),
+                _ => None,
+            };
+        }
+        let order = self.order();
+        let statistics = order_statistics(&self.elements());
+        candidates(order as u64)
+            .into_iter()
+            .find(|candidate| reference_statistics(*candidate) == statistics)
+    }
+
+    /// The name of this group when it is the symmetric or alternating group
+    /// on the points it moves.
+    fn natural_name(&self) -> Option<GroupName> {
+        let generators = self.generators();
+        let support: HashSet<Domain> = self
+            .gset
+            .iter()
+            .filter(|point| generators.iter().any(|g| g.act_on(point) != **point))
+            .cloned()
+            .collect();
+        let start = support.iter().next()?.clone();
+        let m = support.len() as u64;
+        if !(3..=34).contains(&m) || Orbit::new(start, generators).len() as u64 != m {
+            return None;
+        }
+        let full = (1..=m as u128).product::<u128>();
+        if self.order() == full {
+            Some(GroupName::Symmetric(m))
+        } else if self.order() * 2 == full && m > 3 {
+            Some(GroupName::Alternating(m))
+        } else {
+            None
+        }
+    }
+}
+
+/// The non abelian named groups of the given order.
+fn candidates(order: u64) -> Vec<GroupName> {
+    let mut candidates = vec![];
+    if order.is_multiple_of(2) && order >= 8 {
+        candidates.push(GroupName::Dihedral(order / 2));
+    }
+    if order == 8 {
+        candidates.push(GroupName::Quaternion);
+    }
+    for n in 3..8 {
+        if fact(n) == order {
+            candidates.push(GroupName::Symmetric(n));
+        }
+        if n > 3 && fact(n) == 2 * order {
+            candidates.push(GroupName::Alternating(n));
+        }
+    }
+    candidates
+}
+
+/// The number of elements of each order of a non abelian named group.
+fn reference_statistics(name: GroupName) -> BTreeMap<u64, usize> {
+    let generators = match name {
+        GroupName::Dihedral(n) => vec![images(n, |i| (n - i) % n), images(n, |i| (i + 1) % n)],
+        GroupName::Quaternion => {
+            let i = [1, 2, 3, 0, 7, 4, 5, 6];
+            let j = [4, 5, 6, 7, 2, 3, 0, 1];
+            vec![images(8, |x| i[x as usize]), images(8, |x| j[x as usize])]
+        }
+        GroupName::Symmetric(n) => vec![
+            images(n, |i| if i < 2 { 1 - i } else { i }),
+            images(n, |i| (i + 1) % n),
+        ],
+        GroupName::Alternating(n) => vec![
+            images(n, |i| if i < 3 { (i + 1) % 3 } else { i }),
+            if n % 2 == 1 {
+                images(n, |i| (i + 1) % n)
+            } else {
+                images(n, |i| if i == 0 { 0 } else { i % (n - 1) + 1 })
+            },
+        ],
+        _ => vec![],
+    };
+    let n = generators.first().map(|g| g.degree() as u64).unwrap_or(0);
+    let group = Group::with_base((0..n).collect(), vec![], generators);
+    order_statistics(&group.elements())
+}
+
+fn images<F>(n: u64, image: F) -> Permutation
+where
+    F: Fn(u64) -> u64,
+{
+    let images: HashMap<u64, u64> = (0..n).map(|i| (i, image(i))).collect();
+    Permutation::new(images)
+}
+
+/// The number of elements of each order.
+fn order_statistics<G>(elements: &[G]) -> BTreeMap<u64, usize>
+where
+    G: GroupElement + Clone,
+{
+    let mut statistics = BTreeMap::new();
+    for element in elements {
+        let mut order = 1;
+        let mut power = element.clone();
+        while !power.is_identity() {
+            power = power.times(element);
+            order += 1;
+        }
+        *statistics.entry(order).or_insert(0) += 1;
+    }
+    statistics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn abelian_groups_should_be_named_by_invariants() {
+        let rotation = permute!(0, 1, 1, 2, 2, 0, 3, 4, 4, 3);
+        let swaps = Group::with_base(
+            vec![0, 1, 2, 3],
+            vec![],
+            vec![
+                permute!(0, 1, 1, 0, 2, 2, 3, 3),
+                permute!(0, 0, 1, 1, 2, 3, 3, 2),
+            ],
+        );
+        let cyclic = Group::with_base(vec![0, 1, 2, 3, 4], vec![], vec![rotation]);
+
+        assert_eq!(cyclic.identify(), Some(GroupName::Cyclic(6)));
+        assert_eq!(swaps.identify(), Some(GroupName::KleinFour));
+    }
+
+    #[test]
+    fn large_abelian_groups_should_not_be_named() {
+        let mut images: HashMap<u64, u64> = (0..101).map(|i| (i, (i + 1) % 101)).collect();
+        images.extend((101..204).map(|i| (i, (i - 100) % 103 + 101)));
+        let rotation = Permutation::new(images);
+        let cyclic = Group::with_base((0..204).collect(), vec![], vec![rotation]);
+
+        assert_eq!(cyclic.order(), 10_403);
+        assert_eq!(cyclic.identify(), None);
+    }
+
+    #[test]
+    fn symmetric_group_should_be_recognized_in_another_action() {
+        let transposition = permute!(0, 0, 1, 3, 2, 4, 3, 1, 4, 2, 5, 5);
+        let rotation = permute!(0, 3, 1, 4, 2, 0, 3, 5, 4, 1, 5, 2);
+        let pairs = Group::with_base(
+            vec![0, 1, 2, 3, 4, 5],
+            vec![],
+            vec![transposition, rotation],
+        );
+
+        assert_eq!(pairs.identify(), Some(GroupName::Symmetric(4)));
+    }
+
+    #[test]
+    fn natural_actions_should_be_recognized() {
+        let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3, 4, 4);
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 4, 4, 0);
+        let s5 = Group::with_base(vec![0, 1, 2, 3, 4], vec![], vec![transposition, rotation]);
+
+        assert_eq!(s5.identify(), Some(GroupName::Symmetric(5)));
+        assert_eq!(
+            s5.derived_subgroup().identify(),
+            Some(GroupName::Alternating(5))
+        );
+    }
+
+    #[test]
+    fn symmetric_group_on_three_points_should_have_one_name() {
+        let reflection = permute!(0, 3, 1, 5, 2, 4, 3, 0, 4, 2, 5, 1);
+        let rotation = permute!(0, 1, 1, 2, 2, 0, 3, 4, 4, 5, 5, 3);
+        let triangle = Group::with_base(
+            vec![0, 1, 2],
+            vec![],
+            vec![permute!(0, 0, 1, 2, 2, 1), permute!(0, 1, 1, 2, 2, 0)],
+        );
+        let regular = Group::with_base(vec![0, 1, 2, 3, 4, 5], vec![], vec![reflection, rotation]);
+
+        assert_eq!(regular.order(), 6);
+        assert_eq!(triangle.identify(), Some(GroupName::Symmetric(3)));
+        assert_eq!(regular.identify(), Some(GroupName::Symmetric(3)));
+    }
+
+    #[test]
+    fn quaternion_group_should_not_be_mistaken_for_dihedral() {
+        let i = permute!(0, 1, 1, 2, 2, 3, 3, 0, 4, 7, 5, 4, 6, 5, 7, 6);
+        let j = permute!(0, 4, 1, 5, 2, 6, 3, 7, 4, 2, 5, 3, 6, 0, 7, 1);
+        let quaternion = Group::with_base((0..8).collect(), vec![], vec![i, j]);
+        let reflection = permute!(0, 0, 1, 3, 2, 2, 3, 1);
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+        let square = Group::with_base(vec![0, 1, 2, 3], vec![], vec![reflection, rotation]);
+
+        assert_eq!(quaternion.order(), 8);
+        assert_eq!(quaternion.identify(), Some(GroupName::Quaternion));
+        assert_eq!(square.identify(), Some(GroupName::Dihedral(4)));
+    }
+}
@@ -0,0 +1,320 @@
+//! A rewriting system shortens words to a canonical form with respect to a set
+//! of relators.
+//!
+//! The rules are found with [Knuth-Bendix completion](https://en.wikipedia.org/wiki/Knuth%E2%80%93Bendix_completion_algorithm).
+//! Words are ordered by shortlex, where the order of the letters is the order
+//! of the generators, each generator directly followed by its inverse.
+//!
+//! # Examples
+//! The Klein four-group is generated by two commuting involutions.
+//!
+//! ```rust
+//! # use permutation_rs::group::free::Word;
+//! # use permutation_rs::group::rewriting::RewritingSystem;
+//! let relators = vec![
+//!     Word::new(vec![('a', 2)]),
+//!     Word::new(vec![('b', 2)]),
+//!     Word::new(vec![('a', 1), ('b', 1), ('a', 1), ('b', 1)]),
+//! ];
+//! let mut system = RewritingSystem::new(vec!['a', 'b'], relators);
+//! assert!(system.complete(100));
+//!
+//! let word = Word::new(vec![('b', 1), ('a', 3), ('b', -1)]);
+//!
+//! assert_eq!(word.rewrite(&system), Word::generator('a'));
+//! ```
+use super::free::Word;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A letter is a generator or its inverse. Generator `i` is represented by
+/// `2 * i` and its inverse by `2 * i + 1`.
+type Letter = usize;
+
+/// A rewriting system for words over a fixed list of generators.
+#[derive(Debug, Clone)]
+pub struct RewritingSystem<S>
+where
+    S: Eq + Hash + Clone,
+{
+    generators: Vec<S>,
+    indices: HashMap<S, usize>,
+    rules: Vec<(Vec<Letter>, Vec<Letter>)>,
+}
+
+impl<S> RewritingSystem<S>
+where
+    S: Eq + Hash + Clone,
+{
+    /// Create a rewriting system for the group with the given generators and
+    /// relators.
+    ///
+    /// The system contains the free cancellation rules and one rule for each
+    /// relator. Call `complete` to make it confluent.
+    ///
+    /// # Panics
+    /// Panics when a relator contains a symbol that is not one of the
+    /// `generators`.
+    pub fn new(generators: Vec<S>, relators: Vec<Word<S>>) -> RewritingSystem<S> {
+        let indices: HashMap<S, usize> = generators
+            .iter()
+            .enumerate()
+            .map(|(index, symbol)| (symbol.clone(), index))
+            .collect();
+        let mut system = RewritingSystem {
+            generators,
+            indices,
+            rules: vec![],
+        };
+        for index in 0..system.generators.len() {
+            system.rules.push((vec![2 * index, 2 * index + 1], vec![]));
+            system.rules.push((vec![2 * index + 1, 2 * index], vec![]));
+        }
+        for relator in relators {
+            let letters = system.letters(&relator);
+            system.add_rule(letters, vec![]);
+        }
+        system.interreduce();
+        system
+    }
+
+    /// Run the Knuth-Bendix completion.
+    ///
+    /// Returns whether the system is confluent. Completion does not terminate
+    /// for every presentation, so it gives up when there are more than
+    /// `max_rules` rules.
+    pub fn complete(&mut self, max_rules: usize) -> bool {
+        'restart: loop {
+            for i in 0..self.rules.len() {
+                for j in 0..self.rules.len() {
+                    if let Some((left, right)) = self.unresolved_critical_pair(i, j) {
+                        self.add_rule(left, right);
+                        self.interreduce();
+                        if self.rules.len() > max_rules {
+                            return false;
+                        }
+                        continue 'restart;
+                    }
+                }
+            }
+            return true;
+        }
+    }
+
+    /// The number of rules in this system.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Determine if this system has no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Rewrite `word` until no rule applies.
+    ///
+    /// Symbols that are not generators of this system are left untouched and
+    /// no rule applies across them.
+    pub fn reduce(&self, word: &Word<S>) -> Word<S> {
+        let mut terms: Vec<(S, i64)> = vec![];
+        let mut letters: Vec<Letter> = vec![];
+        for (symbol, exponent) in word.iter() {
+            match self.indices.get(symbol) {
+                Some(&index) => {
+                    letters.extend(syllable(index, *exponent));
+                }
+                None => {
+                    terms.extend(self.terms(&self.rewrite(&letters)));
+                    letters.clear();
+                    terms.push((symbol.clone(), *exponent));
+                }
+            }
+        }
+        terms.extend(self.terms(&self.rewrite(&letters)));
+        Word::new(terms)
+    }
+
+    fn letters(&self, word: &Word<S>) -> Vec<Letter> {
+        let mut letters = vec![];
+        for (symbol, exponent) in word.iter() {
+            let index = *self
+                .indices
+                .get(symbol)
+                .expect("relator symbols should be generators");
+            letters.extend(syllable(index, *exponent));
+        }
+        letters
+    }
+
+    fn terms(&self, letters: &[Letter]) -> Vec<(S, i64)> {
+        letters
+            .iter()
+            .map(|letter| {
+                let exponent = if letter % 2 == 1 { -1 } else { 1 };
+                (self.generators[letter / 2].clone(), exponent)
+            })
+            .collect()
+    }
+
+    fn rewrite(&self, letters: &[Letter]) -> Vec<Letter> {
+        let mut result: Vec<Letter> = letters.to_vec();
+        'rewrite: loop {
+            for (left, right) in &self.rules {
+                if let Some(position) = find(&result, left) {
+                    let mut rewritten: Vec<Letter> = result[0..position].to_vec();
+                    rewritten.extend(right);
+                    rewritten.extend(&result[(position + left.len())..]);
+                    result = rewritten;
+                    continue 'rewrite;
+                }
+            }
+            return result;
+        }
+    }
+
+    fn add_rule(&mut self, left: Vec<Letter>, right: Vec<Letter>) {
+        let left = self.rewrite(&left);
+        let right = self.rewrite(&right);
+        match shortlex(&left, &right) {
+            Ordering::Greater => self.rules.push((left, right)),
+            Ordering::Less => self.rules.push((right, left)),
+            Ordering::Equal => {}
+        }
+    }
+
+    fn interreduce(&mut self) {
+        let mut index = 0;
+        while index < self.rules.len() {
+            let (left, right) = self.rules.remove(index);
+            if self
+                .rules
+                .iter()
+                .any(|(other, _)| find(&left, other).is_some())
+            {
+                self.add_rule(left, right);
+                index = 0;
+            } else {
+                let right = self.rewrite(&right);
+                self.rules.insert(index, (left, right));
+                index += 1;
+            }
+        }
+    }
+
+    fn unresolved_critical_pair(&self, i: usize, j: usize) -> Option<(Vec<Letter>, Vec<Letter>)> {
+        let (ref left_i, ref right_i) = self.rules[i];
+        let (ref left_j, ref right_j) = self.rules[j];
+        for overlap in 1..left_i.len().min(left_j.len()) {
+            if left_i[(left_i.len() - overlap)..] == left_j[0..overlap] {
+                let mut first: Vec<Letter> = right_i.clone();
+                first.extend(&left_j[overlap..]);
+                let mut second: Vec<Letter> = left_i[0..(left_i.len() - overlap)].to_vec();
+                second.extend(right_j);
+                let first = self.rewrite(&first);
+                let second = self.rewrite(&second);
+                if first != second {
+                    return Some((first, second));
+                }
+            }
+        }
+        None
+    }
+}
+
+fn syllable(index: usize, exponent: i64) -> Vec<Letter> {
+    let letter = if exponent < 0 {
+        2 * index + 1
+    } else {
+        2 * index
+    };
+    vec![letter; exponent.unsigned_abs() as usize]
+}
+
+fn find(haystack: &[Letter], needle: &[Letter]) -> Option<usize> {
+    if needle.len() > haystack.len() {
+        return None;
+    }
+    (0..=(haystack.len() - needle.len()))
+        .find(|&start| &haystack[start..(start + needle.len())] == needle)
+}
+
+fn shortlex(left: &[Letter], right: &[Letter]) -> Ordering {
+    left.len().cmp(&right.len()).then_with(|| left.cmp(right))
+}
+
+impl<S> Word<S>
+where
+    S: Eq + Hash + Clone,
+{
+    /// Rewrite this word to its canonical form with respect to `system`.
+    pub fn rewrite(&self, system: &RewritingSystem<S>) -> Word<S> {
+        system.reduce(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::free::Word;
+    use super::super::GroupElement;
+    use super::*;
+    use std::collections::HashSet;
+
+    fn s3() -> RewritingSystem<char> {
+        let relators = vec![
+            Word::new(vec![('a', 2)]),
+            Word::new(vec![('b', 3)]),
+            Word::new(vec![('a', 1), ('b', 1), ('a', 1), ('b', 1)]),
+        ];
+        RewritingSystem::new(vec!['a', 'b'], relators)
+    }
+
+    #[test]
+    #[should_panic(expected = "relator symbols should be generators")]
+    fn relators_should_only_use_generators() {
+        RewritingSystem::new(vec!['a'], vec![Word::new(vec![('b', 2)])]);
+    }
+
+    #[test]
+    fn completion_should_produce_confluent_system() {
+        let mut system = s3();
+
+        assert!(system.complete(100));
+
+        let letters = vec![('a', 1), ('a', -1), ('b', 1), ('b', -1)];
+        let mut words: Vec<Word> = vec![Word::identity()];
+        let mut normal_forms: HashSet<Word> = HashSet::new();
+        for _ in 0..5 {
+            let mut next = vec![];
+            for word in &words {
+                normal_forms.insert(word.rewrite(&system));
+                for &letter in &letters {
+                    next.push(word.times(&Word::new(vec![letter])));
+                }
+            }
+            words = next;
+        }
+
+        assert_eq!(normal_forms.len(), 6);
+    }
+
+    #[test]
+    fn rewrite_should_shorten_words() {
+        let mut system = s3();
+        system.complete(100);
+
+        let word = Word::new(vec![('b', 2), ('a', 1), ('b', 2), ('a', 1)]);
+
+        assert!(word.rewrite(&system).is_identity());
+    }
+
+    #[test]
+    fn rewrite_should_leave_other_symbols_alone() {
+        let mut system = s3();
+        system.complete(100);
+
+        let word = Word::new(vec![('a', 2), ('c', 1), ('b', 3)]);
+
+        assert_eq!(word.rewrite(&system), Word::generator('c'));
+    }
+}
@@ -1,12 +1,40 @@
 //! Home for special groups.
+//!
+//! A group of `SLPPermutation`s remembers how each element is built from the
+//! generators, so stripping a state factorizes it. `Group::solve_verified`
+//! turns that factorization into a word over the move names and checks it by
+//! evaluating the word back into a permutation.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::group::GroupElement;
+//! # use permutation_rs::puzzle::models;
+//! let puzzle = models::cube2();
+//! let state = puzzle
+//!     .sequence("R U F'")
+//!     .unwrap()
+//!     .permutation(&puzzle)
+//!     .unwrap();
+//!
+//! let verified = puzzle
+//!     .group()
+//!     .solve_verified(&state, &puzzle.morphism(), &puzzle.move_images())
+//!     .unwrap();
+//!
+//! assert!(state.times(&verified.product).is_identity());
+//! ```
 
 use super::free::Word;
 use super::permutation::Permutation;
 use super::tree::SLP;
-use super::{GroupAction, GroupElement, Morphism};
+use super::{Group, GroupAction, GroupElement, Morphism};
+use std::collections::{HashMap, HashSet};
+use std::error;
+use std::fmt;
+use std::hash::Hash;
 
 /// A special product of a `SLP` and a `Permutation`.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct SLPPermutation {
     /// The product of a SLP and a Permutation.
     pub element: (SLP, Permutation),
@@ -21,12 +49,19 @@ impl SLPPermutation {
     }
 
     /// Map the `SLPPermutation` in to a `Word` according to the `Morphism`.
-    pub fn transform(&self, morphism: &Morphism<SLP, Word>) -> Word {
-        self.element.0.transform(&morphism)
+    pub fn transform<S>(&self, morphism: &Morphism<SLP, Word<S>>) -> Word<S>
+    where
+        S: Eq + Hash + Clone,
+    {
+        self.element.0.transform(morphism)
     }
 }
 
 impl GroupElement for SLPPermutation {
+    fn identity() -> SLPPermutation {
+        SLPPermutation::new(SLP::Identity, Permutation::identity())
+    }
+
     fn is_identity(&self) -> bool {
         self.element.1.is_identity()
     }
@@ -51,12 +86,114 @@ impl GroupAction for SLPPermutation {
     }
 }
 
+impl Group<u64, SLPPermutation> {
+    /// Find a word that solves `state`, and verify it.
+    ///
+    /// The state is stripped, the resulting `SLP` is mapped to a word with
+    /// `morphism`, and the word is evaluated with the permutations in
+    /// `images`. The solution is only returned when that product is the
+    /// inverse of `state`.
+    ///
+    /// Before stripping, `morphism` is checked to have a word for every
+    /// generator of this group, and `images` to have a permutation for every
+    /// symbol of those words.
+    pub fn solve_verified<S>(
+        &self,
+        state: &Permutation,
+        morphism: &Morphism<SLP, Word<S>>,
+        images: &HashMap<S, Permutation>,
+    ) -> Result<Verified<S>, VerificationError>
+    where
+        S: Eq + Hash + Clone,
+    {
+        let mut generators = HashSet::new();
+        for generator in self.generators() {
+            collect_generators(&generator.element.0, &mut generators);
+        }
+        for generator in &generators {
+            let word = morphism
+                .try_transform(generator)
+                .ok_or(VerificationError::MissingImage)?;
+            if !word.iter().all(|(symbol, _)| images.contains_key(symbol)) {
+                return Err(VerificationError::MissingImage);
+            }
+        }
+        let stripped = self.strip(SLPPermutation::new(SLP::Identity, state.clone()));
+        if !stripped.element.1.is_identity() {
+            return Err(VerificationError::NotAMember);
+        }
+        let word = stripped.transform(morphism);
+        let product = word.evaluate(images);
+        if !state.times(&product).is_identity() {
+            return Err(VerificationError::Mismatch(product));
+        }
+        Ok(Verified { word, product })
+    }
+}
+
+/// Gather the generators that `slp` is built from.
+fn collect_generators(slp: &SLP, generators: &mut HashSet<SLP>) {
+    match *slp {
+        SLP::Identity => {}
+        SLP::Generator(_) => {
+            generators.insert(slp.clone());
+        }
+        SLP::Product(ref left, ref right) => {
+            collect_generators(left, generators);
+            collect_generators(right, generators);
+        }
+        SLP::Inverse(ref inverse) => collect_generators(inverse, generators),
+    }
+}
+
+/// A solution together with the proof that it works.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Verified<S>
+where
+    S: Eq + Hash + Clone,
+{
+    /// The word that solves the state.
+    pub word: Word<S>,
+    /// The evaluation of the word, which is the inverse of the state.
+    pub product: Permutation,
+}
+
+/// The ways a verified solve can fail.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VerificationError {
+    /// The state is not a member of the group, so it can not be solved.
+    NotAMember,
+    /// The word does not evaluate to the inverse of the state, but to this
+    /// permutation. This points to a morphism or images that do not match the
+    /// generators of the group.
+    Mismatch(Permutation),
+    /// The morphism has no word for a generator of the group, or the images
+    /// have no permutation for a symbol of such a word.
+    MissingImage,
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VerificationError::NotAMember => write!(f, "this state can not be solved"),
+            VerificationError::Mismatch(ref product) => {
+                write!(f, "solution evaluates to {} instead", product)
+            }
+            VerificationError::MissingImage => write!(f, "a generator or move has no image"),
+        }
+    }
+}
+
+impl error::Error for VerificationError {}
+
 #[cfg(test)]
 mod tests {
+    use super::super::super::puzzle::models;
     use super::super::permutation::Permutation;
     use super::super::tree::SLP;
+    use super::super::Morphism;
     use super::super::{GroupAction, GroupElement};
-    use super::SLPPermutation;
+    use super::{SLPPermutation, VerificationError};
     use std::collections::HashMap;
 
     #[test]
@@ -138,6 +275,59 @@ mod tests {
         assert_eq!(permutation.act_on(&2u64), 0u64);
     }
 
+    #[test]
+    fn solve_verified_should_reject_non_members() {
+        let puzzle = models::cube2();
+        let swap = Permutation::new((0..24).map(|i| (i, i ^ 1)).collect());
+
+        let result =
+            puzzle
+                .group()
+                .solve_verified(&swap, &puzzle.morphism(), &puzzle.move_images());
+
+        assert_eq!(result, Err(VerificationError::NotAMember));
+    }
+
+    #[test]
+    fn solve_verified_should_catch_mismatched_morphisms() {
+        let puzzle = models::cube2();
+        let state = puzzle.move_named("R").unwrap().clone();
+        let images: HashMap<SLP, _> = puzzle
+            .morphism()
+            .images()
+            .keys()
+            .map(|slp| (slp.clone(), super::Word::generator(String::from("U"))))
+            .collect();
+
+        let result =
+            puzzle
+                .group()
+                .solve_verified(&state, &Morphism::new(images), &puzzle.move_images());
+
+        assert!(matches!(result, Err(VerificationError::Mismatch(_))));
+    }
+
+    #[test]
+    fn solve_verified_should_report_missing_images() {
+        let puzzle = models::cube2();
+        let state = puzzle.move_named("R").unwrap().clone();
+        let group = puzzle.group();
+        let mut partial_morphism = puzzle.morphism().images().clone();
+        partial_morphism.remove(&SLP::Generator(0));
+        let mut partial_images = puzzle.move_images();
+        partial_images.remove("R");
+
+        let without_generator = group.solve_verified(
+            &state,
+            &Morphism::new(partial_morphism),
+            &puzzle.move_images(),
+        );
+        let without_move = group.solve_verified(&state, &puzzle.morphism(), &partial_images);
+
+        assert_eq!(without_generator, Err(VerificationError::MissingImage));
+        assert_eq!(without_move, Err(VerificationError::MissingImage));
+    }
+
     // #[test]
     // fn permutation_should_display_correctly() {
     //     let mut identity_images = HashMap::new();
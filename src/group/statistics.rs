@@ -0,0 +1,195 @@
+//! The orders of the elements of a group.
+//!
+//! The order of an element is the least common multiple of the lengths of its
+//! cycles. How many elements there are of each order helps to tell groups
+//! apart. For a small group every element is counted. For a large group a
+//! sample of uniformly distributed random elements estimates the distribution.
+//!
+//! The exponent of a group is the least common multiple of the orders of its
+//! elements: the least _n_ with _g_^_n_ the identity for every element _g_.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let s4 = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation]);
+//!
+//! let statistics = s4.element_order_statistics(100);
+//!
+//! assert!(statistics.is_exhaustive());
+//! assert_eq!(statistics.count(2), 9);
+//! assert_eq!(s4.exponent(), 12);
+//! # }
+//! ```
+
+use super::conjugacy::cycle_type;
+use super::random::{RandomSource, XorShift};
+use super::{Group, GroupAction, GroupElement};
+use num_bigint::BigUint;
+use std::collections::BTreeMap;
+use std::hash::Hash;
+
+/// The number of elements of each order, among all elements or a sample.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderStatistics {
+    counts: BTreeMap<u64, usize>,
+    exhaustive: bool,
+}
+
+impl OrderStatistics {
+    /// The number of elements of each order.
+    pub fn counts(&self) -> &BTreeMap<u64, usize> {
+        &self.counts
+    }
+
+    /// The number of elements of `order`.
+    pub fn count(&self, order: u64) -> usize {
+        self.counts.get(&order).cloned().unwrap_or(0)
+    }
+
+    /// The number of elements that were counted.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// The fraction of the counted elements that have `order`.
+    pub fn fraction(&self, order: u64) -> f64 {
+        self.count(order) as f64 / self.total() as f64
+    }
+
+    /// Determine if every element of the group was counted, rather than a
+    /// sample.
+    pub fn is_exhaustive(&self) -> bool {
+        self.exhaustive
+    }
+}
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// The number of elements of each order. Every element is counted when
+    /// the group has at most `sample_size` elements, otherwise a sample of
+    /// `sample_size` random elements is.
+    ///
+    /// The sample is seeded, so the same group always yields the same
+    /// statistics.
+    pub fn element_order_statistics(&self, sample_size: usize) -> OrderStatistics {
+        let mut random = XorShift::new(sample_size as u64);
+        self.element_order_statistics_with(sample_size, &mut random)
+    }
+
+    /// The same as `element_order_statistics`, with the sample drawn from
+    /// `random`.
+    pub fn element_order_statistics_with<R>(
+        &self,
+        sample_size: usize,
+        random: &mut R,
+    ) -> OrderStatistics
+    where
+        R: RandomSource + ?Sized,
+    {
+        let exhaustive = self.big_order() <= BigUint::from(sample_size);
+        let elements = if exhaustive {
+            self.elements()
+        } else {
+            (0..sample_size)
+                .map(|_| self.random_element(random))
+                .collect()
+        };
+        let mut counts = BTreeMap::new();
+        for element in &elements {
+            *counts.entry(self.element_order(element)).or_insert(0) += 1;
+        }
+        OrderStatistics { counts, exhaustive }
+    }
+
+    /// The exponent of this group, the least common multiple of the orders of
+    /// its elements.
+    ///
+    /// Every element is visited, so this is meant for small groups.
+    pub fn exponent(&self) -> u64 {
+        self.elements().iter().fold(1, |exponent, element| {
+            lcm(exponent, self.element_order(element))
+        })
+    }
+
+    /// The order of `element`, from the lengths of its cycles on the gset.
+    pub fn element_order(&self, element: &G) -> u64 {
+        cycle_type(&self.gset, element)
+            .into_iter()
+            .fold(1, |order, length| lcm(order, length as u64))
+    }
+}
+
+pub(super) fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub(super) fn lcm(a: u64, b: u64) -> u64 {
+    a / gcd(a, b) * b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::permutation::Permutation;
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn small_groups_should_be_counted_exhaustively() {
+        let group = Group::with_base(
+            vec![0, 1, 2, 3],
+            vec![],
+            vec![
+                permute!(0, 1, 1, 0, 2, 2, 3, 3),
+                permute!(0, 1, 1, 2, 2, 3, 3, 0),
+            ],
+        );
+
+        let statistics = group.element_order_statistics(24);
+
+        let expected: BTreeMap<u64, usize> =
+            vec![(1, 1), (2, 9), (3, 8), (4, 6)].into_iter().collect();
+        assert!(statistics.is_exhaustive());
+        assert_eq!(statistics.counts(), &expected);
+        assert_eq!(group.exponent(), 12);
+    }
+
+    #[test]
+    fn large_groups_should_be_sampled() {
+        let group = Group::with_base(
+            vec![0, 1, 2, 3, 4],
+            vec![],
+            vec![
+                permute!(0, 1, 1, 0, 2, 2, 3, 3, 4, 4),
+                permute!(0, 1, 1, 2, 2, 3, 3, 4, 4, 0),
+            ],
+        );
+
+        let statistics = group.element_order_statistics(50);
+
+        assert!(!statistics.is_exhaustive());
+        assert_eq!(statistics.total(), 50);
+        assert!(statistics.counts().keys().all(|order| 60 % order == 0));
+        assert_eq!(group.element_order_statistics(50), statistics);
+    }
+
+    #[test]
+    fn trivial_group_should_have_exponent_one() {
+        let group: Group<u64, Permutation> = Group::with_base(vec![0, 1], vec![], vec![]);
+
+        assert_eq!(group.exponent(), 1);
+        assert_eq!(group.element_order_statistics(10).count(1), 1);
+    }
+}
@@ -0,0 +1,159 @@
+//! Sylow subgroups of a group.
+//!
+//! A Sylow _p_-subgroup is a subgroup whose order is the largest power of _p_
+//! that divides the order of the group. It is found by climbing: starting from
+//! the trivial subgroup, the _p_-part of a random element is added whenever that
+//! keeps the subgroup a _p_-group. Such an element lies in the normalizer of the
+//! current subgroup. The random elements are products of random transversals
+//! of the stabilizer chain, so they are uniformly distributed over the group.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let s4 = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation]);
+//!
+//! assert_eq!(s4.sylow_subgroup(2).order(), 8);
+//! assert_eq!(s4.sylow_subgroup(3).order(), 3);
+//! # }
+//! ```
+
+use super::random::XorShift;
+use super::{factor, Group, GroupAction, GroupElement};
+use std::hash::Hash;
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// A Sylow `p`-subgroup of this group.
+    ///
+    /// The search is randomized, but seeded, so the same group always yields the
+    /// same subgroup. It is meant for groups of moderate size.
+    ///
+    /// # Panics
+    /// Panics when `p` is not prime.
+    pub fn sylow_subgroup(&self, p: u64) -> Group<Domain, G> {
+        assert!(factor(p) == [(p, 1)], "{} should be prime", p);
+        let target = p_exponent(self, p);
+        let mut random = XorShift::new(p);
+        let mut generators: Vec<G> = vec![];
+        let mut sylow = Group::with_base(self.gset.clone(), vec![], vec![]);
+        while p_exponent(&sylow, p) < target {
+            let element = self.random_element(&mut random);
+            let candidate = p_element(&element, p);
+            if candidate.is_identity() || sylow.contains(&candidate) {
+                continue;
+            }
+            let mut extended = generators.clone();
+            extended.push(candidate);
+            let group = Group::with_base(self.gset.clone(), vec![], extended.clone());
+            if group.order_factored().iter().all(|&(prime, _)| prime == p) {
+                generators = extended;
+                sylow = group;
+            }
+        }
+        sylow
+    }
+}
+
+/// The exponent of the largest power of `p` that divides the order of `group`.
+fn p_exponent<Domain, G>(group: &Group<Domain, G>, p: u64) -> u32
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq,
+{
+    group
+        .order_factored()
+        .into_iter()
+        .find(|&(prime, _)| prime == p)
+        .map(|(_, exponent)| exponent)
+        .unwrap_or(0)
+}
+
+/// The power of `element` whose order is the `p`-part of the order of
+/// `element`.
+fn p_element<G>(element: &G, p: u64) -> G
+where
+    G: GroupElement + Clone,
+{
+    let mut order: u64 = 1;
+    let mut power = element.clone();
+    while !power.is_identity() {
+        power = power.times(element);
+        order += 1;
+    }
+    while order.is_multiple_of(p) {
+        order /= p;
+    }
+    let mut result = element.clone();
+    for _ in 1..order {
+        result = result.times(element);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::permutation::Permutation;
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn p_exponent_should_divide_out_other_primes() {
+        let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+        let s4 = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation]);
+
+        assert_eq!(p_exponent(&s4, 2), 3);
+        assert_eq!(p_exponent(&s4, 3), 1);
+        assert_eq!(p_exponent(&s4, 5), 0);
+    }
+
+    #[test]
+    fn sylow_subgroup_of_absent_prime_should_be_trivial() {
+        let rotation = permute!(0, 1, 1, 2, 2, 0);
+        let group = Group::with_base(vec![0, 1, 2], vec![], vec![rotation]);
+
+        assert_eq!(group.sylow_subgroup(2).order(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "should be prime")]
+    fn sylow_subgroup_should_panic_for_one() {
+        let rotation = permute!(0, 1, 1, 2, 2, 0);
+        let group = Group::with_base(vec![0, 1, 2], vec![], vec![rotation]);
+
+        group.sylow_subgroup(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "should be prime")]
+    fn sylow_subgroup_should_panic_for_composites() {
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+        let group = Group::with_base(vec![0, 1, 2, 3], vec![], vec![rotation]);
+
+        group.sylow_subgroup(4);
+    }
+
+    #[test]
+    fn sylow_subgroups_of_symmetric_group_should_have_prime_power_order() {
+        let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3, 4, 4, 5, 5);
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 0);
+        let group = Group::with_base(
+            vec![0, 1, 2, 3, 4, 5],
+            vec![],
+            vec![transposition, rotation],
+        );
+
+        assert_eq!(group.sylow_subgroup(2).order(), 16);
+        assert_eq!(group.sylow_subgroup(3).order(), 9);
+        assert_eq!(group.sylow_subgroup(5).order(), 5);
+    }
+}
@@ -0,0 +1,237 @@
+//! Helpers to test implementations of `GroupElement` and `GroupAction`.
+//!
+//! The assertions check the group axioms on a sample of elements, so that a
+//! new element type can be validated before groups are built from it. With the
+//! `proptest` feature, this module also offers
+//! [proptest](https://docs.rs/proptest) strategies that generate random
+//! permutations and words.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # use permutation_rs::group::testing::{assert_action_axioms, assert_group_axioms};
+//! # fn main() {
+//! let elements = vec![
+//!     permute!(0, 1, 1, 0, 2, 2),
+//!     permute!(0, 1, 1, 2, 2, 0),
+//! ];
+//!
+//! assert_group_axioms(&elements);
+//! assert_action_axioms(&elements, &[0, 1, 2]);
+//! # }
+//! ```
+
+use super::{GroupAction, GroupElement};
+use std::fmt::Debug;
+
+#[cfg(any(test, feature = "proptest"))]
+use super::array::ArrayPermutation;
+#[cfg(any(test, feature = "proptest"))]
+use super::free::Word;
+#[cfg(any(test, feature = "proptest"))]
+use super::permutation::Permutation;
+#[cfg(test)]
+use super::Group;
+#[cfg(any(test, feature = "proptest"))]
+use proptest::prelude::*;
+#[cfg(any(test, feature = "proptest"))]
+use std::collections::HashMap;
+
+/// Assert that `elements` satisfy the group axioms: multiplication is
+/// associative, `identity()` and `a * a^-1` are identities that leave every
+/// element unchanged, and inverses work from both sides.
+///
+/// Panics, naming the offending elements, when an axiom fails.
+pub fn assert_group_axioms<G>(elements: &[G])
+where
+    G: GroupElement + PartialEq + Debug,
+{
+    let neutral = G::identity();
+    assert!(neutral.is_identity(), "identity() is not the identity");
+    for a in elements {
+        assert!(
+            neutral.times(a) == *a,
+            "identity() * {:?} is not {:?}",
+            a,
+            a
+        );
+        assert!(
+            a.times(&neutral) == *a,
+            "{:?} * identity() is not {:?}",
+            a,
+            a
+        );
+    }
+    for a in elements {
+        let inverse = a.inverse();
+        let identity = a.times(&inverse);
+        assert!(
+            identity.is_identity(),
+            "{:?} * {:?}^-1 is not the identity",
+            a,
+            a
+        );
+        assert!(
+            inverse.times(a).is_identity(),
+            "{:?}^-1 * {:?} is not the identity",
+            a,
+            a
+        );
+        for b in elements {
+            assert!(identity.times(b) == *b, "identity * {:?} is not {:?}", b, b);
+            assert!(
+                b.times(&identity) == *b,
+                "{:?} * identity is not {:?}",
+                b,
+                b
+            );
+            for c in elements {
+                assert!(
+                    a.times(b).times(c) == a.times(&b.times(c)),
+                    "multiplication of {:?}, {:?} and {:?} is not associative",
+                    a,
+                    b,
+                    c
+                );
+            }
+        }
+    }
+}
+
+/// Assert that `elements` act on `points` as a right action: acting with
+/// `g * h` equals acting with `g` and then with `h`, and the identity fixes
+/// every point.
+///
+/// Panics, naming the offending elements and point, when an axiom fails.
+pub fn assert_action_axioms<G>(elements: &[G], points: &[G::Domain])
+where
+    G: GroupElement + GroupAction + Debug,
+    G::Domain: PartialEq + Debug,
+{
+    let identity = G::identity();
+    for point in points {
+        assert!(
+            identity.act_on(point) == *point,
+            "identity does not fix {:?}",
+            point
+        );
+    }
+    for g in elements {
+        for point in points {
+            for h in elements {
+                assert!(
+                    g.times(h).act_on(point) == h.act_on(&g.act_on(point)),
+                    "{:?} * {:?} does not act on {:?} as {:?} followed by {:?}",
+                    g,
+                    h,
+                    point,
+                    g,
+                    h
+                );
+            }
+        }
+    }
+}
+
+/// A strategy for permutations of the points `0..degree`.
+#[cfg(any(test, feature = "proptest"))]
+pub fn permutation(degree: u64) -> impl Strategy<Value = Permutation> {
+    Just((0..degree).collect::<Vec<u64>>())
+        .prop_shuffle()
+        .prop_map(|images| {
+            let images: HashMap<u64, u64> = images
+                .into_iter()
+                .enumerate()
+                .map(|(i, image)| (i as u64, image))
+                .collect();
+            Permutation::new(images)
+        })
+}
+
+/// A strategy for permutations of the points `0..N`.
+#[cfg(any(test, feature = "proptest"))]
+pub fn array_permutation<const N: usize>() -> impl Strategy<Value = ArrayPermutation<N>> {
+    Just((0..N as u16).collect::<Vec<u16>>())
+        .prop_shuffle()
+        .prop_map(|images| {
+            let mut array = [0u16; N];
+            array.copy_from_slice(&images);
+            ArrayPermutation::try_new(array).expect("a shuffle should be a permutation")
+        })
+}
+
+/// A strategy for words over `symbols` of at most `length` syllables, with
+/// exponents between -3 and 3.
+#[cfg(any(test, feature = "proptest"))]
+pub fn word(symbols: Vec<char>, length: usize) -> impl Strategy<Value = Word> {
+    proptest::collection::vec((proptest::sample::select(symbols), -3i64..=3), 0..=length)
+        .prop_map(Word::new)
+}
+
+/// The symmetric group on four points, a fixture shared by the tests of the
+/// group modules.
+#[cfg(test)]
+pub(super) fn s4() -> Group<u64, Permutation> {
+    let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+    let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+    Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn permutations_should_satisfy_the_axioms(
+            elements in proptest::collection::vec(permutation(6), 3)
+        ) {
+            assert_group_axioms(&elements);
+            assert_action_axioms(&elements, &[0, 1, 2, 3, 4, 5, 6]);
+        }
+
+        #[test]
+        fn array_permutations_should_satisfy_the_axioms(
+            elements in proptest::collection::vec(array_permutation::<6>(), 3)
+        ) {
+            assert_group_axioms(&elements);
+            assert_action_axioms(&elements, &[0, 1, 2, 3, 4, 5, 6]);
+        }
+
+        #[test]
+        fn words_should_satisfy_the_axioms(
+            elements in proptest::collection::vec(word(vec!['a', 'b', 'c'], 4), 3)
+        ) {
+            assert_group_axioms(&elements);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "is not Subtraction")]
+    fn broken_multiplication_should_be_reported() {
+        #[derive(Debug, PartialEq)]
+        struct Subtraction(i64);
+
+        impl GroupElement for Subtraction {
+            fn identity() -> Subtraction {
+                Subtraction(0)
+            }
+
+            fn is_identity(&self) -> bool {
+                self.0 == 0
+            }
+
+            fn times(&self, multiplicant: &Subtraction) -> Subtraction {
+                Subtraction(self.0 - multiplicant.0)
+            }
+
+            fn inverse(&self) -> Subtraction {
+                Subtraction(self.0)
+            }
+        }
+
+        assert_group_axioms(&[Subtraction(1), Subtraction(2)]);
+    }
+}
@@ -30,12 +30,15 @@
 
 use super::free::Word;
 use super::{GroupAction, GroupElement, Morphism};
+#[cfg(any(test, feature = "arbitrary"))]
+use arbitrary::{Arbitrary, Unstructured};
 use std::cell::{RefCell, RefMut};
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Display;
 use std::hash::Hash;
 use std::rc::Rc;
+use std::sync::{Arc, RwLock};
 
 /// A `SLPElement` keeps track of how a word is formed in a `SLPCollection`.
 pub enum SLPElement {
@@ -45,6 +48,8 @@ pub enum SLPElement {
     Product(u64, u64),
     /// An inverse of an other word, looked up by id in a `SLPCollection`.
     Inverse(u64),
+    /// The identity, which evaluates to the identity element.
+    Identity,
 }
 
 /// A `SLPCollection` keeps tracks of various words that are build up from each
@@ -81,6 +86,10 @@ where
         id
     }
 
+    fn is_identity_element(&self, id: &u64) -> bool {
+        matches!(self.associations.get(id), Some(SLPElement::Identity))
+    }
+
     /// Registers a generator that will evaluate to the group element `g`.
     /// Return the id with which this `Generator` element can be looked up.
     pub fn generator(&mut self, g: G) -> u64 {
@@ -114,6 +123,8 @@ where
 
                     Some(g.inverse())
                 }
+
+                SLPElement::Identity => Some(G::identity()),
             }
         } else {
             None
@@ -121,6 +132,15 @@ where
     }
 }
 
+impl<G> Default for SLPCollection<G>
+where
+    G: GroupElement + Clone,
+{
+    fn default() -> Self {
+        SLPCollection::new()
+    }
+}
+
 /// `SLPWord`s for the actual group elements of a SLP.
 ///
 /// To create `SLPWord` generators you need a `SLPFactory`. Otherwise you can
@@ -150,6 +170,10 @@ where
     G: GroupElement + Eq + Hash + Clone,
 {
     /// Apply a morphism to this element.
+    ///
+    /// # Panics
+    /// Panics when this element does not evaluate to one of the generators of
+    /// `morphism`.
     pub fn transform<H>(&self, morphism: &Morphism<G, H>) -> H
     where
         H: GroupElement + Eq + Hash + Clone,
@@ -158,15 +182,54 @@ where
     }
 }
 
+impl<G> SLPWord<G>
+where
+    G: GroupElement + Clone,
+{
+    fn is_identity_element(&self) -> bool {
+        self.collection.borrow().is_identity_element(&self.id)
+    }
+}
+
+impl<G> Clone for SLPWord<G>
+where
+    G: GroupElement + Clone,
+{
+    fn clone(&self) -> Self {
+        SLPWord {
+            collection: self.collection.clone(),
+            id: self.id,
+        }
+    }
+}
+
 impl<G> GroupElement for SLPWord<G>
 where
     G: GroupElement + Clone,
 {
+    /// An identity word of its own collection. Multiplying with it returns the
+    /// other factor, so it combines with the words of any collection.
+    fn identity() -> Self {
+        let mut collection = SLPCollection::new();
+        let id = collection.register(SLPElement::Identity);
+
+        SLPWord {
+            collection: Rc::new(RefCell::new(collection)),
+            id,
+        }
+    }
+
     fn is_identity(&self) -> bool {
         unimplemented!();
     }
 
     fn times(&self, multiplicant: &Self) -> Self {
+        if self.is_identity_element() {
+            return multiplicant.clone();
+        }
+        if multiplicant.is_identity_element() {
+            return self.clone();
+        }
         let element = SLPElement::Product(self.id, multiplicant.id);
         let mut collection_ref: RefMut<SLPCollection<G>> = self.collection.borrow_mut();
         let id = (*collection_ref).register(element);
@@ -178,6 +241,9 @@ where
     }
 
     fn inverse(&self) -> Self {
+        if self.is_identity_element() {
+            return self.clone();
+        }
         let element = SLPElement::Inverse(self.id);
         let mut collection_ref: RefMut<SLPCollection<G>> = self.collection.borrow_mut();
         let id = (*collection_ref).register(element);
@@ -197,7 +263,7 @@ where
     type Domain = Domain;
 
     fn act_on(&self, element: &Self::Domain) -> Self::Domain {
-        self.evaluate().act_on(&element)
+        self.evaluate().act_on(element)
     }
 }
 
@@ -234,6 +300,206 @@ where
     }
 }
 
+impl<G> Default for SLPFactory<G>
+where
+    G: GroupElement + Clone,
+{
+    fn default() -> Self {
+        SLPFactory::new()
+    }
+}
+
+/// A `Send + Sync` counterpart of `SLPWord`.
+///
+/// The words share their `SLPCollection` behind an `Arc<RwLock<...>>`, so they
+/// can be multiplied on several threads at once, e.g. while a group is
+/// constructed with `Group::new_parallel`.
+pub struct SyncSLPWord<G>
+where
+    G: GroupElement + Clone,
+{
+    collection: Arc<RwLock<SLPCollection<G>>>,
+    id: u64,
+}
+
+impl<G> SyncSLPWord<G>
+where
+    G: GroupElement + Clone,
+{
+    /// Evaluate this `SyncSLPWord` according to the evaluation setup by
+    /// construction.
+    pub fn evaluate(&self) -> G {
+        let collection_ref = self
+            .collection
+            .read()
+            .expect("collection should not be poisoned");
+        (*collection_ref).evaluate(&self.id).unwrap()
+    }
+
+    fn is_identity_element(&self) -> bool {
+        self.collection
+            .read()
+            .expect("collection should not be poisoned")
+            .is_identity_element(&self.id)
+    }
+
+    fn register(&self, element: SLPElement) -> Self {
+        let mut collection_ref = self
+            .collection
+            .write()
+            .expect("collection should not be poisoned");
+        let id = (*collection_ref).register(element);
+
+        SyncSLPWord {
+            collection: self.collection.clone(),
+            id,
+        }
+    }
+}
+
+impl<G> SyncSLPWord<G>
+where
+    G: GroupElement + Eq + Hash + Clone,
+{
+    /// Apply a morphism to this element.
+    ///
+    /// # Panics
+    /// Panics when this element does not evaluate to one of the generators of
+    /// `morphism`.
+    pub fn transform<H>(&self, morphism: &Morphism<G, H>) -> H
+    where
+        H: GroupElement + Eq + Hash + Clone,
+    {
+        morphism.transform(&self.evaluate())
+    }
+}
+
+impl<G> Clone for SyncSLPWord<G>
+where
+    G: GroupElement + Clone,
+{
+    fn clone(&self) -> Self {
+        SyncSLPWord {
+            collection: self.collection.clone(),
+            id: self.id,
+        }
+    }
+}
+
+impl<G> PartialEq for SyncSLPWord<G>
+where
+    G: GroupElement + Clone + PartialEq,
+{
+    /// Two words are equal when they evaluate to the same element.
+    fn eq(&self, other: &Self) -> bool {
+        self.evaluate() == other.evaluate()
+    }
+}
+
+impl<G> GroupElement for SyncSLPWord<G>
+where
+    G: GroupElement + Clone,
+{
+    /// An identity word of its own collection. Multiplying with it returns the
+    /// other factor, so it combines with the words of any collection.
+    fn identity() -> Self {
+        let mut collection = SLPCollection::new();
+        let id = collection.register(SLPElement::Identity);
+
+        SyncSLPWord {
+            collection: Arc::new(RwLock::new(collection)),
+            id,
+        }
+    }
+
+    fn is_identity(&self) -> bool {
+        self.evaluate().is_identity()
+    }
+
+    fn times(&self, multiplicant: &Self) -> Self {
+        if self.is_identity_element() {
+            return multiplicant.clone();
+        }
+        if multiplicant.is_identity_element() {
+            return self.clone();
+        }
+        self.register(SLPElement::Product(self.id, multiplicant.id))
+    }
+
+    fn inverse(&self) -> Self {
+        if self.is_identity_element() {
+            return self.clone();
+        }
+        self.register(SLPElement::Inverse(self.id))
+    }
+}
+
+impl<Domain, G> GroupAction for SyncSLPWord<G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + Clone,
+{
+    type Domain = Domain;
+
+    fn act_on(&self, element: &Self::Domain) -> Self::Domain {
+        self.evaluate().act_on(element)
+    }
+}
+
+/// A `SyncSLPFactory` creates `SyncSLPWord`s that corresponds with generators.
+pub struct SyncSLPFactory<G>
+where
+    G: GroupElement + Clone,
+{
+    collection: Arc<RwLock<SLPCollection<G>>>,
+}
+
+impl<G> SyncSLPFactory<G>
+where
+    G: GroupElement + Clone,
+{
+    /// Create a new `SyncSLPFactory`.
+    pub fn new() -> SyncSLPFactory<G> {
+        SyncSLPFactory {
+            collection: Arc::new(RwLock::new(SLPCollection::new())),
+        }
+    }
+
+    /// Create a `SyncSLPWord` that evaluates to the group element `g`.
+    pub fn generator(&self, g: G) -> SyncSLPWord<G> {
+        let mut collection_ref = self
+            .collection
+            .write()
+            .expect("collection should not be poisoned");
+        let id = (*collection_ref).generator(g);
+
+        SyncSLPWord {
+            collection: self.collection.clone(),
+            id,
+        }
+    }
+}
+
+impl<G> Clone for SyncSLPFactory<G>
+where
+    G: GroupElement + Clone,
+{
+    fn clone(&self) -> Self {
+        SyncSLPFactory {
+            collection: self.collection.clone(),
+        }
+    }
+}
+
+impl<G> Default for SyncSLPFactory<G>
+where
+    G: GroupElement + Clone,
+{
+    fn default() -> Self {
+        SyncSLPFactory::new()
+    }
+}
+
 /// Single Line Program (SLP) references various elements to form a expression
 /// That can be evaluated to actual group elements.
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -250,24 +516,73 @@ pub enum SLP {
 
 impl SLP {
     /// Map the `SLP` in to a `Word` according to the `Morphism`.
-    pub fn transform(&self, morphism: &Morphism<SLP, Word>) -> Word {
+    ///
+    /// # Panics
+    /// Panics when a generator of this `SLP` has no image under `morphism`.
+    pub fn transform<S>(&self, morphism: &Morphism<SLP, Word<S>>) -> Word<S>
+    where
+        S: Eq + Hash + Clone,
+    {
         match *self {
             SLP::Identity => Word::identity(),
-            ref g @ SLP::Generator(_) => morphism.transform(&g),
+            ref g @ SLP::Generator(_) => morphism.transform(g),
+            SLP::Product(ref left, ref right) => (*left)
+                .transform(morphism)
+                .times(&(*right).transform(morphism)),
+            SLP::Inverse(ref g) => (*g).transform(morphism).inverse(),
+        }
+    }
+
+    /// Evaluate the `SLP` in an arbitrary group by replacing each generator
+    /// with its image.
+    ///
+    /// Panics when a generator has no image.
+    pub fn evaluate_with<H>(&self, images: &HashMap<u64, H>) -> H
+    where
+        H: GroupElement + Clone,
+    {
+        match *self {
+            SLP::Identity => H::identity(),
+            SLP::Generator(n) => images.get(&n).expect("should have an image").clone(),
             SLP::Product(ref left, ref right) => (*left)
-                .transform(&morphism)
-                .times(&(*right).transform(&morphism)),
-            SLP::Inverse(ref g) => (*g).transform(&morphism).inverse(),
+                .evaluate_with(images)
+                .times(&(*right).evaluate_with(images)),
+            SLP::Inverse(ref g) => (*g).evaluate_with(images).inverse(),
         }
     }
 }
 
+/// Arbitrary SLPs of bounded depth over at most 16 generators, for fuzzing.
+#[cfg(any(test, feature = "arbitrary"))]
+impl<'a> Arbitrary<'a> for SLP {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<SLP> {
+        arbitrary_slp(u, 32)
+    }
+}
+
+#[cfg(any(test, feature = "arbitrary"))]
+fn arbitrary_slp(u: &mut Unstructured, depth: usize) -> arbitrary::Result<SLP> {
+    if depth == 0 || u.is_empty() {
+        return Ok(SLP::Identity);
+    }
+    Ok(match u.int_in_range(0..=3u8)? {
+        0 => SLP::Identity,
+        1 => SLP::Generator(u.int_in_range(0..=15u64)?),
+        2 => SLP::Product(
+            Box::new(arbitrary_slp(u, depth - 1)?),
+            Box::new(arbitrary_slp(u, depth - 1)?),
+        ),
+        _ => SLP::Inverse(Box::new(arbitrary_slp(u, depth - 1)?)),
+    })
+}
+
 impl GroupElement for SLP {
+    fn identity() -> SLP {
+        SLP::Identity
+    }
+
     fn is_identity(&self) -> bool {
-        match *self {
-            SLP::Identity => true,
-            _ => false,
-        }
+        matches!(*self, SLP::Identity)
     }
 
     fn times(&self, multiplicant: &SLP) -> SLP {
@@ -332,6 +647,21 @@ mod tests {
         assert_eq!(inverse, expected);
     }
 
+    #[test]
+    fn should_evaluate_with_images() {
+        let mut images = HashMap::new();
+        images.insert(0u64, Word::generator('a'));
+        images.insert(1u64, Word::generator('b'));
+        let slp = SLP::Generator(0)
+            .times(&SLP::Generator(1).inverse())
+            .times(&SLP::Identity);
+
+        let word = slp.evaluate_with(&images);
+
+        let expected = Word::new(vec![('a', 1), ('b', -1)]);
+        assert_eq!(word, expected);
+    }
+
     #[test]
     fn should_display_correctly() {
         let identity = SLP::Identity;
@@ -344,4 +674,32 @@ mod tests {
         assert_eq!("(G_1) * (G_2)", format!("{}", product));
         assert_eq!("(G_1)^-1", format!("{}", inverse));
     }
+
+    #[test]
+    fn identity_words_should_be_neutral_for_any_collection() {
+        let factory = SyncSLPFactory::new();
+        let word = factory.generator(Word::generator('a'));
+        let identity = SyncSLPWord::identity();
+
+        assert!(identity.is_identity());
+        assert_eq!(identity.times(&word).evaluate(), Word::generator('a'));
+        assert_eq!(
+            word.times(&identity.inverse()).evaluate(),
+            Word::generator('a')
+        );
+    }
+
+    #[test]
+    fn arbitrary_slps_should_evaluate() {
+        let data: Vec<u8> = (0..4096).map(|n| (n * 7 % 251) as u8).collect();
+        let mut u = Unstructured::new(&data);
+        let images: HashMap<u64, Word<u64>> = (0..16).map(|n| (n, Word::generator(n))).collect();
+
+        while !u.is_empty() {
+            let slp = SLP::arbitrary(&mut u).unwrap();
+
+            let word = slp.evaluate_with(&images);
+            assert_eq!(slp.inverse().evaluate_with(&images), word.inverse());
+        }
+    }
 }
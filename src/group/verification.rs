@@ -0,0 +1,152 @@
+//! Verify that a stabilizer chain is complete.
+//!
+//! A base and strong generating set describe the group they generate only
+//! when the strong generators of every level fix the base points before it,
+//! and every Schreier generator of a level sifts through the levels after it.
+//! `Group::verify` checks both conditions. It is a safety net for chains that
+//! are not built by the deterministic Schreier-Sims algorithm, e.g. with
+//! random elements.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # fn main() {
+//! let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let group = Group::with_base(vec![0, 1, 2, 3], vec![], vec![transposition, rotation]);
+//!
+//! assert_eq!(group.verify(), Ok(()));
+//! # }
+//! ```
+
+use super::{non_sifting_schreier_generator, Group, GroupAction, GroupElement};
+use std::error;
+use std::fmt::{self, Display, Formatter};
+use std::hash::Hash;
+
+impl<Domain, G> Group<Domain, G>
+where
+    Domain: Eq + Hash + Clone,
+    G: GroupElement + GroupAction<Domain = Domain> + PartialEq + Clone,
+{
+    /// Check that the stabilizer chain of this group is complete, reporting
+    /// the first level that is not.
+    ///
+    /// Every Schreier generator of every level is sifted, so this takes about
+    /// as long as the construction itself.
+    pub fn verify(&self) -> Result<(), IncompleteChain> {
+        for (index, level) in self.levels.iter().enumerate() {
+            let moves_base = level.orbit.generators.iter().any(|g| {
+                self.levels[0..index]
+                    .iter()
+                    .any(|earlier| g.act_on(&earlier.orbit.base) != earlier.orbit.base)
+            });
+            if moves_base {
+                return Err(IncompleteChain::NotStabilizing { level: index });
+            }
+        }
+        for index in (0..self.levels.len()).rev() {
+            if let Some((_, depth)) =
+                non_sifting_schreier_generator(&self.gset, &self.levels, index)
+            {
+                return Err(IncompleteChain::NonSiftingSchreierGenerator {
+                    level: index,
+                    depth,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The ways a stabilizer chain can be incomplete.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IncompleteChain {
+    /// A strong generator of `level` moves the base point of an earlier level.
+    NotStabilizing {
+        /// The index of the offending level.
+        level: usize,
+    },
+    /// A Schreier generator of `level` does not sift. Its residue stops at
+    /// `depth`, which equals the number of levels when the base is too short.
+    NonSiftingSchreierGenerator {
+        /// The index of the offending level.
+        level: usize,
+        /// The index of the level the residue has no transversal for.
+        depth: usize,
+    },
+}
+
+impl Display for IncompleteChain {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            IncompleteChain::NotStabilizing { level } => write!(
+                f,
+                "a strong generator of level {} moves an earlier base point",
+                level
+            ),
+            IncompleteChain::NonSiftingSchreierGenerator { level, depth } => write!(
+                f,
+                "a Schreier generator of level {} does not sift beyond level {}",
+                level, depth
+            ),
+        }
+    }
+}
+
+impl error::Error for IncompleteChain {}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::models;
+    use super::super::permutation::Permutation;
+    use super::super::BaseStrongGeneratorLevel;
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn constructed_groups_should_verify() {
+        let puzzle = models::cube2();
+        let group = Group::with_base(puzzle.gset(), vec![], puzzle.generators());
+
+        assert_eq!(group.verify(), Ok(()));
+    }
+
+    #[test]
+    fn missing_levels_should_be_reported() {
+        let transposition = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+        let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+        let group = Group {
+            gset: vec![0, 1, 2, 3],
+            levels: vec![BaseStrongGeneratorLevel::with_generators(
+                0,
+                vec![transposition, rotation],
+            )],
+        };
+
+        assert_eq!(
+            group.verify(),
+            Err(IncompleteChain::NonSiftingSchreierGenerator { level: 0, depth: 1 })
+        );
+    }
+
+    #[test]
+    fn generators_that_move_earlier_base_points_should_be_reported() {
+        let rotation = permute!(0, 1, 1, 2, 2, 0);
+        let group = Group {
+            gset: vec![0, 1, 2],
+            levels: vec![
+                BaseStrongGeneratorLevel::with_generators(0, vec![rotation.clone()]),
+                BaseStrongGeneratorLevel::with_generators(1, vec![rotation]),
+            ],
+        };
+
+        assert_eq!(
+            group.verify(),
+            Err(IncompleteChain::NotStabilizing { level: 1 })
+        );
+    }
+}
@@ -3,4 +3,27 @@
 //!
 //! This implements the [Schreier-Sims algorithm](https://en.wikipedia.org/wiki/Schreier%E2%80%93Sims_algorithm).
 
+#[cfg(any(test, feature = "arbitrary"))]
+extern crate arbitrary;
+#[cfg(feature = "memmap2")]
+extern crate memmap2;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+extern crate num_bigint;
+#[cfg(feature = "permutation")]
+extern crate permutation;
+#[cfg(any(test, feature = "proptest"))]
+extern crate proptest;
+#[cfg(feature = "parallel")]
+extern crate rayon;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "wasm")]
+extern crate wasm_bindgen;
+
+#[macro_use]
 pub mod group;
+pub mod puzzle;
+pub mod solver;
+#[cfg(feature = "wasm")]
+pub mod wasm;
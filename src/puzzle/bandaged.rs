@@ -0,0 +1,276 @@
+//! Bandaged puzzles, whose moves are blocked in some states.
+//!
+//! A bandaged puzzle has pieces that are glued together. A move that would
+//! split them is blocked, until other moves bring the glued pieces into a
+//! position where the move turns both or neither of them. The legal moves
+//! depend on the state, so the moves no longer form a group: every move is a
+//! `PartialGroupAction` on the states of the puzzle.
+//!
+//! Here the glue is a bond between two stickers. A move is blocked in a state
+//! when it moves the position of one sticker of a bond, but not the position of
+//! the other. The solver searches the states the legal moves reach with IDA*,
+//! and still uses the group of the unbandaged puzzle where it can: a state
+//! outside of that group is rejected without a search, and pattern databases
+//! built with the unbandaged moves estimate the distance, because bandaging
+//! only takes moves away.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::group::GroupElement;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # use permutation_rs::puzzle::models;
+//! # use permutation_rs::puzzle::bandaged::Bandaged;
+//! let puzzle = models::cube2();
+//! let bandaged = Bandaged::new(puzzle.clone(), &[("U3", "U8")]).unwrap();
+//! let turn = |name: &str| puzzle.move_named(name).unwrap().clone();
+//!
+//! assert!(bandaged.is_legal(&Permutation::identity(), &turn("R")));
+//! assert!(!bandaged.is_legal(&Permutation::identity(), &turn("F")));
+//!
+//! let r2 = turn("R").times(&turn("R"));
+//! let state = r2.times(&turn("U"));
+//! let solution = bandaged.solve(&state).unwrap();
+//!
+//! assert_eq!(solution.to_string(), "U^-1R^2");
+//! ```
+
+use super::super::group::free::Word;
+use super::super::group::permutation::Permutation;
+use super::super::group::{Group, GroupAction, GroupElement, PartialGroupAction};
+use super::super::solver::pattern::{self, PatternDb};
+use super::super::solver::search::{ida_star, Problem};
+use super::super::solver::{face_turns, Move};
+use super::{Puzzle, PuzzleError};
+
+/// A puzzle with bonds between stickers.
+pub struct Bandaged {
+    puzzle: Puzzle,
+    bonds: Vec<(u64, u64)>,
+    group: Group<u64, Permutation>,
+    moves: Vec<BandagedMove>,
+    databases: Vec<PatternDb>,
+    max_depth: usize,
+}
+
+impl Bandaged {
+    /// Bandage `puzzle` with a bond between the stickers of every pair of
+    /// labels in `bonds`. The moves are the face turns of `puzzle`.
+    ///
+    /// Fails when a label is not a sticker of `puzzle`.
+    pub fn new(puzzle: Puzzle, bonds: &[(&str, &str)]) -> Result<Bandaged, PuzzleError> {
+        let sticker = |label: &str| {
+            puzzle
+                .sticker(label)
+                .ok_or_else(|| PuzzleError::invalid(&format!("unknown sticker '{}'", label)))
+        };
+        let bonds = bonds
+            .iter()
+            .map(|(a, b)| Ok((sticker(a)?, sticker(b)?)))
+            .collect::<Result<Vec<(u64, u64)>, PuzzleError>>()?;
+        let moves = face_turns(&puzzle)
+            .into_iter()
+            .map(|turn| BandagedMove {
+                turn,
+                bonds: bonds.clone(),
+            })
+            .collect();
+        let group = Group::with_base(puzzle.gset(), vec![], puzzle.generators());
+        Ok(Bandaged {
+            puzzle,
+            bonds,
+            group,
+            moves,
+            databases: vec![],
+            max_depth: 20,
+        })
+    }
+
+    /// Guide the search with `databases`, built with the face turns of the
+    /// unbandaged puzzle.
+    pub fn with_pattern_dbs(mut self, databases: Vec<PatternDb>) -> Bandaged {
+        self.databases = databases;
+        self
+    }
+
+    /// Limit the number of moves of a solution. Defaults to 20.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Bandaged {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// The unbandaged puzzle.
+    pub fn puzzle(&self) -> &Puzzle {
+        &self.puzzle
+    }
+
+    /// The face turns, each blocked in the states where it splits a bond.
+    pub fn moves(&self) -> &[BandagedMove] {
+        &self.moves
+    }
+
+    /// Determine if `permutation` can be performed in `state`, i.e. if it does
+    /// not split a bond.
+    pub fn is_legal(&self, state: &Permutation, permutation: &Permutation) -> bool {
+        splits_no_bond(&self.bonds, state, permutation)
+    }
+
+    /// The moves that are legal in `state`.
+    pub fn legal_moves(&self, state: &Permutation) -> Vec<&BandagedMove> {
+        self.moves
+            .iter()
+            .filter(|m| m.try_act_on(state).is_some())
+            .collect()
+    }
+
+    /// A shortest sequence of legal moves that solves `state`, or `None` when
+    /// `state` is not a state of the unbandaged puzzle or there is no solution
+    /// within the maximum depth.
+    pub fn solve(&self, state: &Permutation) -> Option<Word<String>> {
+        if !self.group.contains(state) {
+            return None;
+        }
+        let search = Search {
+            moves: &self.moves,
+            databases: &self.databases,
+        };
+        let path = ida_star(&search, state, self.max_depth)?;
+        Some(path.iter().fold(Word::identity(), |word, index| {
+            word.times(&self.moves[*index].turn.word)
+        }))
+    }
+}
+
+/// A face turn of a bandaged puzzle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandagedMove {
+    turn: Move,
+    bonds: Vec<(u64, u64)>,
+}
+
+impl BandagedMove {
+    /// The face turn, regardless of bonds.
+    pub fn turn(&self) -> &Move {
+        &self.turn
+    }
+}
+
+impl PartialGroupAction for BandagedMove {
+    type Domain = Permutation;
+
+    /// The state after this move, or `None` when it splits a bond in `state`.
+    fn try_act_on(&self, state: &Permutation) -> Option<Permutation> {
+        if splits_no_bond(&self.bonds, state, &self.turn.permutation) {
+            Some(state.times(&self.turn.permutation))
+        } else {
+            None
+        }
+    }
+}
+
+/// Determine if `permutation` moves either both or neither of the positions
+/// the stickers of each bond are at in `state`.
+fn splits_no_bond(bonds: &[(u64, u64)], state: &Permutation, permutation: &Permutation) -> bool {
+    let moves = |sticker: &u64| {
+        let position = state.act_on(sticker);
+        permutation.act_on(&position) != position
+    };
+    bonds.iter().all(|(a, b)| moves(a) == moves(b))
+}
+
+/// The search for legal moves that solve a state.
+struct Search<'a> {
+    moves: &'a [BandagedMove],
+    databases: &'a [PatternDb],
+}
+
+impl<'a> Problem for Search<'a> {
+    type State = Permutation;
+
+    fn moves(&self) -> usize {
+        self.moves.len()
+    }
+
+    fn apply(&self, state: &Permutation, index: usize) -> Permutation {
+        self.try_apply(state, index)
+            .expect("move should be legal in the state")
+    }
+
+    fn try_apply(&self, state: &Permutation, index: usize) -> Option<Permutation> {
+        self.moves[index].try_act_on(state)
+    }
+
+    fn is_goal(&self, state: &Permutation) -> bool {
+        state.is_identity()
+    }
+
+    fn estimate(&self, state: &Permutation) -> usize {
+        pattern::estimate(self.databases, state)
+    }
+
+    fn may_follow(&self, previous: usize, next: usize) -> bool {
+        !self.moves[next].turn.continues(&self.moves[previous].turn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::models;
+    use super::*;
+    use std::collections::HashMap;
+
+    fn bandaged() -> Bandaged {
+        Bandaged::new(models::cube2(), &[("U3", "U8")]).unwrap()
+    }
+
+    fn state(bandaged: &Bandaged, sequence: &str) -> Permutation {
+        let puzzle = bandaged.puzzle();
+        puzzle
+            .sequence(sequence)
+            .unwrap()
+            .permutation(puzzle)
+            .unwrap()
+    }
+
+    #[test]
+    fn legal_moves_should_depend_on_the_state() {
+        let bandaged = bandaged();
+        let names = |state: &Permutation| -> Vec<String> {
+            bandaged
+                .legal_moves(state)
+                .iter()
+                .map(|m| m.turn().word.to_string())
+                .collect()
+        };
+
+        assert!(!names(&Permutation::identity()).contains(&String::from("F^1")));
+        assert!(names(&state(&bandaged, "R")).contains(&String::from("F^1")));
+    }
+
+    #[test]
+    fn solutions_should_only_use_legal_moves() {
+        let bandaged = bandaged().with_max_depth(6);
+        let scramble = state(&bandaged, "R F R' U");
+
+        let solution = bandaged.solve(&scramble).unwrap();
+
+        let mut current = scramble;
+        for (name, exponent) in solution.iter() {
+            let m = bandaged
+                .moves()
+                .iter()
+                .find(|m| m.turn().word == Word::new(vec![(name.clone(), *exponent)]))
+                .unwrap();
+            current = m.try_act_on(&current).unwrap();
+        }
+        assert!(current.is_identity());
+        assert!(solution.iter().count() <= 4);
+    }
+
+    #[test]
+    fn states_outside_the_group_should_not_be_searched() {
+        let bandaged = bandaged();
+        let swap = permute!(0, 1, 1, 0);
+
+        assert_eq!(bandaged.solve(&swap), None);
+    }
+}
@@ -0,0 +1,149 @@
+//! States of a puzzle as the colors of its stickers.
+//!
+//! A sticker permutation tells where every sticker went, but a solver sitting
+//! in front of a puzzle only sees colors. Many puzzles have stickers that can
+//! not be told apart: the four centers of a face of a 4x4x4 cube, or the
+//! stickers of a single color on a void cube. A `State` labels every position
+//! with the color that is on it, so states that only differ in identical
+//! stickers are equal. The solved states form a coset of the subgroup that
+//! permutes identical stickers, not a single element.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::puzzle::models;
+//! # use permutation_rs::puzzle::colored::State;
+//! let puzzle = models::cube2();
+//! let solved = State::from_stickers(&puzzle, |label| label.chars().next().unwrap());
+//! let turn = puzzle.sequence("U2").unwrap().permutation(&puzzle).unwrap();
+//!
+//! let state = solved.apply(&turn);
+//!
+//! assert!(!state.is_solved_under(&solved));
+//! assert!(state.apply(&turn).is_solved_under(&solved));
+//! ```
+
+use super::super::group::permutation::Permutation;
+use super::super::group::GroupAction;
+use super::Puzzle;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The color on every position of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct State<C> {
+    colors: Vec<C>,
+}
+
+impl<C> State<C>
+where
+    C: Eq + Clone,
+{
+    /// The state with `colors[i]` on position `i`.
+    pub fn new(colors: Vec<C>) -> State<C> {
+        State { colors }
+    }
+
+    /// The solved state of `puzzle`, coloring every sticker by its label.
+    pub fn from_stickers<F>(puzzle: &Puzzle, color: F) -> State<C>
+    where
+        F: Fn(&str) -> C,
+    {
+        State::new(puzzle.stickers().iter().map(|label| color(label)).collect())
+    }
+
+    /// The colors of the positions, in order.
+    pub fn colors(&self) -> &[C] {
+        &self.colors
+    }
+
+    /// The color on `position`, if there is such a position.
+    pub fn color(&self, position: u64) -> Option<&C> {
+        self.colors.get(position as usize)
+    }
+
+    /// The state after `g`: the color on every position `p` moves to the
+    /// position `p` maps to.
+    ///
+    /// Panics when `g` maps a position outside of this state.
+    pub fn apply<G>(&self, g: &G) -> State<C>
+    where
+        G: GroupAction<Domain = u64>,
+    {
+        let mut colors = self.colors.clone();
+        for (position, color) in self.colors.iter().enumerate() {
+            colors[g.act_on(&(position as u64)) as usize] = color.clone();
+        }
+        State { colors }
+    }
+
+    /// Determine if this state looks like `solved`. Stickers of the same color
+    /// are indistinguishable, so they may have changed places.
+    pub fn is_solved_under(&self, solved: &State<C>) -> bool {
+        self.colors == solved.colors
+    }
+}
+
+impl<C> State<C>
+where
+    C: Eq + Hash + Clone,
+{
+    /// Generators of the group that permutes the positions of each color among
+    /// themselves, the permutations that leave this state unchanged.
+    pub fn symmetries(&self) -> Vec<Permutation> {
+        let mut classes: Vec<Vec<u64>> = vec![];
+        let mut index: HashMap<&C, usize> = HashMap::new();
+        for (position, color) in self.colors.iter().enumerate() {
+            let class = *index.entry(color).or_insert_with(|| {
+                classes.push(vec![]);
+                classes.len() - 1
+            });
+            classes[class].push(position as u64);
+        }
+        let mut generators = vec![];
+        for class in classes.iter().filter(|class| class.len() > 1) {
+            generators.push(Permutation::new(
+                vec![(class[0], class[1]), (class[1], class[0])]
+                    .into_iter()
+                    .collect(),
+            ));
+            if class.len() > 2 {
+                let cycle = (0..class.len())
+                    .map(|i| (class[i], class[(i + 1) % class.len()]))
+                    .collect();
+                generators.push(Permutation::new(cycle));
+            }
+        }
+        generators
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::group::Group;
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn identical_stickers_should_be_indistinguishable() {
+        let solved = State::new(vec!['a', 'a', 'b', 'b']);
+
+        assert!(solved.apply(&permute!(0, 1, 1, 0)).is_solved_under(&solved));
+        assert_eq!(
+            solved.apply(&permute!(1, 2, 2, 1)).colors(),
+            &['a', 'b', 'a', 'b']
+        );
+    }
+
+    #[test]
+    fn symmetries_should_permute_each_color() {
+        let solved = State::new(vec!['a', 'b', 'a', 'a', 'b', 'c']);
+
+        let symmetries = solved.symmetries();
+        let group = Group::with_base(vec![0, 1, 2, 3, 4, 5], vec![], symmetries.clone());
+
+        assert_eq!(group.order(), 12);
+        assert!(symmetries
+            .iter()
+            .all(|symmetry| solved.apply(symmetry) == solved));
+    }
+}
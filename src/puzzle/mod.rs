@@ -0,0 +1,600 @@
+//! Describe permutation puzzles in a small text format.
+//!
+//! A puzzle consists of labeled stickers and named moves. Each move is written
+//! in disjoint cycle notation over the sticker labels. From a `Puzzle` the
+//! generators, the gset and a `Morphism` to the move names follow
+//! automatically.
+//!
+//! # Format
+//! Every line contains a directive. Empty lines and lines starting with `#` are
+//! ignored.
+//!
+//! * `stickers: <label> <label> ...` lists the labels of the stickers. The
+//!   position of a label in this list is the domain element it corresponds with.
+//!   Labels should be distinct.
+//! * `move <name>: (<label> <label> ...)(...)` defines a move in disjoint cycle
+//!   notation. Every move should have its own name, which should not end in a
+//!   digit because sequences use digits as multipliers.
+//! * `inverse: <suffix>` sets the suffix that denotes inverse moves. Defaults to
+//!   `'`.
+//! * `direction: clockwise|counterclockwise` tells in which direction the
+//!   cycles of the following moves are written. Moves are always stored as
+//!   clockwise turns, so counterclockwise cycles are inverted. Defaults to
+//!   `clockwise`.
+//! * `commute: <name> <name> ...` declares that the named moves commute with
+//!   each other, e.g. the turns of opposite faces of a cube. The moves should
+//!   be defined before and should indeed commute.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::puzzle::Puzzle;
+//! let puzzle: Puzzle = "
+//!     stickers: a b c d e f
+//!     move T: (d f)
+//!     move R: (a b c d e f)
+//! "
+//! .parse()
+//! .unwrap();
+//!
+//! let group = puzzle.group();
+//!
+//! assert_eq!(group.order(), 72);
+//! ```
+
+pub mod bandaged;
+pub mod colored;
+pub mod models;
+pub mod oriented;
+pub mod sequence;
+pub mod sliding;
+
+pub use self::sequence::{Sequence, SequenceError};
+
+use super::group::free::{Notation, Word};
+use super::group::labeling::{Labeling, LabelingError};
+use super::group::peephole::MoveRules;
+use super::group::permutation::Permutation;
+use super::group::special::SLPPermutation;
+use super::group::tree::SLP;
+use super::group::{Group, GroupElement, Morphism};
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A permutation puzzle: labeled stickers and named moves that permute them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Puzzle {
+    stickers: Vec<String>,
+    moves: Vec<(String, Permutation)>,
+    commuting: Vec<(String, String)>,
+    inverse_suffix: String,
+}
+
+impl Puzzle {
+    /// Create a puzzle with the given sticker labels and no moves.
+    pub fn new(stickers: Vec<String>) -> Puzzle {
+        Puzzle {
+            stickers,
+            moves: vec![],
+            commuting: vec![],
+            inverse_suffix: String::from("'"),
+        }
+    }
+
+    /// Read a puzzle definition from a file.
+    pub fn load<P>(path: P) -> Result<Puzzle, PuzzleError>
+    where
+        P: AsRef<Path>,
+    {
+        let source = fs::read_to_string(path)?;
+        source.parse()
+    }
+
+    /// Add a move written in disjoint cycle notation over the sticker labels.
+    /// Fails when the cycles are malformed, when the name ends in a digit, or
+    /// when there already is a move with this name.
+    pub fn add_move(&mut self, name: &str, cycles: &str) -> Result<(), PuzzleError> {
+        self.parse_cycles(cycles)
+            .and_then(|permutation| self.declare_move(name, permutation))
+            .map_err(|message| PuzzleError::invalid(&message))
+    }
+
+    /// Declare that the moves named `left` and `right` commute. Fails when one
+    /// of them is not a move, or when they do not commute.
+    pub fn add_commuting(&mut self, left: &str, right: &str) -> Result<(), PuzzleError> {
+        self.declare_commuting(left, right)
+            .map_err(|message| PuzzleError::invalid(&message))
+    }
+
+    /// Set the suffix that denotes inverse moves.
+    pub fn set_inverse_suffix(&mut self, suffix: &str) {
+        self.inverse_suffix = suffix.to_string();
+    }
+
+    /// The suffix that denotes inverse moves.
+    pub fn inverse_suffix(&self) -> &str {
+        &self.inverse_suffix
+    }
+
+    /// The notation to write words over the move names of this puzzle, using
+    /// its inverse suffix.
+    pub fn notation(&self) -> Notation<String> {
+        Notation::new().with_inverse_suffix(&self.inverse_suffix)
+    }
+
+    /// Parse a sequence of moves of this puzzle, using its inverse suffix.
+    pub fn sequence(&self, text: &str) -> Result<Sequence, SequenceError> {
+        Sequence::parse_with(text, &self.inverse_suffix)
+    }
+
+    /// The labels of the stickers, in the order of their domain elements.
+    pub fn stickers(&self) -> &[String] {
+        &self.stickers
+    }
+
+    /// The domain element that corresponds with a sticker label.
+    pub fn sticker(&self, label: &str) -> Option<u64> {
+        self.stickers
+            .iter()
+            .position(|sticker| sticker == label)
+            .map(|index| index as u64)
+    }
+
+    /// The sticker labels as a `Labeling`, to translate permutations of this
+    /// puzzle to labels. Fails when a label occurs more than once.
+    pub fn labeling(&self) -> Result<Labeling<String>, LabelingError<String>> {
+        Labeling::new(self.stickers.clone())
+    }
+
+    /// The names of the moves, in order of definition.
+    pub fn move_names(&self) -> Vec<String> {
+        self.moves.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    /// The permutation of the move with the given name.
+    pub fn move_named(&self, name: &str) -> Option<&Permutation> {
+        self.moves
+            .iter()
+            .find(|(candidate, _)| candidate == name)
+            .map(|(_, permutation)| permutation)
+    }
+
+    /// The set the moves act upon.
+    pub fn gset(&self) -> Vec<u64> {
+        (0..self.stickers.len()).map(|index| index as u64).collect()
+    }
+
+    /// The permutations of the moves, in order of definition.
+    pub fn generators(&self) -> Vec<Permutation> {
+        self.moves
+            .iter()
+            .map(|(_, permutation)| permutation.clone())
+            .collect()
+    }
+
+    /// The permutations of the moves by name, e.g. to evaluate a word over the
+    /// move names.
+    pub fn move_images(&self) -> HashMap<String, Permutation> {
+        self.moves.iter().cloned().collect()
+    }
+
+    /// The moves as `SLPPermutation`s. Move `i` corresponds with
+    /// `SLP::Generator(i)`.
+    pub fn slp_generators(&self) -> Vec<SLPPermutation> {
+        self.moves
+            .iter()
+            .enumerate()
+            .map(|(index, (_, permutation))| {
+                SLPPermutation::new(SLP::Generator(index as u64), permutation.clone())
+            })
+            .collect()
+    }
+
+    /// The `Morphism` that maps the generators of `slp_generators` to the move
+    /// names.
+    pub fn morphism(&self) -> Morphism<SLP, Word<String>> {
+        let images: HashMap<SLP, Word<String>> = self
+            .moves
+            .iter()
+            .enumerate()
+            .map(|(index, (name, _))| (SLP::Generator(index as u64), Word::generator(name.clone())))
+            .collect();
+        Morphism::new(images)
+    }
+
+    /// The orders of the moves of this puzzle and the pairs of moves that
+    /// commute, to shorten sequences of its moves with `optimize_moves`.
+    ///
+    /// The commuting pairs are the declared ones. When none are declared,
+    /// every pair of moves that commute is used.
+    pub fn move_rules(&self) -> MoveRules<String> {
+        let mut rules = MoveRules::new();
+        for (name, permutation) in &self.moves {
+            rules = rules.with_order(name.clone(), permutation.order());
+        }
+        if !self.commuting.is_empty() {
+            for (left, right) in &self.commuting {
+                rules = rules.with_commuting(left.clone(), right.clone());
+            }
+            return rules;
+        }
+        for (index, (name, permutation)) in self.moves.iter().enumerate() {
+            for (other, image) in &self.moves[index + 1..] {
+                if permutation.times(image) == image.times(permutation) {
+                    rules = rules.with_commuting(name.clone(), other.clone());
+                }
+            }
+        }
+        rules
+    }
+
+    /// The group generated by the moves of this puzzle.
+    pub fn group(&self) -> Group<u64, SLPPermutation> {
+        Group::with_base(self.gset(), vec![], self.slp_generators())
+    }
+
+    fn declare_commuting(&mut self, left: &str, right: &str) -> Result<(), String> {
+        let permutations = [left, right]
+            .iter()
+            .map(|name| {
+                self.move_named(name)
+                    .ok_or_else(|| format!("unknown move '{}'", name))
+            })
+            .collect::<Result<Vec<&Permutation>, String>>()?;
+        if permutations[0].times(permutations[1]) != permutations[1].times(permutations[0]) {
+            return Err(format!("moves '{}' and '{}' do not commute", left, right));
+        }
+        self.commuting.push((left.to_string(), right.to_string()));
+        Ok(())
+    }
+
+    fn declare_move(&mut self, name: &str, permutation: Permutation) -> Result<(), String> {
+        if name.ends_with(|c: char| c.is_ascii_digit()) {
+            return Err(format!("move '{}' should not end in a digit", name));
+        }
+        if self.move_named(name).is_some() {
+            return Err(format!("move '{}' is already defined", name));
+        }
+        self.moves.push((name.to_string(), permutation));
+        Ok(())
+    }
+
+    fn parse_cycles(&self, cycles: &str) -> Result<Permutation, String> {
+        let mut images: HashMap<u64, u64> = self.gset().into_iter().map(|i| (i, i)).collect();
+        let mut seen: Vec<u64> = vec![];
+        let mut rest = cycles.trim();
+        while !rest.is_empty() {
+            if !rest.starts_with('(') {
+                return Err(String::from("expected '(' to start a cycle"));
+            }
+            let end = rest
+                .find(')')
+                .ok_or_else(|| String::from("expected ')' to end a cycle"))?;
+            let mut cycle: Vec<u64> = vec![];
+            for label in rest[1..end]
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|label| !label.is_empty())
+            {
+                let point = self
+                    .sticker(label)
+                    .ok_or_else(|| format!("unknown sticker '{}'", label))?;
+                if seen.contains(&point) {
+                    return Err(format!("sticker '{}' occurs more than once", label));
+                }
+                seen.push(point);
+                cycle.push(point);
+            }
+            for (index, point) in cycle.iter().enumerate() {
+                images.insert(*point, cycle[(index + 1) % cycle.len()]);
+            }
+            rest = rest[(end + 1)..].trim_start();
+        }
+        Ok(Permutation::new(images))
+    }
+}
+
+impl FromStr for Puzzle {
+    type Err = PuzzleError;
+
+    fn from_str(source: &str) -> Result<Puzzle, PuzzleError> {
+        let mut puzzle: Option<Puzzle> = None;
+        let mut inverse_suffix = String::from("'");
+        let mut clockwise = true;
+        for (index, raw) in source.lines().enumerate() {
+            let line = index + 1;
+            let content = raw.trim();
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+            let colon = content
+                .find(':')
+                .ok_or_else(|| PuzzleError::parse(line, "expected ':' after directive"))?;
+            let directive = content[0..colon].trim();
+            let argument = content[(colon + 1)..].trim();
+            if directive == "stickers" {
+                if puzzle.is_some() {
+                    return Err(PuzzleError::parse(line, "stickers are already defined"));
+                }
+                let stickers: Vec<String> = argument.split_whitespace().map(String::from).collect();
+                if let Some((_, label)) = stickers
+                    .iter()
+                    .enumerate()
+                    .find(|(index, label)| stickers[..*index].contains(label))
+                {
+                    return Err(PuzzleError::parse(
+                        line,
+                        &format!("sticker '{}' is defined more than once", label),
+                    ));
+                }
+                puzzle = Some(Puzzle::new(stickers));
+            } else if directive == "inverse" {
+                inverse_suffix = argument.to_string();
+            } else if directive == "direction" {
+                clockwise = match argument {
+                    "clockwise" => true,
+                    "counterclockwise" => false,
+                    _ => {
+                        return Err(PuzzleError::parse(
+                            line,
+                            &format!("unknown direction '{}'", argument),
+                        ))
+                    }
+                };
+            } else if let Some(name) = directive.strip_prefix("move ") {
+                let current = puzzle
+                    .as_mut()
+                    .ok_or_else(|| PuzzleError::parse(line, "stickers should come first"))?;
+                let permutation = current
+                    .parse_cycles(argument)
+                    .map_err(|message| PuzzleError::parse(line, &message))?;
+                let permutation = if clockwise {
+                    permutation
+                } else {
+                    permutation.inverse()
+                };
+                current
+                    .declare_move(name.trim(), permutation)
+                    .map_err(|message| PuzzleError::parse(line, &message))?;
+            } else if directive == "commute" {
+                let current = puzzle
+                    .as_mut()
+                    .ok_or_else(|| PuzzleError::parse(line, "stickers should come first"))?;
+                let names: Vec<&str> = argument.split_whitespace().collect();
+                if names.len() < 2 {
+                    return Err(PuzzleError::parse(line, "expected at least two moves"));
+                }
+                for (index, left) in names.iter().enumerate() {
+                    for right in &names[index + 1..] {
+                        current
+                            .declare_commuting(left, right)
+                            .map_err(|message| PuzzleError::parse(line, &message))?;
+                    }
+                }
+            } else {
+                return Err(PuzzleError::parse(
+                    line,
+                    &format!("unknown directive '{}'", directive),
+                ));
+            }
+        }
+        let mut puzzle = puzzle.ok_or_else(|| PuzzleError::invalid("no stickers defined"))?;
+        puzzle.inverse_suffix = inverse_suffix;
+        Ok(puzzle)
+    }
+}
+
+/// The ways reading a puzzle definition can fail.
+#[derive(Debug)]
+pub enum PuzzleError {
+    /// The definition could not be read.
+    Io(io::Error),
+    /// The definition is malformed at the given line.
+    Parse {
+        /// The line, starting at 1, of the offending directive.
+        line: usize,
+        /// A description of the problem.
+        message: String,
+    },
+    /// The definition is not tied to a line, and is invalid, e.g. because it
+    /// refers to a sticker or a move that does not exist.
+    Invalid(String),
+}
+
+impl PuzzleError {
+    fn parse(line: usize, message: &str) -> PuzzleError {
+        PuzzleError::Parse {
+            line,
+            message: message.to_string(),
+        }
+    }
+
+    fn invalid(message: &str) -> PuzzleError {
+        PuzzleError::Invalid(message.to_string())
+    }
+}
+
+impl From<io::Error> for PuzzleError {
+    fn from(error: io::Error) -> PuzzleError {
+        PuzzleError::Io(error)
+    }
+}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PuzzleError::Io(ref error) => write!(f, "could not read puzzle: {}", error),
+            PuzzleError::Parse { line, ref message } => write!(f, "line {}: {}", line, message),
+            PuzzleError::Invalid(ref message) => write!(f, "invalid puzzle: {}", message),
+        }
+    }
+}
+
+impl error::Error for PuzzleError {}
+
+#[cfg(test)]
+mod tests {
+    use super::super::group::peephole::optimize_moves;
+    use super::super::group::GroupAction;
+    use super::*;
+
+    const BRAINBOW: &str = "
+        # The brainbow
+        stickers: a b c d e f
+        move T: (d f)
+        move R: (a b c d e f)
+    ";
+
+    #[test]
+    fn puzzle_should_parse_moves() {
+        let puzzle: Puzzle = BRAINBOW.parse().unwrap();
+
+        assert_eq!(puzzle.gset(), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(puzzle.move_names(), vec!["T", "R"]);
+        let t = puzzle.move_named("T").unwrap();
+        assert_eq!(t.act_on(&3), 5);
+        assert_eq!(t.act_on(&5), 3);
+        assert_eq!(t.act_on(&0), 0);
+        assert_eq!(puzzle.move_named("R").unwrap().act_on(&5), 0);
+    }
+
+    #[test]
+    fn puzzle_should_respect_direction() {
+        let puzzle: Puzzle = "
+            stickers: a b c
+            direction: counterclockwise
+            move R: (a b c)
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(puzzle.move_named("R").unwrap().act_on(&0), 2);
+    }
+
+    #[test]
+    fn puzzle_should_report_unknown_stickers() {
+        let result: Result<Puzzle, PuzzleError> = "
+            stickers: a b c
+            move R: (a x)
+        "
+        .parse();
+
+        match result {
+            Err(PuzzleError::Parse { line, .. }) => assert_eq!(line, 3),
+            _ => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn puzzle_should_report_duplicate_definitions() {
+        let stickers: Result<Puzzle, PuzzleError> = "
+            stickers: a b a
+        "
+        .parse();
+        let moves: Result<Puzzle, PuzzleError> = "
+            stickers: a b c
+            move R: (a b)
+            move R: (b c)
+        "
+        .parse();
+        let mut puzzle: Puzzle = BRAINBOW.parse().unwrap();
+
+        assert!(matches!(stickers, Err(PuzzleError::Parse { line: 2, .. })));
+        assert!(matches!(moves, Err(PuzzleError::Parse { line: 4, .. })));
+        assert!(matches!(
+            puzzle.add_move("T", "(a b)"),
+            Err(PuzzleError::Invalid(_))
+        ));
+        assert_eq!(puzzle.move_names(), vec!["T", "R"]);
+    }
+
+    #[test]
+    fn puzzle_should_reject_move_names_ending_in_a_digit() {
+        let result: Result<Puzzle, PuzzleError> = "
+            stickers: a b c
+            move R2: (a b)
+        "
+        .parse();
+        let mut puzzle: Puzzle = BRAINBOW.parse().unwrap();
+
+        assert!(matches!(result, Err(PuzzleError::Parse { line: 3, .. })));
+        assert!(matches!(
+            puzzle.add_move("T2", "(a b)"),
+            Err(PuzzleError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn puzzle_should_map_generators_to_move_names() {
+        let puzzle: Puzzle = BRAINBOW.parse().unwrap();
+
+        let morphism = puzzle.morphism();
+
+        assert_eq!(
+            morphism.transform(&SLP::Generator(1)),
+            Word::generator(String::from("R"))
+        );
+    }
+
+    #[test]
+    fn puzzle_should_derive_its_move_rules() {
+        let puzzle = models::cube3();
+
+        let rules = puzzle.move_rules();
+
+        assert_eq!(rules.order(&String::from("U")), Some(4));
+        assert!(rules.commute(&String::from("U"), &String::from("D")));
+        assert!(!rules.commute(&String::from("U"), &String::from("R")));
+        let sequence = puzzle.sequence("U D U R R' D2 U").unwrap().to_word();
+        assert_eq!(
+            optimize_moves(&sequence, &rules),
+            puzzle.sequence("U' D'").unwrap().to_word()
+        );
+    }
+
+    #[test]
+    fn puzzle_should_check_declared_commuting_moves() {
+        let definition = "
+            stickers: a b c d e
+            move X: (a b)
+            move Y: (c d)
+            move Z: (d e)
+        ";
+        let declared: Puzzle = format!("{}commute: X Y", definition).parse().unwrap();
+        let wrong: Result<Puzzle, PuzzleError> = format!("{}commute: X Y Z", definition).parse();
+        let mut puzzle: Puzzle = definition.parse().unwrap();
+
+        let rules = declared.move_rules();
+
+        assert!(rules.commute(&String::from("X"), &String::from("Y")));
+        assert!(!rules.commute(&String::from("X"), &String::from("Z")));
+        assert!(puzzle
+            .move_rules()
+            .commute(&String::from("X"), &String::from("Z")));
+        assert!(matches!(wrong, Err(PuzzleError::Parse { line: 6, .. })));
+        assert!(matches!(
+            puzzle.add_commuting("Y", "Z"),
+            Err(PuzzleError::Invalid(_))
+        ));
+        assert!(matches!(
+            puzzle.add_commuting("X", "W"),
+            Err(PuzzleError::Invalid(_))
+        ));
+        assert!(matches!(
+            puzzle.add_move("W", "(a q)"),
+            Err(PuzzleError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn puzzle_should_label_its_moves() {
+        let puzzle: Puzzle = BRAINBOW.parse().unwrap();
+
+        let labeling = puzzle.labeling().unwrap();
+
+        assert_eq!(labeling.format(puzzle.move_named("T").unwrap()), "(d f)");
+    }
+}
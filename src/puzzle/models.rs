@@ -0,0 +1,465 @@
+//! Ready-made definitions of well known puzzles.
+//!
+//! Each function returns a `Puzzle` with the moves of that puzzle. The stickers
+//! are labeled by the face they are on. `cube`, `megaminx` and `kilominx`
+//! compute their moves from the geometry of the puzzle, because writing down
+//! the hundreds of sticker images of a big puzzle by hand is not feasible. The
+//! order of their groups exceeds a `u128`, see `Group::big_order`.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::puzzle::models;
+//! let puzzle = models::cube2();
+//!
+//! assert_eq!(puzzle.move_names(), vec!["U", "R", "F"]);
+//! ```
+
+use super::super::group::permutation::Permutation;
+use super::Puzzle;
+use std::collections::HashMap;
+
+/// The 3x3x3 cube with its six face turns.
+///
+/// The 48 moving stickers are labeled by face and a number from 1 to 8, in
+/// reading order with the center skipped.
+pub fn cube3() -> Puzzle {
+    CUBE3.parse().expect("cube3 definition should be valid")
+}
+
+/// The 2x2x2 cube.
+///
+/// Only the `U`, `R` and `F` turns are defined, so the `DBL` corner stays in
+/// place. The stickers are labeled like the corresponding stickers of `cube3`.
+pub fn cube2() -> Puzzle {
+    CUBE2.parse().expect("cube2 definition should be valid")
+}
+
+/// The Pyraminx.
+///
+/// The upper case moves `U`, `R`, `L` and `B` turn a vertex together with the
+/// layer below it, the lower case moves turn only the tip. The stickers are
+/// labeled by the face opposite to a vertex.
+pub fn pyraminx() -> Puzzle {
+    PYRAMINX
+        .parse()
+        .expect("pyraminx definition should be valid")
+}
+
+/// The Skewb.
+///
+/// The moves follow the usual notation: `R`, `U`, `L` and `B` turn the half of
+/// the puzzle around the `DRB`, `UBL`, `DLF` and `DBL` corner respectively. The
+/// `UFR` corner is never moved, which fixes the orientation of the puzzle. A
+/// center sticker is labeled by its face, a corner sticker by its face followed
+/// by the other two faces of its corner.
+pub fn skewb() -> Puzzle {
+    SKEWB.parse().expect("skewb definition should be valid")
+}
+
+/// The NxNxN cube, generated from its geometry.
+///
+/// The stickers are labeled by face and a number from 1 to N², in reading order
+/// on the same net as `cube3`. For odd N the center of a face never moves and
+/// is skipped, so `cube(3)` is `cube3()`. Besides the face turns `U`, `D`, `L`,
+/// `R`, `F` and `B` there is a move for every inner layer that is not a middle
+/// slice: `2U` turns the second layer from the top, in the same direction as
+/// `U`. The turns of layers around the same axis are declared to commute.
+///
+/// Panics when `n` is less than 2.
+pub fn cube(n: usize) -> Puzzle {
+    assert!(n >= 2, "a cube has at least two layers");
+    let faces = [
+        ("U", [0, 1, 0], [0, 0, 1], [1, 0, 0]),
+        ("L", [-1, 0, 0], [0, -1, 0], [0, 0, 1]),
+        ("F", [0, 0, 1], [0, -1, 0], [1, 0, 0]),
+        ("R", [1, 0, 0], [0, -1, 0], [0, 0, -1]),
+        ("B", [0, 0, -1], [0, -1, 0], [-1, 0, 0]),
+        ("D", [0, -1, 0], [0, 0, -1], [1, 0, 0]),
+    ];
+    let extent = n as i64 - 1;
+    let coordinate = |i: usize| 2 * i as i64 - extent;
+    let mut labels = vec![];
+    let mut stickers = vec![];
+    for (name, normal, down, right) in faces.iter() {
+        for row in 0..n {
+            for column in 0..n {
+                if n % 2 == 1 && row == n / 2 && column == n / 2 {
+                    continue;
+                }
+                let position = add(
+                    scale(*normal, extent),
+                    add(
+                        scale(*down, coordinate(row)),
+                        scale(*right, coordinate(column)),
+                    ),
+                );
+                labels.push(format!("{}{}", name, labels.len() % (n * n - n % 2) + 1));
+                stickers.push((position, *normal));
+            }
+        }
+    }
+    let mut puzzle = Puzzle::new(labels);
+    for name in ["U", "D", "L", "R", "F", "B"].iter() {
+        let (_, axis, _, _) = faces
+            .iter()
+            .find(|face| face.0 == *name)
+            .expect("face exists");
+        for depth in 1..=n / 2 {
+            let layer = extent - 2 * (depth as i64 - 1);
+            let images: Vec<usize> = stickers
+                .iter()
+                .enumerate()
+                .map(|(index, (position, normal))| {
+                    if dot(*position, *axis) != layer {
+                        return index;
+                    }
+                    let image = (quarter_turn(*axis, *position), quarter_turn(*axis, *normal));
+                    stickers
+                        .iter()
+                        .position(|sticker| *sticker == image)
+                        .expect("turned sticker exists")
+                })
+                .collect();
+            let name = if depth == 1 {
+                name.to_string()
+            } else {
+                format!("{}{}", depth, name)
+            };
+            add_generated_move(&mut puzzle, &name, &images);
+        }
+    }
+    for axis in [["U", "D"], ["L", "R"], ["F", "B"]].iter() {
+        let layers: Vec<String> = puzzle
+            .move_names()
+            .into_iter()
+            .filter(|name| axis.iter().any(|face| name.ends_with(face)))
+            .collect();
+        for (index, left) in layers.iter().enumerate() {
+            for right in &layers[index + 1..] {
+                puzzle
+                    .add_commuting(left, right)
+                    .expect("layers around an axis commute");
+            }
+        }
+    }
+    puzzle
+}
+
+/// The megaminx, generated from the geometry of the dodecahedron.
+///
+/// The faces are `U` on top, `F`, `R`, `BR`, `BL` and `L` around it, `DR`,
+/// `DBR`, `B`, `DBL` and `DL` below those, and `D` at the bottom. The ten
+/// stickers around the center of a face are labeled by the face and a number
+/// from 1 to 10, clockwise. They start at the edge or corner towards `U`, or
+/// towards `F` for `U` and `D`. The centers never move and are left out.
+pub fn megaminx() -> Puzzle {
+    dodecahedron(true)
+}
+
+/// The kilominx, the corners of the megaminx.
+///
+/// Faces and stickers are labeled like those of `megaminx`, without the edges,
+/// so the stickers of a face are numbered 1 to 5.
+pub fn kilominx() -> Puzzle {
+    dodecahedron(false)
+}
+
+type Vector = [i64; 3];
+
+fn add(u: Vector, v: Vector) -> Vector {
+    [u[0] + v[0], u[1] + v[1], u[2] + v[2]]
+}
+
+fn scale(v: Vector, factor: i64) -> Vector {
+    [v[0] * factor, v[1] * factor, v[2] * factor]
+}
+
+fn dot(u: Vector, v: Vector) -> i64 {
+    u[0] * v[0] + u[1] * v[1] + u[2] * v[2]
+}
+
+/// Turn `v` a quarter clockwise, as seen from the end of the unit vector
+/// `axis`.
+fn quarter_turn(axis: Vector, v: Vector) -> Vector {
+    let cross = [
+        axis[1] * v[2] - axis[2] * v[1],
+        axis[2] * v[0] - axis[0] * v[2],
+        axis[0] * v[1] - axis[1] * v[0],
+    ];
+    add(scale(axis, dot(axis, v)), scale(cross, -1))
+}
+
+/// Add the move `name` that maps sticker `i` to `images[i]`.
+fn add_generated_move(puzzle: &mut Puzzle, name: &str, images: &[usize]) {
+    let images = images
+        .iter()
+        .enumerate()
+        .map(|(index, image)| (index as u64, *image as u64))
+        .collect();
+    let cycles: String = Permutation::new(images)
+        .cycles()
+        .iter()
+        .map(|cycle| {
+            let labels: Vec<&str> = cycle
+                .iter()
+                .map(|index| puzzle.stickers()[*index as usize].as_str())
+                .collect();
+            format!("({})", labels.join(" "))
+        })
+        .collect();
+    puzzle
+        .add_move(name, &cycles)
+        .expect("generated move should be valid");
+}
+
+type Point = [f64; 3];
+
+fn normalized(v: Point) -> Point {
+    let length = dot_f(v, v).sqrt();
+    [v[0] / length, v[1] / length, v[2] / length]
+}
+
+fn dot_f(u: Point, v: Point) -> f64 {
+    u[0] * v[0] + u[1] * v[1] + u[2] * v[2]
+}
+
+fn cross_f(u: Point, v: Point) -> Point {
+    [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ]
+}
+
+/// Rotate `v` clockwise by `angle` radians, as seen from the end of the unit
+/// vector `axis`.
+fn rotate(axis: Point, v: Point, angle: f64) -> Point {
+    let (sin, cos) = (-angle).sin_cos();
+    let along = dot_f(axis, v) * (1.0 - cos);
+    let cross = cross_f(axis, v);
+    [
+        v[0] * cos + cross[0] * sin + axis[0] * along,
+        v[1] * cos + cross[1] * sin + axis[1] * along,
+        v[2] * cos + cross[2] * sin + axis[2] * along,
+    ]
+}
+
+/// The direction of `v`, rounded so that equal directions compare equal.
+fn key(v: Point) -> [i64; 3] {
+    let v = normalized(v);
+    [
+        (v[0] * 1e6).round() as i64,
+        (v[1] * 1e6).round() as i64,
+        (v[2] * 1e6).round() as i64,
+    ]
+}
+
+/// A puzzle with a face turn for every face of the dodecahedron, with corner
+/// stickers and, if `edges`, edge stickers.
+fn dodecahedron(edges: bool) -> Puzzle {
+    let phi = (1.0 + 5f64.sqrt()) / 2.0;
+    let mut normals: Vec<Point> = vec![];
+    for &a in [-1.0, 1.0].iter() {
+        for &b in [-phi, phi].iter() {
+            normals.push(normalized([0.0, a, b]));
+            normals.push(normalized([a, b, 0.0]));
+            normals.push(normalized([b, 0.0, a]));
+        }
+    }
+    let adjacent = |i: usize, j: usize| i != j && dot_f(normals[i], normals[j]) > 0.4;
+    let up = normals[1];
+    let f = (0..12).find(|&j| adjacent(1, j)).expect("U has neighbours");
+    let front = normalized(cross_f(cross_f(up, normals[f]), up));
+    let right = cross_f(up, front);
+    let names = (0..12).map(|i| {
+        let height = dot_f(normals[i], up);
+        let angle = dot_f(normals[i], right)
+            .atan2(dot_f(normals[i], front))
+            .to_degrees()
+            .round() as i64;
+        let name = if height > 0.9 {
+            "U"
+        } else if height < -0.9 {
+            "D"
+        } else if height > 0.0 {
+            match angle {
+                0 => "F",
+                72 => "R",
+                144 => "BR",
+                -144 => "BL",
+                _ => "L",
+            }
+        } else {
+            match angle {
+                36 => "DR",
+                108 => "DBR",
+                -108 => "DBL",
+                -36 => "DL",
+                _ => "B",
+            }
+        };
+        (name, i)
+    });
+    let order = [
+        "U", "F", "R", "BR", "BL", "L", "DR", "DBR", "B", "DBL", "DL", "D",
+    ];
+    let mut faces: Vec<(&str, usize)> = names.collect();
+    faces.sort_by_key(|(name, _)| order.iter().position(|candidate| candidate == name));
+    let mut labels = vec![];
+    let mut stickers: Vec<(Point, Point)> = vec![];
+    for (name, i) in faces.iter() {
+        let normal = normals[*i];
+        let mut points: Vec<Point> = vec![];
+        for j in (0..12).filter(|&j| adjacent(*i, j)) {
+            if edges {
+                points.push(normalized(add_f(normal, normals[j])));
+            }
+            for k in (j + 1..12).filter(|&k| adjacent(*i, k) && adjacent(j, k)) {
+                points.push(normalized(add_f(normal, add_f(normals[j], normals[k]))));
+            }
+        }
+        let towards = if *name == "U" || *name == "D" {
+            front
+        } else {
+            up
+        };
+        let reference = normalized(add_f(towards, scale_f(normal, -dot_f(towards, normal))));
+        let clockwise = |p: &Point| {
+            let angle = dot_f(cross_f(*p, reference), normal)
+                .atan2(dot_f(*p, reference) - dot_f(*p, normal) * dot_f(reference, normal));
+            let degrees = angle.to_degrees().round() as i64;
+            (degrees + 360) % 360
+        };
+        points.sort_by_key(|p| clockwise(p));
+        for (index, point) in points.into_iter().enumerate() {
+            labels.push(format!("{}{}", name, index + 1));
+            stickers.push((normal, point));
+        }
+    }
+    let positions: HashMap<([i64; 3], [i64; 3]), usize> = stickers
+        .iter()
+        .enumerate()
+        .map(|(index, (normal, point))| ((key(*normal), key(*point)), index))
+        .collect();
+    let mut puzzle = Puzzle::new(labels);
+    for (name, i) in faces.iter() {
+        let axis = normals[*i];
+        let angle = 72f64.to_radians();
+        let images: Vec<usize> = stickers
+            .iter()
+            .enumerate()
+            .map(|(index, (normal, point))| {
+                if dot_f(*point, axis) < 0.7 {
+                    return index;
+                }
+                let image = (
+                    key(rotate(axis, *normal, angle)),
+                    key(rotate(axis, *point, angle)),
+                );
+                positions[&image]
+            })
+            .collect();
+        add_generated_move(&mut puzzle, name, &images);
+    }
+    puzzle
+}
+
+fn add_f(u: Point, v: Point) -> Point {
+    [u[0] + v[0], u[1] + v[1], u[2] + v[2]]
+}
+
+fn scale_f(v: Point, factor: f64) -> Point {
+    [v[0] * factor, v[1] * factor, v[2] * factor]
+}
+
+const CUBE3: &str = "
+stickers: U1 U2 U3 U4 U5 U6 U7 U8 L1 L2 L3 L4 L5 L6 L7 L8 F1 F2 F3 F4 F5 F6 F7 F8 R1 R2 R3 R4 R5 R6 R7 R8 B1 B2 B3 B4 B5 B6 B7 B8 D1 D2 D3 D4 D5 D6 D7 D8
+move U: (U1 U3 U8 U6)(U2 U5 U7 U4)(L1 B1 R1 F1)(L2 B2 R2 F2)(L3 B3 R3 F3)
+move D: (D1 D3 D8 D6)(D2 D5 D7 D4)(L6 F6 R6 B6)(L7 F7 R7 B7)(L8 F8 R8 B8)
+move L: (L1 L3 L8 L6)(L2 L5 L7 L4)(U1 F1 D1 B8)(U4 F4 D4 B5)(U6 F6 D6 B3)
+move R: (R1 R3 R8 R6)(R2 R5 R7 R4)(U3 B6 D3 F3)(U5 B4 D5 F5)(U8 B1 D8 F8)
+move F: (F1 F3 F8 F6)(F2 F5 F7 F4)(U6 R1 D3 L8)(U7 R4 D2 L5)(U8 R6 D1 L3)
+move B: (B1 B3 B8 B6)(B2 B5 B7 B4)(U3 L1 D6 R8)(U2 L4 D7 R5)(U1 L6 D8 R3)
+commute: U D
+commute: L R
+commute: F B
+";
+
+const CUBE2: &str = "
+stickers: U1 U3 U6 U8 L1 L3 L6 L8 F1 F3 F6 F8 R1 R3 R6 R8 B1 B3 B6 B8 D1 D3 D6 D8
+move U: (U1 U3 U8 U6)(L1 B1 R1 F1)(L3 B3 R3 F3)
+move R: (R1 R3 R8 R6)(U3 B6 D3 F3)(U8 B1 D8 F8)
+move F: (F1 F3 F8 F6)(U6 R1 D3 L8)(U8 R6 D1 L3)
+";
+
+const PYRAMINX: &str = "
+stickers: u0 u1 u2 u3 u4 u5 u6 u7 u8 r0 r1 r2 r3 r4 r5 r6 r7 r8 l0 l1 l2 l3 l4 l5 l6 l7 l8 b0 b1 b2 b3 b4 b5 b6 b7 b8
+move U: (r0 b0 l0)(r1 b1 l1)(r2 b2 l5)(r5 b5 l2)
+move u: (r0 b0 l0)
+move R: (u0 l8 b8)(u1 l6 b6)(u2 l5 b7)(u5 l7 b5)
+move r: (u0 l8 b8)
+move L: (u5 b2 r7)(u6 b3 r6)(u7 b7 r5)(u8 b4 r8)
+move l: (u8 b4 r8)
+move B: (u2 r7 l2)(u3 r3 l3)(u4 r4 l4)(u7 r2 l7)
+move b: (u4 r4 l4)
+";
+
+const SKEWB: &str = "
+stickers: U UBR UFR UBL UFL D DBR DFR DBL DFL F FRU FDR FLU FDL B BRU BDR BLU BDL R RBU RBD RFU RDF L LBU LBD LFU LDF
+move R: (UBR LBD FDR)(D R B)(DBR RBD BDR)(DFR RBU BDL)(DBL RDF BRU)
+move U: (U L B)(UBR LFU BDL)(UBL LBU BLU)(UFL LBD BRU)(DBL RBU FLU)
+move L: (UFL RDF BDL)(D L F)(DFR LBD FLU)(DBL LFU FDR)(DFL LDF FDL)
+move B: (UBL FDL RBD)(D B L)(DBR BLU LDF)(DBL BDL LBD)(DFL BDR LBU)
+";
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::group::Group;
+    use super::*;
+
+    fn order(puzzle: Puzzle) -> u128 {
+        Group::with_base(puzzle.gset(), vec![], puzzle.generators()).order()
+    }
+
+    #[test]
+    fn cube2_should_have_the_known_order() {
+        assert_eq!(order(cube2()), 3_674_160);
+    }
+
+    #[test]
+    fn pyraminx_should_have_the_known_order() {
+        assert_eq!(order(pyraminx()), 75_582_720);
+    }
+
+    #[test]
+    fn skewb_should_have_the_known_order() {
+        assert_eq!(order(skewb()), 3_149_280);
+    }
+
+    #[test]
+    fn generated_cubes_should_match_the_definitions() {
+        let cube2 = cube(2);
+
+        assert_eq!(cube(3), cube3());
+        assert_eq!(cube2.stickers().len(), 24);
+        assert_eq!(order(cube2), 24 * 3_674_160);
+        assert_eq!(cube(4).move_names().len(), 12);
+    }
+
+    #[test]
+    fn generated_megaminx_should_turn_five_cycles() {
+        let puzzle = megaminx();
+        let turn = puzzle.move_named("U").unwrap();
+
+        assert_eq!(puzzle.stickers().len(), 120);
+        assert_eq!(puzzle.move_names().len(), 12);
+        assert_eq!(turn.cycles().len(), 5);
+        assert!(turn.cycles().iter().all(|cycle| cycle.len() == 5));
+        assert_eq!(kilominx().stickers().len(), 60);
+    }
+
+    #[test]
+    fn cube3_should_have_the_known_order() {
+        assert_eq!(order(cube3()), 43_252_003_274_489_856_000);
+    }
+}
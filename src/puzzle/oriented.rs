@@ -0,0 +1,515 @@
+//! Pieces that have both a position and an orientation.
+//!
+//! A sticker permutation hides the structure of a puzzle: the stickers of a
+//! corner always move together. An `OrientedPermutation` describes where each
+//! piece goes and how much it is twisted on the way. This makes invariants like
+//! "the corner twists sum to 0 mod 3" checkable, which in turn explains *why* a
+//! state of a cube can not be solved.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::puzzle::models;
+//! # use permutation_rs::puzzle::oriented::CubeState;
+//! let puzzle = models::cube3();
+//! let state = puzzle.sequence("R U R' U'").unwrap().permutation(&puzzle).unwrap();
+//!
+//! assert_eq!(CubeState::from_stickers(&state).unwrap().check(), Ok(()));
+//! ```
+
+use super::super::group::permutation::Permutation;
+use super::super::group::{GroupAction, GroupElement};
+use super::{Puzzle, PuzzleError};
+use std::error;
+use std::fmt;
+
+/// A piece at a position with an orientation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OrientedPiece {
+    /// The position of the piece.
+    pub position: u64,
+    /// The orientation of the piece, modulo the number of orientations.
+    pub orientation: u64,
+}
+
+impl OrientedPiece {
+    /// Create an `OrientedPiece`.
+    pub fn new(position: u64, orientation: u64) -> OrientedPiece {
+        OrientedPiece {
+            position,
+            orientation,
+        }
+    }
+}
+
+/// A permutation of pieces that also twists them.
+///
+/// The image of piece `i` tells the position piece `i` moves to and the twist
+/// it receives.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OrientedPermutation {
+    modulus: u64,
+    images: Vec<OrientedPiece>,
+}
+
+impl OrientedPermutation {
+    /// Create an `OrientedPermutation` of pieces with `modulus` orientations.
+    pub fn new(modulus: u64, images: Vec<OrientedPiece>) -> OrientedPermutation {
+        OrientedPermutation { modulus, images }
+    }
+
+    /// The identity on `n` pieces with `modulus` orientations. See
+    /// `GroupElement::identity` for the identity on any number of pieces.
+    pub fn identity_on(n: usize, modulus: u64) -> OrientedPermutation {
+        let images = (0..n)
+            .map(|position| OrientedPiece::new(position as u64, 0))
+            .collect();
+        OrientedPermutation::new(modulus, images)
+    }
+
+    /// The number of orientations of a piece.
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// The images of the pieces.
+    pub fn images(&self) -> &[OrientedPiece] {
+        &self.images
+    }
+
+    /// The sum of all twists, modulo the number of orientations.
+    pub fn total_orientation(&self) -> u64 {
+        self.images
+            .iter()
+            .fold(0, |total, image| (total + image.orientation) % self.modulus)
+    }
+
+    /// Determine if the pieces are permuted by an even permutation.
+    pub fn is_even(&self) -> bool {
+        let mut visited = vec![false; self.images.len()];
+        let mut transpositions = 0;
+        for start in 0..self.images.len() {
+            let mut current = start;
+            let mut length = 0;
+            while !visited[current] {
+                visited[current] = true;
+                current = self.images[current].position as usize;
+                length += 1;
+            }
+            if length > 0 {
+                transpositions += length - 1;
+            }
+        }
+        transpositions % 2 == 0
+    }
+}
+
+impl GroupElement for OrientedPermutation {
+    /// The identity on no pieces. Pieces beyond the images of a permutation
+    /// stay in place, so it acts as the identity on any number of pieces. Its
+    /// modulus is 0, and a product with it takes the modulus of the other
+    /// factor.
+    fn identity() -> OrientedPermutation {
+        OrientedPermutation::new(0, vec![])
+    }
+
+    fn is_identity(&self) -> bool {
+        self.images
+            .iter()
+            .enumerate()
+            .all(|(index, image)| image.position == index as u64 && image.orientation == 0)
+    }
+
+    /// # Panics
+    /// Panics when the factors have a different number of orientations and
+    /// neither is the identity.
+    fn times(&self, multiplicant: &OrientedPermutation) -> OrientedPermutation {
+        let modulus = if self.is_identity() {
+            multiplicant.modulus
+        } else if multiplicant.is_identity() {
+            self.modulus
+        } else {
+            assert_eq!(
+                self.modulus, multiplicant.modulus,
+                "pieces should have the same number of orientations"
+            );
+            self.modulus
+        };
+        let images = (0..self.images.len().max(multiplicant.images.len()))
+            .map(|position| {
+                let image = self
+                    .images
+                    .get(position)
+                    .copied()
+                    .unwrap_or_else(|| OrientedPiece::new(position as u64, 0));
+                multiplicant.act_on(&image)
+            })
+            .collect();
+        OrientedPermutation::new(modulus, images)
+    }
+
+    fn inverse(&self) -> OrientedPermutation {
+        let mut images = vec![OrientedPiece::new(0, 0); self.images.len()];
+        for (index, image) in self.images.iter().enumerate() {
+            images[image.position as usize] = OrientedPiece::new(
+                index as u64,
+                (self.modulus - image.orientation) % self.modulus,
+            );
+        }
+        OrientedPermutation::new(self.modulus, images)
+    }
+}
+
+impl GroupAction for OrientedPermutation {
+    type Domain = OrientedPiece;
+
+    fn act_on(&self, piece: &OrientedPiece) -> OrientedPiece {
+        let image = match self.images.get(piece.position as usize) {
+            Some(image) => *image,
+            None => return *piece,
+        };
+        OrientedPiece::new(
+            image.position,
+            (piece.orientation + image.orientation) % self.modulus,
+        )
+    }
+}
+
+impl fmt::Display for OrientedPermutation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let images: Vec<String> = self
+            .images
+            .iter()
+            .map(|image| format!("{}+{}", image.position, image.orientation))
+            .collect();
+        write!(f, "[{}]", images.join(" "))
+    }
+}
+
+/// Pieces of a puzzle that have the same number of stickers.
+///
+/// Each piece lists its stickers in clockwise order, starting with the sticker
+/// that defines the orientation. The orientation of a piece at a position is
+/// the index of that sticker in the list of the position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pieces {
+    stickers: Vec<Vec<u64>>,
+}
+
+impl Pieces {
+    /// Create pieces from lists of sticker labels of `puzzle`, e.g.
+    /// `"U8 R1 F3"`.
+    pub fn new(puzzle: &Puzzle, pieces: &[&str]) -> Result<Pieces, PuzzleError> {
+        let mut stickers = vec![];
+        for piece in pieces {
+            let piece_stickers = piece
+                .split_whitespace()
+                .map(|label| {
+                    puzzle.sticker(label).ok_or_else(|| {
+                        PuzzleError::invalid(&format!("unknown sticker '{}'", label))
+                    })
+                })
+                .collect::<Result<Vec<u64>, PuzzleError>>()?;
+            stickers.push(piece_stickers);
+        }
+        if stickers
+            .windows(2)
+            .any(|pair| pair[0].len() != pair[1].len())
+        {
+            return Err(PuzzleError::invalid(
+                "pieces should have the same number of stickers",
+            ));
+        }
+        Ok(Pieces { stickers })
+    }
+
+    /// The number of pieces.
+    pub fn len(&self) -> usize {
+        self.stickers.len()
+    }
+
+    /// Determine if there are no pieces.
+    pub fn is_empty(&self) -> bool {
+        self.stickers.is_empty()
+    }
+
+    /// Determine how `state`, a permutation of stickers, moves and twists the
+    /// pieces.
+    ///
+    /// Fails with the index of the first piece whose stickers do not stay
+    /// together, i.e. a piece that is broken.
+    pub fn oriented(&self, state: &Permutation) -> Result<OrientedPermutation, u64> {
+        let modulus = self.stickers.first().map_or(1, |piece| piece.len()) as u64;
+        let mut images = vec![];
+        for (index, piece) in self.stickers.iter().enumerate() {
+            let image = self
+                .locate(state.act_on(&piece[0]))
+                .filter(|&(position, orientation)| {
+                    let target = &self.stickers[position as usize];
+                    piece.iter().enumerate().all(|(offset, sticker)| {
+                        let expected = (orientation as usize + offset) % target.len();
+                        state.act_on(sticker) == target[expected]
+                    })
+                })
+                .ok_or(index as u64)?;
+            images.push(OrientedPiece::new(image.0, image.1));
+        }
+        Ok(OrientedPermutation::new(modulus, images))
+    }
+
+    fn locate(&self, sticker: u64) -> Option<(u64, u64)> {
+        self.stickers
+            .iter()
+            .enumerate()
+            .filter_map(|(position, piece)| {
+                piece
+                    .iter()
+                    .position(|candidate| *candidate == sticker)
+                    .map(|orientation| (position as u64, orientation as u64))
+            })
+            .next()
+    }
+}
+
+/// The corners of `models::cube3`, starting with the `U` or `D` sticker.
+const CUBE3_CORNERS: [&str; 8] = [
+    "U8 R1 F3", "U6 F1 L3", "U1 L1 B3", "U3 B1 R3", "D3 F8 R6", "D1 L8 F6", "D6 B8 L6", "D8 R8 B6",
+];
+
+/// The edges of `models::cube3`, starting with the `U` or `D` sticker, or the
+/// `F` or `B` sticker for the middle layer.
+const CUBE3_EDGES: [&str; 12] = [
+    "U5 R2", "U7 F2", "U4 L2", "U2 B2", "D5 R7", "D2 F7", "D4 L7", "D7 B7", "F5 R4", "F4 L5",
+    "B5 L4", "B4 R5",
+];
+
+/// The state of a 3x3x3 cube in terms of its corners and edges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CubeState {
+    /// The permutation and twists of the corners.
+    pub corners: OrientedPermutation,
+    /// The permutation and flips of the edges.
+    pub edges: OrientedPermutation,
+}
+
+impl CubeState {
+    /// Determine the corners and edges of a state of `models::cube3`.
+    pub fn from_stickers(state: &Permutation) -> Result<CubeState, Unsolvable> {
+        let puzzle = super::models::cube3();
+        let corners = Pieces::new(&puzzle, &CUBE3_CORNERS).expect("corners should be valid");
+        let edges = Pieces::new(&puzzle, &CUBE3_EDGES).expect("edges should be valid");
+        Ok(CubeState {
+            corners: corners.oriented(state).map_err(Unsolvable::BrokenCorner)?,
+            edges: edges.oriented(state).map_err(Unsolvable::BrokenEdge)?,
+        })
+    }
+
+    /// Verify the invariants of the cube: the corner twists sum to 0 mod 3, the
+    /// edge flips sum to 0 mod 2 and the corners and edges have equal parity.
+    pub fn check(&self) -> Result<(), Unsolvable> {
+        let twist = self.corners.total_orientation();
+        if twist != 0 {
+            return Err(Unsolvable::TwistedCorner(twist));
+        }
+        if self.edges.total_orientation() != 0 {
+            return Err(Unsolvable::FlippedEdge);
+        }
+        if self.corners.is_even() != self.edges.is_even() {
+            return Err(Unsolvable::Parity);
+        }
+        Ok(())
+    }
+}
+
+/// The reasons a state of a 3x3x3 cube can not be solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unsolvable {
+    /// The stickers of the corner with this index are not together.
+    BrokenCorner(u64),
+    /// The stickers of the edge with this index are not together.
+    BrokenEdge(u64),
+    /// The corner twists sum to this amount instead of 0 mod 3.
+    TwistedCorner(u64),
+    /// The edge flips sum to 1 mod 2.
+    FlippedEdge,
+    /// The corners and edges are permuted with different parity, i.e. two
+    /// pieces are swapped.
+    Parity,
+}
+
+impl fmt::Display for Unsolvable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Unsolvable::BrokenCorner(index) => write!(f, "corner {} is broken", index),
+            Unsolvable::BrokenEdge(index) => write!(f, "edge {} is broken", index),
+            Unsolvable::TwistedCorner(twist) => {
+                write!(f, "a corner is twisted, the twists sum to {} mod 3", twist)
+            }
+            Unsolvable::FlippedEdge => write!(f, "an edge is flipped"),
+            Unsolvable::Parity => write!(f, "two pieces are swapped"),
+        }
+    }
+}
+
+impl error::Error for Unsolvable {}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::group::Group;
+    use super::super::models;
+    use super::*;
+    use std::collections::HashMap;
+
+    fn cube3_state(sequence: &str) -> Permutation {
+        let puzzle = models::cube3();
+        puzzle
+            .sequence(sequence)
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap()
+    }
+
+    fn swap(state: &Permutation, cycles: &[(&str, &str)]) -> Permutation {
+        let puzzle = models::cube3();
+        let mut images: HashMap<u64, u64> = puzzle.gset().into_iter().map(|i| (i, i)).collect();
+        for (a, b) in cycles {
+            let (a, b) = (puzzle.sticker(a).unwrap(), puzzle.sticker(b).unwrap());
+            images.insert(a, b);
+            images.insert(b, a);
+        }
+        state.times(&Permutation::new(images))
+    }
+
+    #[test]
+    fn oriented_permutation_should_compose_twists() {
+        let twist =
+            OrientedPermutation::new(3, vec![OrientedPiece::new(1, 1), OrientedPiece::new(0, 0)]);
+
+        let square = twist.times(&twist);
+
+        assert_eq!(
+            square.act_on(&OrientedPiece::new(0, 0)),
+            OrientedPiece::new(0, 1)
+        );
+        assert!(twist.times(&twist.inverse()).is_identity());
+    }
+
+    #[test]
+    fn identity_should_fix_any_number_of_pieces() {
+        let twist =
+            OrientedPermutation::new(3, vec![OrientedPiece::new(1, 1), OrientedPiece::new(0, 0)]);
+        let identity = <OrientedPermutation as GroupElement>::identity();
+
+        assert!(identity.is_identity());
+        assert_eq!(identity.times(&twist), twist);
+        assert_eq!(twist.times(&identity), twist);
+        assert_eq!(
+            identity.act_on(&OrientedPiece::new(5, 2)),
+            OrientedPiece::new(5, 2)
+        );
+        assert_eq!(
+            OrientedPermutation::identity_on(2, 2)
+                .times(&twist)
+                .modulus(),
+            3
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the same number of orientations")]
+    fn products_should_not_mix_moduli() {
+        let twist =
+            OrientedPermutation::new(3, vec![OrientedPiece::new(1, 1), OrientedPiece::new(0, 0)]);
+        let flip =
+            OrientedPermutation::new(2, vec![OrientedPiece::new(0, 1), OrientedPiece::new(1, 0)]);
+
+        twist.times(&flip);
+    }
+
+    #[test]
+    fn moves_should_satisfy_the_invariants() {
+        let state = cube3_state("R U2 F' L D B2 R' U");
+
+        let cube = CubeState::from_stickers(&state).unwrap();
+
+        assert_eq!(cube.check(), Ok(()));
+        assert!(!cube.corners.is_identity());
+    }
+
+    #[test]
+    fn check_should_explain_a_twisted_corner() {
+        let twisted = twist_ufr();
+
+        let cube = CubeState::from_stickers(&twisted).unwrap();
+
+        assert_eq!(cube.check(), Err(Unsolvable::TwistedCorner(1)));
+    }
+
+    #[test]
+    fn check_should_explain_a_flipped_edge() {
+        let flipped = swap(&cube3_state(""), &[("U5", "R2")]);
+
+        let cube = CubeState::from_stickers(&flipped).unwrap();
+
+        assert_eq!(cube.check(), Err(Unsolvable::FlippedEdge));
+    }
+
+    #[test]
+    fn check_should_explain_swapped_pieces() {
+        let swapped = swap(&cube3_state(""), &[("U5", "U7"), ("R2", "F2")]);
+
+        let cube = CubeState::from_stickers(&swapped).unwrap();
+
+        assert_eq!(cube.check(), Err(Unsolvable::Parity));
+    }
+
+    #[test]
+    fn from_stickers_should_detect_broken_pieces() {
+        let broken = swap(&cube3_state(""), &[("U5", "U7")]);
+
+        assert_eq!(
+            CubeState::from_stickers(&broken),
+            Err(Unsolvable::BrokenEdge(0))
+        );
+    }
+
+    #[test]
+    fn oriented_corners_of_the_2x2x2_should_form_its_group() {
+        let puzzle = models::cube2();
+        let corners = Pieces::new(
+            &puzzle,
+            &[
+                "U8 R1 F3", "U6 F1 L3", "U1 L1 B3", "U3 B1 R3", "D3 F8 R6", "D1 L8 F6", "D6 B8 L6",
+                "D8 R8 B6",
+            ],
+        )
+        .unwrap();
+        let generators: Vec<OrientedPermutation> = puzzle
+            .generators()
+            .iter()
+            .map(|generator| corners.oriented(generator).unwrap())
+            .collect();
+        let gset: Vec<OrientedPiece> = (0..8)
+            .flat_map(|position| {
+                (0..3).map(move |orientation| OrientedPiece::new(position, orientation))
+            })
+            .collect();
+
+        let group = Group::with_base(gset, vec![], generators);
+
+        assert_eq!(group.order(), 3_674_160);
+    }
+
+    fn twist_ufr() -> Permutation {
+        let puzzle = models::cube3();
+        let mut images: HashMap<u64, u64> = puzzle.gset().into_iter().map(|i| (i, i)).collect();
+        let (u, r, f) = (
+            puzzle.sticker("U8").unwrap(),
+            puzzle.sticker("R1").unwrap(),
+            puzzle.sticker("F3").unwrap(),
+        );
+        images.insert(u, r);
+        images.insert(r, f);
+        images.insert(f, u);
+        Permutation::new(images)
+    }
+}
@@ -0,0 +1,253 @@
+//! Sequences of moves in the notation puzzle users are familiar with.
+//!
+//! A sequence is a whitespace separated list of moves. Each move is a name
+//! followed by an optional multiplier and an optional `'` for the inverse, e.g.
+//! `R`, `U'`, `R2` or `Rw2'`.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::puzzle::{Puzzle, Sequence};
+//! let puzzle: Puzzle = "
+//!     stickers: a b c d
+//!     move R: (a b c d)
+//! "
+//! .parse()
+//! .unwrap();
+//!
+//! let sequence = Sequence::parse("R2 R'").unwrap();
+//! let state = sequence.permutation(&puzzle).unwrap();
+//!
+//! assert_eq!(state, puzzle.move_named("R").unwrap().clone());
+//! ```
+
+use super::super::group::free::Word;
+use super::super::group::permutation::Permutation;
+use super::super::group::GroupElement;
+use super::Puzzle;
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A sequence of named moves, each with an exponent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    moves: Vec<(String, i64)>,
+    inverse_suffix: String,
+}
+
+impl Sequence {
+    /// Parse a sequence of moves like `"R U R' U'"`.
+    pub fn parse(text: &str) -> Result<Sequence, SequenceError> {
+        Sequence::parse_with(text, "'")
+    }
+
+    /// Parse a sequence of moves where inverses are denoted by `inverse_suffix`.
+    pub fn parse_with(text: &str, inverse_suffix: &str) -> Result<Sequence, SequenceError> {
+        let mut moves = vec![];
+        for token in text.split_whitespace() {
+            moves.push(parse_move(token, inverse_suffix)?);
+        }
+        Ok(Sequence {
+            moves,
+            inverse_suffix: inverse_suffix.to_string(),
+        })
+    }
+
+    /// The moves of this sequence with their exponents.
+    pub fn moves(&self) -> &[(String, i64)] {
+        &self.moves
+    }
+
+    /// The sequence as a word over the move names.
+    pub fn to_word(&self) -> Word<String> {
+        Word::new(self.moves.clone())
+    }
+
+    /// Resolve the moves with `puzzle` and calculate the resulting state.
+    pub fn permutation(&self, puzzle: &Puzzle) -> Result<Permutation, SequenceError> {
+        let identity: HashMap<u64, u64> = puzzle.gset().into_iter().map(|i| (i, i)).collect();
+        let mut result = Permutation::new(identity);
+        for (name, exponent) in &self.moves {
+            let permutation = puzzle
+                .move_named(name)
+                .ok_or_else(|| SequenceError::UnknownMove(name.clone()))?;
+            let order = permutation.order() as i64;
+            for _ in 0..exponent.rem_euclid(order) {
+                result = result.times(permutation);
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn parse_move(token: &str, inverse_suffix: &str) -> Result<(String, i64), SequenceError> {
+    let mut rest = token;
+    let mut inverted = false;
+    if !inverse_suffix.is_empty() {
+        if let Some(stripped) = rest.strip_suffix(inverse_suffix) {
+            rest = stripped;
+            inverted = true;
+        }
+    }
+    let digits = rest.len()
+        - rest
+            .chars()
+            .rev()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+    let (mut name, multiplier) = rest.split_at(digits);
+    if !inverted && !inverse_suffix.is_empty() {
+        if let Some(stripped) = name.strip_suffix(inverse_suffix) {
+            name = stripped;
+            inverted = true;
+        }
+    }
+    if name.is_empty() {
+        return Err(SequenceError::Malformed(token.to_string()));
+    }
+    let multiplier: i64 = if multiplier.is_empty() {
+        1
+    } else {
+        multiplier
+            .parse()
+            .map_err(|_| SequenceError::Malformed(token.to_string()))?
+    };
+    let exponent = if inverted { -multiplier } else { multiplier };
+    Ok((name.to_string(), exponent))
+}
+
+impl FromStr for Sequence {
+    type Err = SequenceError;
+
+    fn from_str(text: &str) -> Result<Sequence, SequenceError> {
+        Sequence::parse(text)
+    }
+}
+
+impl fmt::Display for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let moves: Vec<String> = self
+            .moves
+            .iter()
+            .map(|(name, exponent)| {
+                let suffix = if *exponent < 0 {
+                    self.inverse_suffix.as_str()
+                } else {
+                    ""
+                };
+                match exponent.abs() {
+                    1 => format!("{}{}", name, suffix),
+                    power => format!("{}{}{}", name, power, suffix),
+                }
+            })
+            .collect();
+        write!(f, "{}", moves.join(" "))
+    }
+}
+
+/// The ways parsing or resolving a sequence can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SequenceError {
+    /// The token is not a valid move.
+    Malformed(String),
+    /// The puzzle has no move with this name.
+    UnknownMove(String),
+}
+
+impl fmt::Display for SequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SequenceError::Malformed(ref token) => write!(f, "malformed move '{}'", token),
+            SequenceError::UnknownMove(ref name) => write!(f, "unknown move '{}'", name),
+        }
+    }
+}
+
+impl error::Error for SequenceError {}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::group::GroupAction;
+    use super::*;
+
+    fn puzzle() -> Puzzle {
+        "
+            stickers: a b c d e
+            move R: (a b c d)
+            move Rw: (d e)
+        "
+        .parse()
+        .unwrap()
+    }
+
+    #[test]
+    fn sequence_should_parse_multipliers_and_inverses() {
+        let sequence = Sequence::parse("R U' R2 Rw2' U'2").unwrap();
+
+        assert_eq!(
+            sequence.moves(),
+            &[
+                (String::from("R"), 1),
+                (String::from("U"), -1),
+                (String::from("R"), 2),
+                (String::from("Rw"), -2),
+                (String::from("U"), -2),
+            ]
+        );
+        assert_eq!(format!("{}", sequence), "R U' R2 Rw2' U2'");
+    }
+
+    #[test]
+    fn sequence_should_display_its_inverse_suffix() {
+        let sequence = Sequence::parse_with("R U-2 R-", "-").unwrap();
+
+        assert_eq!(format!("{}", sequence), "R U2- R-");
+    }
+
+    #[test]
+    fn sequence_should_reject_malformed_moves() {
+        assert_eq!(
+            Sequence::parse("R '"),
+            Err(SequenceError::Malformed(String::from("'")))
+        );
+    }
+
+    #[test]
+    fn sequence_should_calculate_the_state() {
+        let puzzle = puzzle();
+
+        let state = Sequence::parse("R Rw R'")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+
+        assert_eq!(state.act_on(&0), 0);
+        assert_eq!(state.act_on(&2), 4);
+        assert_eq!(state.act_on(&4), 2);
+    }
+
+    #[test]
+    fn sequence_should_reduce_large_multipliers() {
+        let puzzle = puzzle();
+
+        let state = Sequence::parse("R99999999999 R'4000000000001")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+
+        assert_eq!(
+            state,
+            Sequence::parse("R2").unwrap().permutation(&puzzle).unwrap()
+        );
+    }
+
+    #[test]
+    fn sequence_should_report_unknown_moves() {
+        let puzzle = puzzle();
+
+        let result = Sequence::parse("R U").unwrap().permutation(&puzzle);
+
+        assert_eq!(result, Err(SequenceError::UnknownMove(String::from("U"))));
+    }
+}
@@ -0,0 +1,315 @@
+//! The n×n sliding puzzle, of which the 15-puzzle is the best known instance.
+//!
+//! The positions are numbered row by row, starting at the top left. In the
+//! solved state tile `p` occupies position `p` and the blank is the last tile,
+//! occupying the bottom right position. A state is a `Permutation` that maps
+//! every tile to the position it occupies.
+//!
+//! Not every state can be reached. A slide swaps the blank with a neighbouring
+//! tile, so the parity of a state always equals the parity of the distance of
+//! the blank to its home position. Half of the states are unsolvable.
+//!
+//! The states that leave the blank at home form a group. It is generated by
+//! loops of the blank around the 2×2 squares of the puzzle. A state is solved
+//! by moving the blank home and stripping the remaining permutation with the
+//! group of loops.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::puzzle::sliding::{Slide, SlidingPuzzle};
+//! let puzzle = SlidingPuzzle::new(3);
+//! let scramble = [Slide::Down, Slide::Right, Slide::Down, Slide::Left];
+//! let state = puzzle.apply_all(&puzzle.solved(), &scramble).unwrap();
+//!
+//! let solution = puzzle.solve(&state).unwrap();
+//!
+//! assert!(puzzle.apply_all(&state, &solution).unwrap() == puzzle.solved());
+//! ```
+
+use super::super::group::free::Word;
+use super::super::group::permutation::Permutation;
+use super::super::group::special::SLPPermutation;
+use super::super::group::tree::SLP;
+use super::super::group::{Group, GroupAction, GroupElement, Morphism};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A move of the sliding puzzle, named by the direction in which a tile slides
+/// into the blank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Slide {
+    /// The tile below the blank slides up.
+    Up,
+    /// The tile above the blank slides down.
+    Down,
+    /// The tile right of the blank slides left.
+    Left,
+    /// The tile left of the blank slides right.
+    Right,
+}
+
+impl Slide {
+    /// The slide that undoes this slide.
+    pub fn opposite(self) -> Slide {
+        match self {
+            Slide::Up => Slide::Down,
+            Slide::Down => Slide::Up,
+            Slide::Left => Slide::Right,
+            Slide::Right => Slide::Left,
+        }
+    }
+}
+
+impl fmt::Display for Slide {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            Slide::Up => "U",
+            Slide::Down => "D",
+            Slide::Left => "L",
+            Slide::Right => "R",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// An n×n sliding puzzle together with the group of states that leave the
+/// blank at home.
+pub struct SlidingPuzzle {
+    size: u64,
+    loops: Vec<Vec<Slide>>,
+    group: Group<u64, SLPPermutation>,
+}
+
+impl SlidingPuzzle {
+    /// Create the n×n sliding puzzle. This builds the group of loops, so it
+    /// takes a while for larger puzzles.
+    ///
+    /// Panics when `size` is smaller than 2.
+    pub fn new(size: u64) -> SlidingPuzzle {
+        assert!(size >= 2, "a sliding puzzle should be at least 2×2");
+        let loops = square_loops(size);
+        let solved = identity(size);
+        let generators: Vec<SLPPermutation> = loops
+            .iter()
+            .enumerate()
+            .map(|(index, slides)| {
+                let permutation = slide_all(size, &solved, slides).expect("loops should be legal");
+                SLPPermutation::new(SLP::Generator(index as u64), permutation)
+            })
+            .collect();
+        let base: Vec<u64> = (0..(size * size - 1)).collect();
+        let group = Group::with_base(positions(size), base, generators);
+        SlidingPuzzle { size, loops, group }
+    }
+
+    /// The number of tiles along a side.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The blank, which is the last tile.
+    pub fn blank(&self) -> u64 {
+        self.size * self.size - 1
+    }
+
+    /// The solved state.
+    pub fn solved(&self) -> Permutation {
+        identity(self.size)
+    }
+
+    /// The group of states that leave the blank at home.
+    pub fn group(&self) -> &Group<u64, SLPPermutation> {
+        &self.group
+    }
+
+    /// Perform `slide` on `state`. Returns `None` when there is no tile to
+    /// slide in that direction.
+    pub fn apply(&self, state: &Permutation, slide: Slide) -> Option<Permutation> {
+        apply(self.size, state, slide)
+    }
+
+    /// Perform `slides` in order on `state`. Returns `None` when one of the
+    /// slides is not possible.
+    pub fn apply_all(&self, state: &Permutation, slides: &[Slide]) -> Option<Permutation> {
+        slide_all(self.size, state, slides)
+    }
+
+    /// Determine if `state` can be solved, i.e. if its parity equals the parity
+    /// of the distance of the blank to its home position.
+    pub fn is_solvable(&self, state: &Permutation) -> bool {
+        let blank = state.act_on(&self.blank());
+        let last = self.size - 1;
+        let distance = (last - blank / self.size) + (last - blank % self.size);
+        is_even(self.size, state) == distance.is_multiple_of(2)
+    }
+
+    /// Find slides that solve `state`. Returns `None` when `state` can not be
+    /// solved.
+    pub fn solve(&self, state: &Permutation) -> Option<Vec<Slide>> {
+        if !self.is_solvable(state) {
+            return None;
+        }
+        let blank = state.act_on(&self.blank());
+        let last = self.size - 1;
+        let mut slides: Vec<Slide> = vec![];
+        slides.extend(vec![Slide::Left; (last - blank % self.size) as usize]);
+        slides.extend(vec![Slide::Up; (last - blank / self.size) as usize]);
+        let home = self.apply_all(state, &slides)?;
+
+        let stripped = self.group.strip(SLPPermutation::new(SLP::Identity, home));
+        if !stripped.element.1.is_identity() {
+            return None;
+        }
+        for (index, exponent) in stripped.transform(&self.morphism()).iter() {
+            let path = &self.loops[*index];
+            for _ in 0..exponent.abs() {
+                if *exponent > 0 {
+                    slides.extend(path.iter().cloned());
+                } else {
+                    slides.extend(path.iter().rev().map(|slide| slide.opposite()));
+                }
+            }
+        }
+        Some(cancel(slides))
+    }
+
+    fn morphism(&self) -> Morphism<SLP, Word<usize>> {
+        let images: HashMap<SLP, Word<usize>> = (0..self.loops.len())
+            .map(|index| (SLP::Generator(index as u64), Word::generator(index)))
+            .collect();
+        Morphism::new(images)
+    }
+}
+
+fn positions(size: u64) -> Vec<u64> {
+    (0..(size * size)).collect()
+}
+
+fn identity(size: u64) -> Permutation {
+    Permutation::new(positions(size).into_iter().map(|p| (p, p)).collect())
+}
+
+fn apply(size: u64, state: &Permutation, slide: Slide) -> Option<Permutation> {
+    let blank = state.act_on(&(size * size - 1));
+    let (row, column) = (blank / size, blank % size);
+    let neighbour = match slide {
+        Slide::Up if row < size - 1 => blank + size,
+        Slide::Down if row > 0 => blank - size,
+        Slide::Left if column < size - 1 => blank + 1,
+        Slide::Right if column > 0 => blank - 1,
+        _ => return None,
+    };
+    let mut images: HashMap<u64, u64> = positions(size).into_iter().map(|p| (p, p)).collect();
+    images.insert(blank, neighbour);
+    images.insert(neighbour, blank);
+    Some(state.times(&Permutation::new(images)))
+}
+
+fn slide_all(size: u64, state: &Permutation, slides: &[Slide]) -> Option<Permutation> {
+    slides.iter().try_fold(state.clone(), |current, slide| {
+        apply(size, &current, *slide)
+    })
+}
+
+fn is_even(size: u64, state: &Permutation) -> bool {
+    let mut visited = vec![false; (size * size) as usize];
+    let mut transpositions: u64 = 0;
+    for start in positions(size) {
+        let mut current = start;
+        let mut length = 0;
+        while !visited[current as usize] {
+            visited[current as usize] = true;
+            current = state.act_on(&current);
+            length += 1;
+        }
+        if length > 0 {
+            transpositions += length - 1;
+        }
+    }
+    transpositions.is_multiple_of(2)
+}
+
+/// Loops of the blank, starting from its home position, around each of the 2×2
+/// squares.
+fn square_loops(size: u64) -> Vec<Vec<Slide>> {
+    let mut loops = vec![];
+    for row in 0..(size - 1) {
+        for column in 0..(size - 1) {
+            let mut path = vec![];
+            path.extend(vec![Slide::Right; (size - 2 - column) as usize]);
+            path.extend(vec![Slide::Down; (size - 2 - row) as usize]);
+            let mut slides = path.clone();
+            slides.extend(vec![Slide::Down, Slide::Right, Slide::Up, Slide::Left]);
+            slides.extend(path.iter().rev().map(|slide| slide.opposite()));
+            loops.push(slides);
+        }
+    }
+    loops
+}
+
+/// Remove slides that are directly undone by the next slide.
+fn cancel(slides: Vec<Slide>) -> Vec<Slide> {
+    let mut result: Vec<Slide> = vec![];
+    for slide in slides {
+        if result.last() == Some(&slide.opposite()) {
+            result.pop();
+        } else {
+            result.push(slide);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loops_should_generate_the_alternating_group() {
+        let puzzle = SlidingPuzzle::new(3);
+
+        assert_eq!(puzzle.group().order(), 20_160);
+    }
+
+    #[test]
+    fn swapped_tiles_should_not_be_solvable() {
+        let puzzle = SlidingPuzzle::new(4);
+        let mut images: HashMap<u64, u64> = positions(4).into_iter().map(|p| (p, p)).collect();
+        images.insert(13, 14);
+        images.insert(14, 13);
+        let state = Permutation::new(images);
+
+        assert!(!puzzle.is_solvable(&state));
+        assert_eq!(puzzle.solve(&state), None);
+    }
+
+    #[test]
+    fn slides_should_not_leave_the_board() {
+        let puzzle = SlidingPuzzle::new(3);
+
+        assert_eq!(puzzle.apply(&puzzle.solved(), Slide::Up), None);
+        assert!(puzzle.apply(&puzzle.solved(), Slide::Down).is_some());
+    }
+
+    #[test]
+    fn solve_should_solve_scrambled_states() {
+        let puzzle = SlidingPuzzle::new(4);
+        let scramble = [
+            Slide::Down,
+            Slide::Down,
+            Slide::Right,
+            Slide::Up,
+            Slide::Right,
+            Slide::Down,
+            Slide::Left,
+            Slide::Down,
+            Slide::Right,
+        ];
+        let state = puzzle.apply_all(&puzzle.solved(), &scramble).unwrap();
+        assert!(puzzle.is_solvable(&state));
+
+        let solution = puzzle.solve(&state).unwrap();
+
+        assert_eq!(puzzle.apply_all(&state, &solution), Some(puzzle.solved()));
+    }
+}
@@ -0,0 +1,182 @@
+//! Find algorithms for small cycles the way people do: with commutators.
+//!
+//! A commutator [_A_, _B_] = _A_^-1 _B_^-1 _A_ _B_ of an insertion _A_ and a
+//! single move _B_ often affects only a few pieces. Conjugating it with a setup
+//! _S_, i.e. _S_^-1 [_A_, _B_] _S_, moves those pieces to where they are needed.
+//! `CommutatorFinder` searches such products for one that performs a target
+//! permutation, e.g. a 3-cycle of corners. The result is far shorter than what
+//! stripping with a stabilizer chain produces.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::puzzle::models;
+//! # use permutation_rs::solver;
+//! # use permutation_rs::solver::commutator::CommutatorFinder;
+//! let puzzle = models::cube2();
+//! let target = puzzle
+//!     .sequence("R U R' F R U' R' F'")
+//!     .unwrap()
+//!     .permutation(&puzzle)
+//!     .unwrap();
+//! let finder = CommutatorFinder::new(solver::face_turns(&puzzle), 3, 0);
+//!
+//! let algorithm = finder.find(&target).unwrap();
+//!
+//! assert!(algorithm.syllable_count() <= 8);
+//! ```
+
+use super::super::group::free::Word;
+use super::super::group::permutation::Permutation;
+use super::super::group::{GroupAction, GroupElement};
+use super::Move;
+use std::collections::{HashMap, HashSet};
+
+/// Searches conjugated commutators of short words.
+pub struct CommutatorFinder {
+    moves: Vec<Move>,
+    insertions: Vec<(Word<String>, Permutation)>,
+    setups: Vec<(Word<String>, Permutation)>,
+}
+
+impl CommutatorFinder {
+    /// Create a finder that combines insertions of at most `insertion_length`
+    /// moves with single `moves`, and conjugates them with setups of at most
+    /// `setup_length` moves.
+    pub fn new(moves: Vec<Move>, insertion_length: usize, setup_length: usize) -> CommutatorFinder {
+        let insertions = words(&moves, insertion_length);
+        let setups = words(&moves, setup_length);
+        CommutatorFinder {
+            moves,
+            insertions,
+            setups,
+        }
+    }
+
+    /// A shortest algorithm of the form _S_^-1 [_A_, _B_] _S_ that performs
+    /// `target`, or `None` when there is none within the lengths of this
+    /// finder.
+    pub fn find(&self, target: &Permutation) -> Option<Word<String>> {
+        let shape = cycle_type(target);
+        let mut commutators: HashMap<Permutation, Word<String>> = HashMap::new();
+        for (insertion, a) in &self.insertions {
+            for m in &self.moves {
+                for (word, permutation) in &[
+                    (insertion.commutator(&m.word), a.commutator(&m.permutation)),
+                    (m.word.commutator(insertion), m.permutation.commutator(a)),
+                ] {
+                    if permutation.is_identity() || cycle_type(permutation) != shape {
+                        continue;
+                    }
+                    let shorter = commutators
+                        .get(permutation)
+                        .is_none_or(|known| word.letter_length() < known.letter_length());
+                    if shorter {
+                        commutators.insert(permutation.clone(), word.clone());
+                    }
+                }
+            }
+        }
+
+        let mut best: Option<Word<String>> = None;
+        for (setup, s) in &self.setups {
+            let needed = target.conjugate_by(&s.inverse());
+            if let Some(commutator) = commutators.get(&needed) {
+                let algorithm = commutator.conjugate_by(setup);
+                let better = best
+                    .as_ref()
+                    .is_none_or(|known| algorithm.letter_length() < known.letter_length());
+                if better {
+                    best = Some(algorithm);
+                }
+            }
+        }
+        best
+    }
+}
+
+/// The distinct permutations of words of at most `length` moves, each with a
+/// shortest word, in order of length.
+fn words(moves: &[Move], length: usize) -> Vec<(Word<String>, Permutation)> {
+    let identity = Permutation::identity();
+    let mut seen: HashSet<Permutation> = HashSet::new();
+    seen.insert(identity.clone());
+    let mut result = vec![(Word::identity(), identity)];
+    let mut start = 0;
+    for _ in 0..length {
+        let end = result.len();
+        for index in start..end {
+            for m in moves {
+                let permutation = result[index].1.times(&m.permutation);
+                if seen.insert(permutation.clone()) {
+                    let word = result[index].0.times(&m.word);
+                    result.push((word, permutation));
+                }
+            }
+        }
+        start = end;
+    }
+    result
+}
+
+/// The lengths of the non trivial cycles of `permutation`, in increasing order.
+fn cycle_type(permutation: &Permutation) -> Vec<usize> {
+    let mut visited: HashSet<u64> = HashSet::new();
+    let mut lengths = vec![];
+    for point in permutation.moved_points() {
+        if visited.contains(&point) {
+            continue;
+        }
+        let mut length = 0;
+        let mut current = point;
+        while visited.insert(current) {
+            current = permutation.act_on(&current);
+            length += 1;
+        }
+        lengths.push(length);
+    }
+    lengths.sort_unstable();
+    lengths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::models;
+    use super::super::face_turns;
+    use super::*;
+
+    #[test]
+    fn finder_should_use_a_setup_when_needed() {
+        let puzzle = models::cube2();
+        let target = puzzle
+            .sequence("U R U R' F R U' R' F' U'")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+        let finder = CommutatorFinder::new(face_turns(&puzzle), 3, 1);
+
+        let algorithm = finder.find(&target).unwrap();
+
+        assert_eq!(algorithm.evaluate(&puzzle.move_images()), target);
+        assert!(algorithm.syllable_count() <= 10);
+    }
+
+    #[test]
+    fn finder_should_give_up_beyond_its_lengths() {
+        let puzzle = models::cube2();
+        let target = puzzle
+            .sequence("U R U R' F R U' R' F' U'")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+        let finder = CommutatorFinder::new(face_turns(&puzzle), 1, 0);
+
+        assert_eq!(finder.find(&target), None);
+    }
+
+    #[test]
+    fn cycle_type_should_list_cycle_lengths() {
+        let permutation = permute!(0u64, 1u64, 1u64, 2u64, 2u64, 0u64, 3u64, 4u64, 4u64, 3u64);
+
+        assert_eq!(cycle_type(&permutation), vec![2, 3]);
+    }
+}
@@ -0,0 +1,260 @@
+//! Breadth first search through every state of a small puzzle.
+//!
+//! The distance of every element of a group to the identity, counted in moves,
+//! follows from a breadth first search through the Cayley graph. Elements are
+//! identified by their rank, see `Group::rank`, so the visited elements fit in
+//! a bitset of one bit per element. The transversals of the stabilizer chain
+//! are computed once, to rank and unrank quickly.
+//!
+//! The largest distance is the diameter of the graph, known as God's number
+//! for puzzles. This is feasible for groups up to about 10^8 elements, like
+//! the 2x2x2 cube, and it gives exact distances to validate heuristic solvers
+//! against.
+//!
+//! With the `parallel` feature the neighbours of each layer are computed on
+//! all cores.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::puzzle::models;
+//! # use permutation_rs::solver;
+//! # use permutation_rs::solver::exhaustive;
+//! let puzzle = models::cube2();
+//! let u = puzzle.move_named("U").unwrap().clone();
+//! let group = Group::with_base(puzzle.gset(), vec![], vec![u]);
+//! let moves = solver::moves(&puzzle, &["U", "U2", "U'"]).unwrap();
+//!
+//! let distances = exhaustive::distances(&group, &moves);
+//!
+//! assert_eq!(distances.counts, vec![1, 3]);
+//! assert_eq!(distances.diameter(), 1);
+//! ```
+
+use super::super::group::permutation::Permutation;
+use super::super::group::{Group, GroupAction, GroupElement};
+use super::Move;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// The number of elements at each distance from the identity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distances {
+    /// The number of elements at distance 0, 1, 2 and so on.
+    pub counts: Vec<u64>,
+}
+
+impl Distances {
+    /// The largest distance of an element to the identity.
+    pub fn diameter(&self) -> usize {
+        self.counts.len().saturating_sub(1)
+    }
+
+    /// The number of elements that were reached.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+}
+
+/// The distances of the elements of `group` to the identity, when every move
+/// multiplies with one of `moves`.
+///
+/// Panics when a move is not an element of `group`.
+pub fn distances(group: &Group<u64, Permutation>, moves: &[Move]) -> Distances {
+    let ranking = Ranking::new(group);
+    let mut visited: Vec<u64> = vec![0; group.order().div_ceil(64) as usize];
+    let mut frontier: Vec<u128> = vec![0];
+    visited[0] |= 1;
+    let mut counts: Vec<u64> = vec![];
+    while !frontier.is_empty() {
+        counts.push(frontier.len() as u64);
+        let mut next: Vec<u128> = vec![];
+        for rank in neighbours(&ranking, moves, &frontier) {
+            let index = rank as usize;
+            let (word, bit) = (index / 64, 1u64 << (index % 64));
+            if visited[word] & bit == 0 {
+                visited[word] |= bit;
+                next.push(rank);
+            }
+        }
+        frontier = next;
+    }
+    Distances { counts }
+}
+
+#[cfg(not(feature = "parallel"))]
+fn neighbours(ranking: &Ranking, moves: &[Move], frontier: &[u128]) -> Vec<u128> {
+    frontier
+        .iter()
+        .flat_map(|&rank| neighbours_of(ranking, moves, rank))
+        .collect()
+}
+
+#[cfg(feature = "parallel")]
+fn neighbours(ranking: &Ranking, moves: &[Move], frontier: &[u128]) -> Vec<u128> {
+    frontier
+        .par_iter()
+        .flat_map_iter(|&rank| neighbours_of(ranking, moves, rank))
+        .collect()
+}
+
+/// The ranks of the elements one move away from the element with `rank`.
+fn neighbours_of(ranking: &Ranking, moves: &[Move], rank: u128) -> Vec<u128> {
+    let element = ranking.unrank(rank);
+    moves
+        .iter()
+        .map(|m| {
+            ranking
+                .rank(&element.times(&m.permutation))
+                .expect("move should be an element of the group")
+        })
+        .collect()
+}
+
+/// The transversals of every level of a stabilizer chain, to rank and unrank
+/// the same way `Group::rank` and `Group::unrank` do.
+struct Ranking {
+    levels: Vec<RankingLevel>,
+}
+
+struct RankingLevel {
+    base: u64,
+    indices: HashMap<u64, usize>,
+    transversals: Vec<Permutation>,
+    inverses: Vec<Permutation>,
+}
+
+impl Ranking {
+    fn new(group: &Group<u64, Permutation>) -> Ranking {
+        let levels = group
+            .levels()
+            .map(|level| {
+                let points: Vec<u64> = level.orbit_points().cloned().collect();
+                let transversals: Vec<Permutation> = points
+                    .iter()
+                    .map(|point| level.transversal(point).expect("point in orbit"))
+                    .collect();
+                RankingLevel {
+                    base: *level.base(),
+                    indices: points
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, p)| (p, i))
+                        .collect(),
+                    inverses: transversals.iter().map(|t| t.inverse()).collect(),
+                    transversals,
+                }
+            })
+            .collect();
+        Ranking { levels }
+    }
+
+    fn rank(&self, element: &Permutation) -> Option<u128> {
+        let mut current = element.clone();
+        let mut rank = 0u128;
+        for level in &self.levels {
+            let index = *level.indices.get(&current.act_on(&level.base))?;
+            current = current.times(&level.inverses[index]);
+            rank = rank * level.transversals.len() as u128 + index as u128;
+        }
+        if current.is_identity() {
+            Some(rank)
+        } else {
+            None
+        }
+    }
+
+    fn unrank(&self, rank: u128) -> Permutation {
+        let mut remainder = rank;
+        let mut factors: Vec<&Permutation> = vec![];
+        for level in self.levels.iter().rev() {
+            let radix = level.transversals.len() as u128;
+            factors.push(&level.transversals[(remainder % radix) as usize]);
+            remainder /= radix;
+        }
+        match factors.split_first() {
+            Some((first, rest)) => rest
+                .iter()
+                .fold((*first).clone(), |product, factor| product.times(factor)),
+            None => Permutation::identity(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::group::free::Word;
+    use super::super::super::puzzle::models;
+    use super::*;
+
+    #[test]
+    fn adjacent_transpositions_should_count_inversions() {
+        let transpositions = vec![
+            permute!(0u64, 1u64, 1u64, 0u64, 2u64, 2u64, 3u64, 3u64),
+            permute!(0u64, 0u64, 1u64, 2u64, 2u64, 1u64, 3u64, 3u64),
+            permute!(0u64, 0u64, 1u64, 1u64, 2u64, 3u64, 3u64, 2u64),
+        ];
+        let group = Group::with_base(vec![0, 1, 2, 3], vec![], transpositions.clone());
+        let moves: Vec<Move> = transpositions
+            .into_iter()
+            .zip(&["a", "b", "c"])
+            .map(|(permutation, name)| Move {
+                word: Word::new(vec![(name.to_string(), 1)]),
+                permutation,
+            })
+            .collect();
+
+        let distances = distances(&group, &moves);
+
+        assert_eq!(distances.counts, vec![1, 3, 5, 6, 5, 3, 1]);
+        assert_eq!(distances.total(), 24);
+    }
+
+    #[test]
+    fn ranking_should_agree_with_the_group() {
+        let puzzle = models::cube2();
+        let group = Group::with_base(puzzle.gset(), vec![], puzzle.generators());
+        let ranking = Ranking::new(&group);
+        let element = puzzle
+            .sequence("R U2 F' R U'")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+
+        let rank = group.rank(&element).unwrap();
+
+        assert_eq!(ranking.rank(&element), Some(rank));
+        assert_eq!(ranking.unrank(rank), element);
+    }
+
+    #[test]
+    fn the_trivial_group_should_only_contain_the_identity() {
+        let trivial = Group::with_base(vec![0, 1, 2], vec![], vec![]);
+        let identity = Move {
+            word: Word::new(vec![]),
+            permutation: Permutation::identity(),
+        };
+
+        let distances = distances(&trivial, &[identity]);
+
+        assert_eq!(distances.counts, vec![1]);
+        assert_eq!(distances.diameter(), 0);
+    }
+
+    #[test]
+    fn a_single_move_should_walk_around_its_cycle() {
+        let puzzle = models::cube2();
+        let u = puzzle.move_named("U").unwrap().clone();
+        let group = Group::with_base(puzzle.gset(), vec![], vec![u.clone()]);
+        let moves = vec![Move {
+            word: Word::new(vec![("U".to_string(), 1)]),
+            permutation: u,
+        }];
+
+        let distances = distances(&group, &moves);
+
+        assert_eq!(distances.counts, vec![1, 1, 1, 1]);
+        assert_eq!(distances.diameter(), 3);
+    }
+}
@@ -0,0 +1,177 @@
+//! Solve to any of a set of goal states.
+//!
+//! A stage of a human method does not aim for a single state. Orienting all
+//! edges, or solving the cross, is done in any of the many states where the
+//! rest of the puzzle is scrambled. `Solver::solve_to_any` searches a shortest
+//! sequence that brings a state to one of a list of goals. When the goals are
+//! exactly the states of a coset of a subgroup, `Solver::solve_mod` decides
+//! membership with the stabilizer chain of the subgroup instead of a list.
+//!
+//! The pattern databases of a solver estimate the distance to the identity,
+//! not to the goals, so they are not used. Instead a breadth first search
+//! backwards from the goals finds every state within a radius of them, with
+//! its exact distance. States further away are at least one more than the
+//! radius away. This perimeter guides the search. Its size grows quickly with
+//! the radius, so the radius defaults to 0, where the estimate only tells
+//! goals apart from the other states.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::group::GroupElement;
+//! # use permutation_rs::group::permutation::Permutation;
+//! # use permutation_rs::puzzle::models;
+//! # use permutation_rs::solver::{self, Solver};
+//! let puzzle = models::cube2();
+//! let u = puzzle.move_named("U").unwrap().clone();
+//! let goals = vec![Permutation::identity(), u.clone(), u.times(&u), u.inverse()];
+//! let state = puzzle.sequence("U R").unwrap().permutation(&puzzle).unwrap();
+//! let solver = Solver::new(solver::face_turns(&puzzle)).with_goal_radius(1);
+//!
+//! let solution = solver.solve_to_any(&state, &goals).unwrap();
+//!
+//! assert_eq!(solution.to_string(), "R^-1");
+//! ```
+
+use super::super::group::free::Word;
+use super::super::group::permutation::Permutation;
+use super::super::group::GroupElement;
+use super::search::{ida_star_limited, NodeLimitExceeded};
+use super::{word, Move, Phase, Solver};
+use std::collections::HashMap;
+
+impl Solver {
+    /// Build a perimeter of `radius` moves around the goals of `solve_to_any`
+    /// to guide its search. Defaults to 0.
+    pub fn with_goal_radius(mut self, radius: usize) -> Solver {
+        self.goal_radius = radius;
+        self
+    }
+
+    /// Find a shortest word that brings `state` to one of `goals`, or `None`
+    /// when there is none within the maximum depth or the search visits too
+    /// many states.
+    pub fn solve_to_any(&self, state: &Permutation, goals: &[Permutation]) -> Option<Word<String>> {
+        self.try_solve_to_any(state, goals).ok().flatten()
+    }
+
+    /// Find a shortest word that brings `state` to one of `goals`, like
+    /// `solve_to_any`, but report how far the search got when it visits too
+    /// many states.
+    pub fn try_solve_to_any(
+        &self,
+        state: &Permutation,
+        goals: &[Permutation],
+    ) -> Result<Option<Word<String>>, NodeLimitExceeded> {
+        let perimeter = Perimeter::around(goals, &self.moves, self.goal_radius);
+        let phase = Phase {
+            moves: &self.moves,
+            costs: &self.costs,
+            rules: self.rules.as_ref(),
+            goal: &|state: &Permutation| perimeter.distance(state) == 0,
+            databases: &[],
+            perimeter: Some(&perimeter),
+            estimate: None,
+        };
+        let path = ida_star_limited(&phase, state, self.max_depth, self.max_nodes)?;
+        Ok(path.map(|path| word(&self.moves, &path)))
+    }
+}
+
+/// The states within a radius of a set of goals, with their distance to the
+/// nearest goal.
+pub(super) struct Perimeter {
+    distances: HashMap<Permutation, usize>,
+    radius: usize,
+}
+
+impl Perimeter {
+    /// Search backwards from `goals`, with the inverses of `moves`, up to
+    /// `radius` moves.
+    fn around(goals: &[Permutation], moves: &[Move], radius: usize) -> Perimeter {
+        let inverses: Vec<Permutation> = moves
+            .iter()
+            .map(|turn| turn.permutation.inverse())
+            .collect();
+        let mut distances: HashMap<Permutation, usize> = HashMap::new();
+        let mut layer: Vec<Permutation> = vec![];
+        for goal in goals {
+            if !distances.contains_key(goal) {
+                distances.insert(goal.clone(), 0);
+                layer.push(goal.clone());
+            }
+        }
+        for distance in 1..=radius {
+            let mut next = vec![];
+            for state in &layer {
+                for inverse in &inverses {
+                    let previous = state.times(inverse);
+                    if !distances.contains_key(&previous) {
+                        distances.insert(previous.clone(), distance);
+                        next.push(previous);
+                    }
+                }
+            }
+            layer = next;
+        }
+        Perimeter { distances, radius }
+    }
+
+    /// The number of moves from `state` to the nearest goal, or one more than
+    /// the radius for a state outside of the perimeter, which is a lower bound.
+    pub(super) fn distance(&self, state: &Permutation) -> usize {
+        self.distances
+            .get(state)
+            .cloned()
+            .unwrap_or(self.radius + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::models;
+    use super::super::face_turns;
+    use super::*;
+
+    #[test]
+    fn solver_should_reach_the_nearest_goal() {
+        let puzzle = models::cube2();
+        let solver = Solver::new(face_turns(&puzzle));
+        let state = puzzle
+            .sequence("R U F")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+        let near = puzzle.sequence("R").unwrap().permutation(&puzzle).unwrap();
+        let far = puzzle.sequence("F2").unwrap().permutation(&puzzle).unwrap();
+
+        let solution = solver.solve_to_any(&state, &[far, near.clone()]).unwrap();
+
+        assert_eq!(solution.syllable_count(), 2);
+        let images = puzzle.move_images();
+        assert_eq!(state.times(&solution.evaluate(&images)), near);
+    }
+
+    #[test]
+    fn perimeter_should_guide_the_search() {
+        let puzzle = models::cube2();
+        let goals = vec![Permutation::identity()];
+        let state = puzzle
+            .sequence("R U F R")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+
+        let blind = Solver::new(face_turns(&puzzle)).with_max_nodes(500);
+        let guided = Solver::new(face_turns(&puzzle))
+            .with_goal_radius(2)
+            .with_max_nodes(500);
+
+        assert!(blind.try_solve_to_any(&state, &goals).is_err());
+        assert_eq!(
+            guided
+                .solve_to_any(&state, &goals)
+                .map(|word| word.syllable_count()),
+            Some(4)
+        );
+    }
+}
@@ -0,0 +1,464 @@
+//! Search for short solutions of permutation puzzles.
+//!
+//! Stripping with a stabilizer chain always solves a puzzle, but the resulting
+//! sequences are long. The solvers in this module search for short sequences
+//! instead. They build upon `search::ida_star`, an iterative deepening search
+//! that can be guided by an estimate of the remaining distance. `Solver`
+//! searches a shortest solution directly, with the pattern databases of
+//! `pattern` as estimate, or a shortest word into a subgroup of symmetries
+//! that can not be seen, like the permutations of identical stickers.
+//! `goals` searches a shortest sequence to any of a set of states.
+//! `commutator` finds short algorithms for small cycles of pieces. `exhaustive`
+//! determines the distance of every state of a small puzzle. `symmetry`
+//! reduces states that are the same up to a symmetry of the puzzle. `partial`
+//! searches with moves that are only legal in some states. `pipeline` solves
+//! in stages, each into a smaller subgroup, like `thistlethwaite` does for the
+//! 3x3x3 cube. `table` stores the distance of every coset to a subgroup, to
+//! guide such a stage.
+//!
+//! A solver works with `Move`s: a word over the moves of a puzzle together with
+//! the permutation it performs. That way `R2` can be a single move of a search.
+//! By default every move counts as one; `Solver::with_metric` counts them in a
+//! `Metric` instead, e.g. the quarter turn metric where `R2` counts as two.
+//! `Solver::with_move_rules` skips sequences that only differ from another in
+//! the order of commuting moves, like `D U` next to `U D`.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::puzzle::models;
+//! # use permutation_rs::solver;
+//! let puzzle = models::cube3();
+//! let moves = solver::face_turns(&puzzle);
+//!
+//! assert_eq!(moves.len(), 18);
+//! ```
+
+pub mod commutator;
+pub mod exhaustive;
+pub mod goals;
+pub mod partial;
+pub mod pattern;
+pub mod pipeline;
+pub mod search;
+pub mod symmetry;
+pub mod table;
+pub mod thistlethwaite;
+pub mod two_phase;
+
+pub use self::pattern::{PatternDb, Projection};
+pub use self::search::{ida_star, ida_star_limited, NodeLimitExceeded, Problem};
+
+use super::group::free::{Metric, Word};
+use super::group::peephole::MoveRules;
+use super::group::permutation::Permutation;
+use super::group::{Group, GroupElement};
+use super::puzzle::{Puzzle, SequenceError};
+
+/// A solver that searches a shortest solution with IDA*, guided by pattern
+/// databases.
+pub struct Solver {
+    moves: Vec<Move>,
+    costs: Vec<usize>,
+    rules: Option<MoveRules<String>>,
+    databases: Vec<PatternDb>,
+    max_depth: usize,
+    max_nodes: u64,
+    goal_radius: usize,
+}
+
+impl Solver {
+    /// Create a solver that uses `moves`, each counting as a single move.
+    pub fn new(moves: Vec<Move>) -> Solver {
+        Solver {
+            costs: vec![1; moves.len()],
+            moves,
+            rules: None,
+            databases: vec![],
+            max_depth: 20,
+            max_nodes: u64::MAX,
+            goal_radius: 0,
+        }
+    }
+
+    /// Guide the search with `databases`. The largest of their distances is
+    /// used as estimate. They should be built with the moves of this solver.
+    pub fn with_pattern_dbs(mut self, databases: Vec<PatternDb>) -> Solver {
+        self.databases = databases;
+        self
+    }
+
+    /// Only search canonical sequences under `rules`: of two commuting moves
+    /// of a single syllable in a row, the first should be declared first. Every
+    /// other order has a canonical equivalent of the same length, so solutions
+    /// stay shortest, while far fewer sequences are searched. Moves of more
+    /// than one syllable, like `R U R'`, are never pruned. Use the
+    /// `move_rules` of the puzzle the moves are from.
+    pub fn with_move_rules(mut self, rules: MoveRules<String>) -> Solver {
+        self.rules = Some(rules);
+        self
+    }
+
+    /// Count the moves of a solution in `metric`, so that the solver searches
+    /// a shortest solution in that metric. Every move costs at least 1, so the
+    /// pattern databases, which count moves, still underestimate.
+    pub fn with_metric<M>(mut self, metric: &M) -> Solver
+    where
+        M: Metric<String> + ?Sized,
+    {
+        self.costs = self
+            .moves
+            .iter()
+            .map(|turn| (turn.word.length_in(metric) as usize).max(1))
+            .collect();
+        self
+    }
+
+    /// Limit the length of a solution, counted in the metric of this solver.
+    /// Defaults to 20.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Solver {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Limit the number of states a search may visit. Unlimited by default.
+    pub fn with_max_nodes(mut self, max_nodes: u64) -> Solver {
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    /// Find a shortest solution for `state`, or `None` when there is none
+    /// within the maximum depth or the search visits too many states.
+    pub fn solve(&self, state: &Permutation) -> Option<Word<String>> {
+        self.try_solve(state).ok().flatten()
+    }
+
+    /// Find a shortest solution for `state`, `None` when there is none within
+    /// the maximum depth, or an error that tells how far the search got when
+    /// it visits too many states.
+    pub fn try_solve(
+        &self,
+        state: &Permutation,
+    ) -> Result<Option<Word<String>>, NodeLimitExceeded> {
+        let phase = Phase {
+            moves: &self.moves,
+            costs: &self.costs,
+            rules: self.rules.as_ref(),
+            goal: &|state: &Permutation| state.is_identity(),
+            databases: &self.databases,
+            perimeter: None,
+            estimate: None,
+        };
+        let path = ida_star_limited(&phase, state, self.max_depth, self.max_nodes)?;
+        Ok(path.map(|path| word(&self.moves, &path)))
+    }
+
+    /// Find a shortest word that brings `state` into `h`, or `None` when there
+    /// is none within the maximum depth or the search visits too many states.
+    ///
+    /// The elements of `h` are the symmetries that can not be seen, e.g. the
+    /// permutations of identical stickers, so any state in `h` looks solved.
+    /// Membership is decided with the stabilizer chain of `h`. The pattern
+    /// databases estimate the distance to the identity, which can be more than
+    /// the distance to `h`, so they are not used.
+    pub fn solve_mod(
+        &self,
+        state: &Permutation,
+        h: &Group<u64, Permutation>,
+    ) -> Option<Word<String>> {
+        self.try_solve_mod(state, h).ok().flatten()
+    }
+
+    /// Find a shortest word that brings `state` into `h`, like `solve_mod`,
+    /// but report how far the search got when it visits too many states.
+    pub fn try_solve_mod(
+        &self,
+        state: &Permutation,
+        h: &Group<u64, Permutation>,
+    ) -> Result<Option<Word<String>>, NodeLimitExceeded> {
+        let phase = Phase {
+            moves: &self.moves,
+            costs: &self.costs,
+            rules: self.rules.as_ref(),
+            goal: &|state: &Permutation| h.contains(state),
+            databases: &[],
+            perimeter: None,
+            estimate: None,
+        };
+        let path = ida_star_limited(&phase, state, self.max_depth, self.max_nodes)?;
+        Ok(path.map(|path| word(&self.moves, &path)))
+    }
+}
+
+/// A move of a search: a word over the moves of a puzzle and its permutation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Move {
+    /// The word over the move names of the puzzle.
+    pub word: Word<String>,
+    /// The permutation the word performs.
+    pub permutation: Permutation,
+}
+
+impl Move {
+    /// Determine if this move turns the same part of the puzzle as `other`,
+    /// when both are a single syllable with the same move name. A search does
+    /// not need to follow a move with such a move, because the two combine
+    /// into a single move. A move of more syllables, like `R U R'`, continues
+    /// nothing, since combining it with a move need not give one of the moves.
+    pub fn continues(&self, other: &Move) -> bool {
+        match (self.syllable(), other.syllable()) {
+            (Some(first), Some(last)) => first == last,
+            _ => false,
+        }
+    }
+
+    /// Determine if this move may follow `previous` in a canonical sequence
+    /// under `rules`. When both are a single syllable, it may not continue
+    /// `previous`, and when they commute, the move of `previous` should be
+    /// declared first. Moves of more syllables may follow every move.
+    pub fn is_canonical_after(&self, previous: &Move, rules: &MoveRules<String>) -> bool {
+        match (previous.syllable(), self.syllable()) {
+            (Some(last), Some(first)) => {
+                last != first
+                    && !(rules.commute(last, first) && rules.rank(first) < rules.rank(last))
+            }
+            _ => true,
+        }
+    }
+
+    /// The move name of this move when it is a single syllable.
+    fn syllable(&self) -> Option<&String> {
+        if self.word.syllable_count() == 1 {
+            self.word.iter().next().map(|(name, _)| name)
+        } else {
+            None
+        }
+    }
+}
+
+/// The moves described by `sequences`, each a sequence of moves of `puzzle`.
+pub fn moves(puzzle: &Puzzle, sequences: &[&str]) -> Result<Vec<Move>, SequenceError> {
+    sequences
+        .iter()
+        .map(|text| {
+            let sequence = puzzle.sequence(text)?;
+            Ok(Move {
+                word: sequence.to_word(),
+                permutation: sequence.permutation(puzzle)?,
+            })
+        })
+        .collect()
+}
+
+/// Every non trivial power of every move of `puzzle`. Powers beyond half the
+/// order of a move are expressed as a negative power, e.g. `R'` instead of `R3`.
+pub fn face_turns(puzzle: &Puzzle) -> Vec<Move> {
+    let mut moves = vec![];
+    for name in puzzle.move_names() {
+        let permutation = puzzle.move_named(&name).expect("move should exist");
+        let order = permutation.order() as i64;
+        let mut power = permutation.clone();
+        for exponent in 1..order {
+            let exponent = if 2 * exponent > order {
+                exponent - order
+            } else {
+                exponent
+            };
+            moves.push(Move {
+                word: Word::new(vec![(name.clone(), exponent)]),
+                permutation: power.clone(),
+            });
+            power = power.times(permutation);
+        }
+    }
+    moves
+}
+
+/// The search for a sequence of `moves` that brings a state to a `goal`.
+struct Phase<'a> {
+    moves: &'a [Move],
+    costs: &'a [usize],
+    rules: Option<&'a MoveRules<String>>,
+    goal: &'a dyn Fn(&Permutation) -> bool,
+    databases: &'a [PatternDb],
+    perimeter: Option<&'a goals::Perimeter>,
+    estimate: Option<&'a dyn Fn(&Permutation) -> usize>,
+}
+
+impl<'a> Problem for Phase<'a> {
+    type State = Permutation;
+
+    fn moves(&self) -> usize {
+        self.moves.len()
+    }
+
+    fn apply(&self, state: &Permutation, index: usize) -> Permutation {
+        state.times(&self.moves[index].permutation)
+    }
+
+    fn cost(&self, index: usize) -> usize {
+        self.costs[index]
+    }
+
+    fn is_goal(&self, state: &Permutation) -> bool {
+        (self.goal)(state)
+    }
+
+    fn estimate(&self, state: &Permutation) -> usize {
+        let estimate = pattern::estimate(self.databases, state);
+        let estimate = match self.perimeter {
+            Some(perimeter) => estimate.max(perimeter.distance(state)),
+            None => estimate,
+        };
+        match self.estimate {
+            Some(other) => estimate.max(other(state)),
+            None => estimate,
+        }
+    }
+
+    fn may_follow(&self, previous: usize, next: usize) -> bool {
+        match self.rules {
+            Some(rules) => self.moves[next].is_canonical_after(&self.moves[previous], rules),
+            None => !self.moves[next].continues(&self.moves[previous]),
+        }
+    }
+}
+
+fn word(moves: &[Move], path: &[usize]) -> Word<String> {
+    path.iter().fold(Word::identity(), |word, index| {
+        word.times(&moves[*index].word)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::group::free::{HalfTurn, QuarterTurn};
+    use super::super::puzzle::models;
+    use super::*;
+
+    #[test]
+    fn solver_should_find_a_shortest_solution() {
+        let puzzle = models::cube2();
+        let moves = face_turns(&puzzle);
+        let projections = [["U3", "U6", "U8"], ["D1", "D3", "D8"]];
+        let databases = projections
+            .iter()
+            .map(|labels| {
+                let points = labels.iter().map(|l| puzzle.sticker(l).unwrap()).collect();
+                PatternDb::build(Projection::new(points), &moves)
+            })
+            .collect();
+        let solver = Solver::new(moves).with_pattern_dbs(databases);
+        let state = puzzle
+            .sequence("R U2 F' R")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+
+        let solution = solver.solve(&state).unwrap();
+
+        assert_eq!(solution.syllable_count(), 4);
+        let solved = state.times(&solution.evaluate(&puzzle.move_images()));
+        assert!(solved.is_identity());
+    }
+
+    #[test]
+    fn solver_should_solve_up_to_a_subgroup() {
+        let puzzle = models::cube2();
+        let solver = Solver::new(face_turns(&puzzle));
+        let u = puzzle.move_named("U").unwrap().clone();
+        let h = Group::with_base(puzzle.gset(), vec![], vec![u]);
+        let state = puzzle
+            .sequence("U R")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+
+        let solution = solver.solve_mod(&state, &h).unwrap();
+
+        assert_eq!(solution.syllable_count(), 1);
+        assert_eq!(solver.solve(&state).unwrap().syllable_count(), 2);
+        let solved = state.times(&solution.evaluate(&puzzle.move_images()));
+        assert!(h.contains(&solved));
+    }
+
+    #[test]
+    fn solver_should_search_in_its_metric() {
+        let puzzle = models::cube2();
+        let moves = face_turns(&puzzle);
+        let state = puzzle
+            .sequence("R2 U2")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+
+        let half_turns = Solver::new(moves.clone()).with_metric(&HalfTurn::new());
+        let quarter_turns = Solver::new(moves)
+            .with_metric(&QuarterTurn::new())
+            .with_max_depth(3);
+
+        let solution = half_turns.solve(&state).unwrap();
+        assert_eq!(solution.length_in(&HalfTurn::new()), 2);
+        assert_eq!(solution.length_in(&QuarterTurn::new()), 4);
+        assert_eq!(quarter_turns.solve(&state), None);
+    }
+
+    #[test]
+    fn macro_moves_should_not_be_pruned() {
+        let puzzle = models::cube2();
+        let moves = moves(&puzzle, &["R U R'", "R U' R'", "R", "R'"]).unwrap();
+        let state = puzzle
+            .sequence("R U")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+
+        assert!(!moves[2].continues(&moves[0]));
+        assert!(moves[2].is_canonical_after(&moves[0], &puzzle.move_rules()));
+        assert!(moves[3].continues(&moves[2]));
+        let solver = Solver::new(moves)
+            .with_move_rules(puzzle.move_rules())
+            .with_max_depth(2);
+        let solution = solver.solve(&state).unwrap();
+        assert!(state
+            .times(&solution.evaluate(&puzzle.move_images()))
+            .is_identity());
+    }
+
+    #[test]
+    fn canonical_sequences_should_need_fewer_nodes() {
+        let puzzle = models::cube3();
+        let state = puzzle
+            .sequence("F B")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+
+        let plain = Solver::new(face_turns(&puzzle)).with_max_nodes(3500);
+        let canonical = Solver::new(face_turns(&puzzle))
+            .with_move_rules(puzzle.move_rules())
+            .with_max_nodes(3500);
+
+        assert!(plain.try_solve(&state).is_err());
+        assert_eq!(
+            canonical.solve(&state),
+            Some(puzzle.sequence("F' B'").unwrap().to_word())
+        );
+    }
+
+    #[test]
+    fn solver_should_report_an_exceeded_node_limit() {
+        let puzzle = models::cube2();
+        let solver = Solver::new(face_turns(&puzzle)).with_max_nodes(100);
+        let state = puzzle
+            .sequence("R U2 F' R")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+
+        let error = solver.try_solve(&state).unwrap_err();
+
+        assert_eq!(error.nodes, 100);
+        assert!(error.bound < 4);
+        assert_eq!(solver.solve(&state), None);
+    }
+}
@@ -0,0 +1,132 @@
+//! Search with moves that are not legal in every state.
+//!
+//! On a bandaged puzzle some pieces are glued together, so a move that would
+//! split them is blocked until other moves bring the pieces out of its way.
+//! Such moves form a `PartialGroupAction` on the states of the puzzle rather
+//! than a group. A `PartialProblem` searches the states the legal moves reach:
+//! a move is only tried in a state where it acts.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::group::PartialGroupAction;
+//! # use permutation_rs::solver::{ida_star, partial::PartialProblem};
+//! /// A step along a track of 6 positions, blocked at the ends.
+//! struct Step(i64);
+//!
+//! impl PartialGroupAction for Step {
+//!     type Domain = i64;
+//!
+//!     fn try_act_on(&self, position: &i64) -> Option<i64> {
+//!         let next = position + self.0;
+//!         if 0 <= next && next < 6 { Some(next) } else { None }
+//!     }
+//! }
+//!
+//! let steps = [Step(4), Step(-1)];
+//! let problem = PartialProblem::new(&steps, |position: &i64| *position == 5);
+//!
+//! assert_eq!(ida_star(&problem, &3, 10), Some(vec![1, 1, 0]));
+//! ```
+
+use super::super::group::PartialGroupAction;
+use super::search::Problem;
+
+/// The search for a sequence of partial `actions` that reaches a goal.
+pub struct PartialProblem<'a, A, F> {
+    actions: &'a [A],
+    goal: F,
+}
+
+impl<'a, A, F> PartialProblem<'a, A, F>
+where
+    A: PartialGroupAction,
+    F: Fn(&A::Domain) -> bool,
+{
+    /// Search with `actions` for a state that satisfies `goal`.
+    pub fn new(actions: &'a [A], goal: F) -> PartialProblem<'a, A, F> {
+        PartialProblem { actions, goal }
+    }
+}
+
+impl<'a, A, F> Problem for PartialProblem<'a, A, F>
+where
+    A: PartialGroupAction,
+    F: Fn(&A::Domain) -> bool,
+{
+    type State = A::Domain;
+
+    fn moves(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Panics when the move at `index` is not legal in `state`.
+    fn apply(&self, state: &A::Domain, index: usize) -> A::Domain {
+        self.try_apply(state, index)
+            .expect("move should be legal in the state")
+    }
+
+    fn try_apply(&self, state: &A::Domain, index: usize) -> Option<A::Domain> {
+        self.actions[index].try_act_on(state)
+    }
+
+    fn is_goal(&self, state: &A::Domain) -> bool {
+        (self.goal)(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::group::permutation::Permutation;
+    use super::super::super::group::GroupAction;
+    use super::super::search::ida_star;
+    use super::*;
+    use std::collections::HashMap;
+
+    /// A rotation of the positions of two pieces, which is blocked while the
+    /// piece `guard` is at position `blocked`.
+    struct Guarded {
+        rotation: Permutation,
+        guard: usize,
+        blocked: u64,
+    }
+
+    impl PartialGroupAction for Guarded {
+        type Domain = Vec<u64>;
+
+        fn try_act_on(&self, positions: &Vec<u64>) -> Option<Vec<u64>> {
+            if positions[self.guard] == self.blocked {
+                return None;
+            }
+            Some(positions.iter().map(|p| self.rotation.act_on(p)).collect())
+        }
+    }
+
+    #[test]
+    fn search_should_only_make_legal_moves() {
+        let actions = [
+            Guarded {
+                rotation: permute!(0, 1, 1, 2, 2, 0),
+                guard: 1,
+                blocked: 4,
+            },
+            Guarded {
+                rotation: permute!(3, 4, 4, 5, 5, 3),
+                guard: 0,
+                blocked: 1,
+            },
+        ];
+        let problem = PartialProblem::new(&actions, |positions: &Vec<u64>| positions[0] == 0);
+
+        assert_eq!(ida_star(&problem, &vec![1, 3], 10), Some(vec![0, 0]));
+        assert_eq!(ida_star(&problem, &vec![2, 4], 10), Some(vec![1, 0]));
+        assert_eq!(ida_star(&problem, &vec![1, 4], 10), None);
+    }
+
+    #[test]
+    fn group_actions_should_be_defined_everywhere() {
+        let rotation = permute!(0, 1, 1, 2, 2, 0);
+
+        assert_eq!(rotation.try_act_on(&0), Some(1));
+        assert_eq!(rotation.try_act_on(&7), Some(7));
+    }
+}
@@ -0,0 +1,232 @@
+//! [Pattern databases](https://en.wikipedia.org/wiki/Pattern_database) that
+//! guide a search.
+//!
+//! A pattern database projects a state onto a few of its stickers: it only
+//! records the positions of those stickers. The projected states are explored
+//! breadth first from the solved state, and for each the number of moves is
+//! stored. Solving the whole state takes at least as many moves as solving the
+//! projection, so the stored distance is an admissible estimate for IDA*.
+//!
+//! A database can be written to and read from a simple text format, so it
+//! only has to be built once. The first line lists the projected points, every
+//! following line lists the positions of a projected state and its distance.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::puzzle::models;
+//! # use permutation_rs::solver;
+//! # use permutation_rs::solver::pattern::{PatternDb, Projection};
+//! let puzzle = models::cube2();
+//! let moves = solver::face_turns(&puzzle);
+//! let projection = Projection::new(vec![puzzle.sticker("U8").unwrap()]);
+//!
+//! let database = PatternDb::build(projection, &moves);
+//!
+//! assert_eq!(database.len(), 21);
+//! ```
+
+use super::super::group::permutation::Permutation;
+use super::super::group::GroupAction;
+use super::Move;
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::io::{BufRead, Write};
+
+/// The points a state is projected onto.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projection {
+    points: Vec<u64>,
+}
+
+impl Projection {
+    /// Project onto `points`.
+    pub fn new(points: Vec<u64>) -> Projection {
+        Projection { points }
+    }
+
+    /// The points that are projected onto.
+    pub fn points(&self) -> &[u64] {
+        &self.points
+    }
+
+    /// The positions of the projected points in `state`.
+    pub fn project(&self, state: &Permutation) -> Vec<u64> {
+        self.points
+            .iter()
+            .map(|point| state.act_on(point))
+            .collect()
+    }
+}
+
+/// The distances of all projected states reachable from the solved state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternDb {
+    projection: Projection,
+    distances: HashMap<Vec<u64>, u8>,
+}
+
+impl PatternDb {
+    /// Explore the projected states reachable with `moves` breadth first.
+    pub fn build(projection: Projection, moves: &[Move]) -> PatternDb {
+        let mut distances: HashMap<Vec<u64>, u8> = HashMap::new();
+        let mut to_visit: VecDeque<Vec<u64>> = VecDeque::new();
+        distances.insert(projection.points.clone(), 0);
+        to_visit.push_back(projection.points.clone());
+        while let Some(positions) = to_visit.pop_front() {
+            let distance = distances[&positions];
+            for m in moves {
+                let next: Vec<u64> = positions
+                    .iter()
+                    .map(|position| m.permutation.act_on(position))
+                    .collect();
+                if !distances.contains_key(&next) {
+                    distances.insert(next.clone(), distance + 1);
+                    to_visit.push_back(next);
+                }
+            }
+        }
+        PatternDb {
+            projection,
+            distances,
+        }
+    }
+
+    /// The projection of this database.
+    pub fn projection(&self) -> &Projection {
+        &self.projection
+    }
+
+    /// The number of projected states.
+    pub fn len(&self) -> usize {
+        self.distances.len()
+    }
+
+    /// A database always contains the solved state, so it is never empty.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The largest distance in this database.
+    pub fn max_distance(&self) -> usize {
+        self.distances.values().cloned().max().unwrap_or(0) as usize
+    }
+
+    /// The number of moves needed to solve the projection of `state`, or
+    /// `None` when that projection can not be solved.
+    pub fn distance(&self, state: &Permutation) -> Option<usize> {
+        self.distances
+            .get(&self.projection.project(state))
+            .map(|distance| *distance as usize)
+    }
+
+    /// Write this database in its text format.
+    pub fn write<W>(&self, mut writer: W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        writeln!(writer, "{}", join(&self.projection.points))?;
+        let mut entries: Vec<(&Vec<u64>, &u8)> = self.distances.iter().collect();
+        entries.sort();
+        for (positions, distance) in entries {
+            writeln!(writer, "{} {}", join(positions), distance)?;
+        }
+        Ok(())
+    }
+
+    /// Read a database in its text format.
+    pub fn read<R>(reader: R) -> io::Result<PatternDb>
+    where
+        R: BufRead,
+    {
+        let mut lines = reader.lines();
+        let points = parse(&lines.next().unwrap_or_else(|| Ok(String::new()))?)?;
+        let mut distances: HashMap<Vec<u64>, u8> = HashMap::new();
+        for line in lines {
+            let mut numbers = parse(&line?)?;
+            if numbers.len() != points.len() + 1 {
+                return Err(invalid("entry does not match projection"));
+            }
+            let distance = numbers.pop().expect("entry should have a distance");
+            if distance > u8::MAX as u64 {
+                return Err(invalid("distance is too large"));
+            }
+            distances.insert(numbers, distance as u8);
+        }
+        Ok(PatternDb {
+            projection: Projection::new(points),
+            distances,
+        })
+    }
+}
+
+/// The largest distance that `databases` assign to `state`. States that one of
+/// the databases can not solve get `usize::MAX`.
+pub fn estimate(databases: &[PatternDb], state: &Permutation) -> usize {
+    databases
+        .iter()
+        .map(|database| database.distance(state).unwrap_or(usize::MAX))
+        .max()
+        .unwrap_or(0)
+}
+
+fn join(numbers: &[u64]) -> String {
+    numbers
+        .iter()
+        .map(|number| number.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn parse(line: &str) -> io::Result<Vec<u64>> {
+    line.split_whitespace()
+        .map(|number| number.parse().map_err(|_| invalid("expected a number")))
+        .collect()
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::puzzle::models;
+    use super::super::face_turns;
+    use super::*;
+
+    fn corners() -> PatternDb {
+        let puzzle = models::cube2();
+        let points = ["U3", "U6", "U8"]
+            .iter()
+            .map(|label| puzzle.sticker(label).unwrap())
+            .collect();
+        PatternDb::build(Projection::new(points), &face_turns(&puzzle))
+    }
+
+    #[test]
+    fn database_should_count_moves_to_the_solved_projection() {
+        let puzzle = models::cube2();
+        let database = corners();
+
+        assert_eq!(database.len(), 21 * 18 * 15);
+        let r = puzzle.move_named("R").unwrap();
+        assert_eq!(database.distance(r), Some(1));
+    }
+
+    #[test]
+    fn database_should_survive_writing_and_reading() {
+        let database = corners();
+        let mut text: Vec<u8> = vec![];
+        database.write(&mut text).unwrap();
+
+        let read = PatternDb::read(&text[..]).unwrap();
+
+        assert_eq!(read, database);
+    }
+
+    #[test]
+    fn reading_should_reject_malformed_entries() {
+        let text = "1 2\n3 4 5\n6 7\n";
+
+        assert!(PatternDb::read(text.as_bytes()).is_err());
+    }
+}
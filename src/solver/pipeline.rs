@@ -0,0 +1,293 @@
+//! Solve in stages, each into a smaller subgroup.
+//!
+//! Human methods and algorithms like Thistlethwaite's solve a puzzle in
+//! stages. Every stage brings the state into a subgroup, using only moves that
+//! keep the progress of the stages before it. The last stage ends in the
+//! solved state. A `Pipeline` is such a list of `Stage`s; it searches a
+//! shortest sequence for every stage in turn and chains them into one
+//! solution.
+//!
+//! A stage tells that its target is reached either with a label that is
+//! constant on the cosets of the target subgroup, like `TwoPhase` does, or
+//! with the stabilizer chain of the subgroup. An estimate of the remaining
+//! distance, e.g. from a lookup table of the cosets, speeds up its search.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::puzzle::models;
+//! # use permutation_rs::solver::{self, pipeline::{Pipeline, Stage}};
+//! let puzzle = models::cube2();
+//! let u = puzzle.move_named("U").unwrap().clone();
+//! let layer = Group::with_base(puzzle.gset(), vec![], vec![u]);
+//! let pipeline = Pipeline::new()
+//!     .with_stage(Stage::into_group("layer", solver::face_turns(&puzzle), layer))
+//!     .with_stage(Stage::new(
+//!         "solve",
+//!         solver::moves(&puzzle, &["U", "U2", "U'"]).unwrap(),
+//!         |state| state.clone(),
+//!     ));
+//! let state = puzzle.sequence("U2 R").unwrap().permutation(&puzzle).unwrap();
+//!
+//! let solution = pipeline.solve(&state).unwrap();
+//!
+//! assert_eq!(solution.stages[0].1.to_string(), "R^-1");
+//! assert_eq!(solution.stages[1].1.to_string(), "U^2");
+//! ```
+
+use super::super::group::free::{Metric, Word};
+use super::super::group::peephole::MoveRules;
+use super::super::group::permutation::Permutation;
+use super::super::group::{Group, GroupElement};
+use super::search::ida_star;
+use super::{word, Move, Phase};
+
+/// A stage of a pipeline: the moves it may use and the subgroup it aims for.
+pub struct Stage {
+    name: String,
+    moves: Vec<Move>,
+    costs: Vec<usize>,
+    goal: Box<dyn Fn(&Permutation) -> bool>,
+    estimate: Box<dyn Fn(&Permutation) -> usize>,
+    max_depth: usize,
+}
+
+impl Stage {
+    /// A stage that uses `moves` to reach the states with the label of the
+    /// solved state. `label` should be constant on the cosets of the target
+    /// subgroup and differ between them.
+    pub fn new<F, L>(name: &str, moves: Vec<Move>, label: F) -> Stage
+    where
+        F: Fn(&Permutation) -> L + 'static,
+        L: PartialEq + 'static,
+    {
+        let target = label(&Permutation::identity());
+        Stage::with_goal(name, moves, move |state| label(state) == target)
+    }
+
+    /// A stage that uses `moves` to reach the elements of `group`.
+    pub fn into_group(name: &str, moves: Vec<Move>, group: Group<u64, Permutation>) -> Stage {
+        Stage::with_goal(name, moves, move |state| group.contains(state))
+    }
+
+    fn with_goal<F>(name: &str, moves: Vec<Move>, goal: F) -> Stage
+    where
+        F: Fn(&Permutation) -> bool + 'static,
+    {
+        Stage {
+            name: name.to_string(),
+            costs: vec![1; moves.len()],
+            moves,
+            goal: Box::new(goal),
+            estimate: Box::new(|_| 0),
+            max_depth: 20,
+        }
+    }
+
+    /// Guide the search of this stage with `estimate`, a lower bound on the
+    /// number of moves to its target.
+    pub fn with_estimate<F>(mut self, estimate: F) -> Stage
+    where
+        F: Fn(&Permutation) -> usize + 'static,
+    {
+        self.estimate = Box::new(estimate);
+        self
+    }
+
+    /// Count the moves of this stage in `metric`, like `Solver::with_metric`.
+    /// Every move costs at least 1, so an estimate that counts moves still
+    /// underestimates.
+    pub fn with_metric<M>(mut self, metric: &M) -> Stage
+    where
+        M: Metric<String> + ?Sized,
+    {
+        self.costs = self
+            .moves
+            .iter()
+            .map(|turn| (turn.word.length_in(metric) as usize).max(1))
+            .collect();
+        self
+    }
+
+    /// Limit the length of the sequence of this stage, counted in its metric.
+    /// Defaults to 20.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Stage {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// The name of this stage.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The moves this stage may use.
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Determine if `state` is in the target of this stage.
+    pub fn is_reached(&self, state: &Permutation) -> bool {
+        (self.goal)(state)
+    }
+}
+
+/// Stages that are solved one after the other.
+pub struct Pipeline {
+    stages: Vec<Stage>,
+    rules: Option<MoveRules<String>>,
+}
+
+impl Pipeline {
+    /// A pipeline without stages.
+    pub fn new() -> Pipeline {
+        Pipeline {
+            stages: vec![],
+            rules: None,
+        }
+    }
+
+    /// Add `stage` after the stages of this pipeline. Its moves should keep
+    /// the states in the targets of the earlier stages.
+    pub fn with_stage(mut self, stage: Stage) -> Pipeline {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Only search canonical sequences under `rules` in every stage, like
+    /// `Solver::with_move_rules`.
+    pub fn with_move_rules(mut self, rules: MoveRules<String>) -> Pipeline {
+        self.rules = Some(rules);
+        self
+    }
+
+    /// The stages of this pipeline, in order.
+    pub fn stages(&self) -> &[Stage] {
+        &self.stages
+    }
+
+    /// Solve `state` stage by stage. Returns `None` when a stage finds no
+    /// sequence within its maximum depth.
+    pub fn solve(&self, state: &Permutation) -> Option<Solution> {
+        let mut current = state.clone();
+        let mut stages = vec![];
+        for stage in &self.stages {
+            let phase = Phase {
+                moves: &stage.moves,
+                costs: &stage.costs,
+                rules: self.rules.as_ref(),
+                goal: &*stage.goal,
+                databases: &[],
+                perimeter: None,
+                estimate: Some(&*stage.estimate),
+            };
+            let path = ida_star(&phase, &current, stage.max_depth)?;
+            current = path.iter().fold(current, |current, index| {
+                current.times(&stage.moves[*index].permutation)
+            });
+            stages.push((stage.name.clone(), word(&stage.moves, &path)));
+        }
+        Some(Solution { stages })
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Pipeline {
+        Pipeline::new()
+    }
+}
+
+/// The sequences found by the stages of a `Pipeline`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    /// The name of every stage with the sequence it found, in order.
+    pub stages: Vec<(String, Word<String>)>,
+}
+
+impl Solution {
+    /// The complete solution: the sequences of all stages, one after the
+    /// other.
+    pub fn word(&self) -> Word<String> {
+        self.stages
+            .iter()
+            .fold(Word::identity(), |word, (_, stage)| word.times(stage))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::group::free::QuarterTurn;
+    use super::super::super::puzzle::models;
+    use super::super::{face_turns, moves};
+    use super::*;
+
+    #[test]
+    fn pipeline_should_chain_its_stages() {
+        let puzzle = models::cube2();
+        let u = puzzle.move_named("U").unwrap().clone();
+        let r = puzzle.move_named("R").unwrap().clone();
+        let pipeline = Pipeline::new()
+            .with_stage(Stage::into_group(
+                "two faces",
+                face_turns(&puzzle),
+                Group::with_base(puzzle.gset(), vec![], vec![u, r]),
+            ))
+            .with_stage(Stage::new(
+                "solve",
+                moves(&puzzle, &["U", "U2", "U'", "R", "R2", "R'"]).unwrap(),
+                |state| state.clone(),
+            ))
+            .with_move_rules(puzzle.move_rules());
+        let state = puzzle
+            .sequence("R U R' F")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+
+        let solution = pipeline.solve(&state).unwrap();
+
+        assert_eq!(solution.stages.len(), 2);
+        assert!(pipeline.stages()[0]
+            .is_reached(&state.times(&solution.stages[0].1.evaluate(&puzzle.move_images()))));
+        assert!(state
+            .times(&solution.word().evaluate(&puzzle.move_images()))
+            .is_identity());
+    }
+
+    #[test]
+    fn stages_should_count_moves_in_their_metric() {
+        let puzzle = models::cube2();
+        let stage = |name| Stage::new(name, face_turns(&puzzle), |state| state.clone());
+        let half_turns = Pipeline::new().with_stage(stage("solve").with_max_depth(1));
+        let quarter_turns = Pipeline::new().with_stage(
+            stage("solve")
+                .with_metric(&QuarterTurn::new())
+                .with_max_depth(1),
+        );
+        let state = puzzle.sequence("R2").unwrap().permutation(&puzzle).unwrap();
+
+        assert!(half_turns.solve(&state).is_some());
+        assert_eq!(quarter_turns.solve(&state), None);
+    }
+
+    #[test]
+    fn pipeline_should_fail_when_a_stage_is_too_deep() {
+        let puzzle = models::cube2();
+        let pipeline = Pipeline::new().with_stage(
+            Stage::new("solve", face_turns(&puzzle), |state| state.clone()).with_max_depth(1),
+        );
+        let state = puzzle
+            .sequence("R U")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+
+        assert_eq!(pipeline.solve(&state), None);
+        assert_eq!(
+            pipeline
+                .solve(&Permutation::identity())
+                .map(|solution| solution.word()),
+            Some(Word::identity())
+        );
+    }
+}
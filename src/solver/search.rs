@@ -0,0 +1,300 @@
+//! [Iterative deepening A*](https://en.wikipedia.org/wiki/Iterative_deepening_A*)
+//! search.
+//!
+//! The search explores sequences of moves depth first, up to a bound on the
+//! number of moves plus the estimated number of remaining moves. When no goal is
+//! found the bound is raised and the search starts over. As long as the
+//! estimate never exceeds the real distance, the first solution found is a
+//! shortest one. When moves have a cost, e.g. a half turn that counts as two
+//! quarter turns, the bound is on the cost instead and the first solution found
+//! is a cheapest one.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::solver::{ida_star, Problem};
+//! struct Counter;
+//!
+//! impl Problem for Counter {
+//!     type State = i64;
+//!
+//!     fn moves(&self) -> usize { 2 }
+//!     fn apply(&self, state: &i64, index: usize) -> i64 {
+//!         if index == 0 { state + 1 } else { state * 2 }
+//!     }
+//!     fn is_goal(&self, state: &i64) -> bool { *state == 10 }
+//! }
+//!
+//! assert_eq!(ida_star(&Counter, &1, 10), Some(vec![0, 1, 0, 1]));
+//! ```
+
+use std::error;
+use std::fmt::{self, Display, Formatter};
+
+/// A search problem: states, the moves between them and the goal.
+pub trait Problem {
+    /// The states that are searched.
+    type State;
+
+    /// The number of moves. Moves are referred to by their index.
+    fn moves(&self) -> usize;
+
+    /// The state that results from making the move at `index` in `state`.
+    fn apply(&self, state: &Self::State, index: usize) -> Self::State;
+
+    /// The state that results from making the move at `index` in `state`, or
+    /// `None` when that move is not legal in `state`. Defaults to `apply`, so
+    /// every move is legal in every state.
+    fn try_apply(&self, state: &Self::State, index: usize) -> Option<Self::State> {
+        Some(self.apply(state, index))
+    }
+
+    /// Determine if `state` is a goal.
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    /// The cost of the move at `index`. Defaults to 1, so the search minimizes
+    /// the number of moves. Costs should be at least 1.
+    fn cost(&self, _index: usize) -> usize {
+        1
+    }
+
+    /// A lower bound on the cost of the moves from `state` to a goal. Defaults
+    /// to 0, which turns the search into plain iterative deepening.
+    fn estimate(&self, _state: &Self::State) -> usize {
+        0
+    }
+
+    /// Determine if the move at `next` may follow the move at `previous`. Use
+    /// this to prune sequences that have a shorter equivalent.
+    fn may_follow(&self, _previous: usize, _next: usize) -> bool {
+        true
+    }
+}
+
+/// Find a cheapest sequence of moves, by index, from `start` to a goal of
+/// `problem`, with a cost of at most `max_depth`. With the default costs that
+/// is a shortest sequence with at most `max_depth` moves.
+pub fn ida_star<P>(problem: &P, start: &P::State, max_depth: usize) -> Option<Vec<usize>>
+where
+    P: Problem,
+{
+    ida_star_limited(problem, start, max_depth, u64::MAX).unwrap_or(None)
+}
+
+/// Like `ida_star`, but give up after visiting `max_nodes` states.
+///
+/// When the search gives up, the error tells how far it got: no solution
+/// needs fewer moves than its `bound`.
+pub fn ida_star_limited<P>(
+    problem: &P,
+    start: &P::State,
+    max_depth: usize,
+    max_nodes: u64,
+) -> Result<Option<Vec<usize>>, NodeLimitExceeded>
+where
+    P: Problem,
+{
+    let mut bound = problem.estimate(start);
+    let mut path: Vec<usize> = vec![];
+    let mut budget = Budget {
+        nodes: 0,
+        max_nodes,
+    };
+    while bound <= max_depth {
+        match depth_first(problem, start, &mut path, 0, bound, &mut budget) {
+            Outcome::Found => return Ok(Some(path)),
+            Outcome::Exceeded(next) => bound = next,
+            Outcome::Exhausted => return Ok(None),
+            Outcome::Aborted => {
+                return Err(NodeLimitExceeded {
+                    nodes: budget.nodes,
+                    bound,
+                })
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// A search that visited more states than it was allowed to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeLimitExceeded {
+    /// The number of states that were visited.
+    pub nodes: u64,
+    /// The bound of the last iteration. Every solution has at least this many
+    /// moves.
+    pub bound: usize,
+}
+
+impl Display for NodeLimitExceeded {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "search gave up after {} nodes, no solution has fewer than {} moves",
+            self.nodes, self.bound
+        )
+    }
+}
+
+impl error::Error for NodeLimitExceeded {}
+
+/// The number of states visited and the number of states that may be visited.
+struct Budget {
+    nodes: u64,
+    max_nodes: u64,
+}
+
+enum Outcome {
+    Found,
+    Exceeded(usize),
+    Exhausted,
+    Aborted,
+}
+
+fn depth_first<P>(
+    problem: &P,
+    state: &P::State,
+    path: &mut Vec<usize>,
+    cost: usize,
+    bound: usize,
+    budget: &mut Budget,
+) -> Outcome
+where
+    P: Problem,
+{
+    if budget.nodes >= budget.max_nodes {
+        return Outcome::Aborted;
+    }
+    budget.nodes += 1;
+    let total = cost.saturating_add(problem.estimate(state));
+    if total > bound {
+        return Outcome::Exceeded(total);
+    }
+    if problem.is_goal(state) {
+        return Outcome::Found;
+    }
+    let mut next_bound: Option<usize> = None;
+    for index in 0..problem.moves() {
+        if let Some(&previous) = path.last() {
+            if !problem.may_follow(previous, index) {
+                continue;
+            }
+        }
+        let next = match problem.try_apply(state, index) {
+            Some(next) => next,
+            None => continue,
+        };
+        path.push(index);
+        let cost = cost.saturating_add(problem.cost(index));
+        match depth_first(problem, &next, path, cost, bound, budget) {
+            Outcome::Found => return Outcome::Found,
+            Outcome::Aborted => return Outcome::Aborted,
+            Outcome::Exceeded(total) => {
+                next_bound = Some(next_bound.map_or(total, |current| current.min(total)));
+            }
+            Outcome::Exhausted => {}
+        }
+        path.pop();
+    }
+    match next_bound {
+        Some(total) => Outcome::Exceeded(total),
+        None => Outcome::Exhausted,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Line {
+        length: i64,
+    }
+
+    impl Problem for Line {
+        type State = i64;
+
+        fn moves(&self) -> usize {
+            2
+        }
+
+        fn apply(&self, state: &i64, index: usize) -> i64 {
+            if index == 0 {
+                (state + 1).min(self.length)
+            } else {
+                (state - 1).max(0)
+            }
+        }
+
+        fn is_goal(&self, state: &i64) -> bool {
+            *state == self.length
+        }
+
+        fn estimate(&self, state: &i64) -> usize {
+            (self.length - state) as usize
+        }
+    }
+
+    #[test]
+    fn search_should_find_a_shortest_path() {
+        let problem = Line { length: 5 };
+
+        assert_eq!(ida_star(&problem, &2, 10), Some(vec![0, 0, 0]));
+    }
+
+    #[test]
+    fn search_should_find_a_cheapest_path() {
+        struct Costly(Line);
+
+        impl Problem for Costly {
+            type State = i64;
+
+            fn moves(&self) -> usize {
+                3
+            }
+
+            fn apply(&self, state: &i64, index: usize) -> i64 {
+                match index {
+                    2 => (state + 2).min(self.0.length),
+                    _ => self.0.apply(state, index),
+                }
+            }
+
+            fn cost(&self, index: usize) -> usize {
+                if index == 2 {
+                    3
+                } else {
+                    1
+                }
+            }
+
+            fn is_goal(&self, state: &i64) -> bool {
+                self.0.is_goal(state)
+            }
+        }
+
+        let problem = Costly(Line { length: 5 });
+
+        assert_eq!(ida_star(&problem, &2, 10), Some(vec![0, 0, 0]));
+        assert_eq!(ida_star(&problem, &2, 2), None);
+    }
+
+    #[test]
+    fn search_should_respect_the_maximum_depth() {
+        let problem = Line { length: 5 };
+
+        assert_eq!(ida_star(&problem, &0, 4), None);
+    }
+
+    #[test]
+    fn search_should_give_up_beyond_the_node_limit() {
+        let problem = Line { length: 5 };
+
+        assert_eq!(
+            ida_star_limited(&problem, &2, 10, 4),
+            Ok(Some(vec![0, 0, 0]))
+        );
+        assert_eq!(
+            ida_star_limited(&problem, &2, 10, 3),
+            Err(NodeLimitExceeded { nodes: 3, bound: 3 })
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! Reduce states by the symmetries of a puzzle.
+//!
+//! A symmetry of a puzzle, e.g. a rotation of the whole cube, relabels its
+//! states: the state _s_^-1 _x_ _s_ is _x_ seen from another side. Such states
+//! are equally far from solved when the symmetry maps the moves onto moves.
+//! `Symmetry` picks one canonical state among them, so a pattern database or a
+//! breadth first search only needs to store that one. A solution of the
+//! canonical state is restored to a solution of the original state by
+//! relabeling its moves.
+//!
+//! # Examples
+//! ```rust
+//! # #[macro_use] extern crate permutation_rs;
+//! # use std::collections::HashMap;
+//! # use permutation_rs::group::{Group, GroupElement};
+//! # use permutation_rs::group::permutation::Permutation;
+//! # use permutation_rs::solver::symmetry::Symmetry;
+//! # fn main() {
+//! let rotation = permute!(0, 1, 1, 2, 2, 3, 3, 0);
+//! let symmetry = Symmetry::new(&Group::with_base(vec![0, 1, 2, 3], vec![], vec![rotation.clone()]));
+//! let state = permute!(0, 1, 1, 0, 2, 2, 3, 3);
+//!
+//! let (canonical, index) = symmetry.canonicalize(&state);
+//!
+//! assert_eq!(symmetry.canonicalize(&state.conjugate_by(&rotation)).0, canonical);
+//! assert_eq!(symmetry.restore(&canonical, index), state);
+//! # }
+//! ```
+
+use super::super::group::permutation::Permutation;
+use super::super::group::{Group, GroupAction, GroupElement};
+use super::Move;
+
+/// The symmetries of a puzzle, to canonicalize its states with.
+pub struct Symmetry {
+    symmetries: Vec<Permutation>,
+    inverses: Vec<Permutation>,
+}
+
+impl Symmetry {
+    /// The symmetries that are the elements of `group`.
+    pub fn new(group: &Group<u64, Permutation>) -> Symmetry {
+        let symmetries = group.elements();
+        let inverses = symmetries.iter().map(|s| s.inverse()).collect();
+        Symmetry {
+            symmetries,
+            inverses,
+        }
+    }
+
+    /// The number of symmetries.
+    pub fn order(&self) -> usize {
+        self.symmetries.len()
+    }
+
+    /// The symmetry at `index`.
+    pub fn symmetry(&self, index: usize) -> &Permutation {
+        &self.symmetries[index]
+    }
+
+    /// The smallest conjugate _s_^-1 `state` _s_ by a symmetry _s_, comparing
+    /// the images of the points in order, together with the index of _s_.
+    ///
+    /// States that are conjugate by a symmetry have the same canonical state.
+    /// A group without symmetries leaves `state` as it is.
+    pub fn canonicalize(&self, state: &Permutation) -> (Permutation, usize) {
+        let mut best: Option<(Vec<u64>, Permutation, usize)> = None;
+        for (index, (symmetry, inverse)) in self.symmetries.iter().zip(&self.inverses).enumerate() {
+            let conjugate = inverse.times(state).times(symmetry);
+            let key = key(&conjugate);
+            let smaller = best.as_ref().is_none_or(|(known, _, _)| &key < known);
+            if smaller {
+                best = Some((key, conjugate, index));
+            }
+        }
+        match best {
+            Some((_, canonical, index)) => (canonical, index),
+            None => (state.clone(), 0),
+        }
+    }
+
+    /// The state that `canonicalize` turned into `canonical` with the
+    /// symmetry at `index`.
+    pub fn restore(&self, canonical: &Permutation, index: usize) -> Permutation {
+        self.symmetries[index]
+            .times(canonical)
+            .times(&self.inverses[index])
+    }
+
+    /// The move among `moves` that takes the place of `m` when a solution of
+    /// a canonical state is restored with the symmetry at `index`, or `None`
+    /// when the symmetry does not map `m` onto one of `moves`.
+    pub fn restore_move(&self, m: &Move, moves: &[Move], index: usize) -> Option<Move> {
+        let image = self.symmetries[index]
+            .times(&m.permutation)
+            .times(&self.inverses[index]);
+        moves
+            .iter()
+            .find(|other| other.permutation == image)
+            .cloned()
+    }
+}
+
+/// The images of the points of `permutation` in increasing order of the points.
+fn key(permutation: &Permutation) -> Vec<u64> {
+    let mut domain = permutation.domain().to_vec();
+    domain.sort_unstable();
+    domain
+        .iter()
+        .map(|point| permutation.act_on(point))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::group::free::Word;
+    use super::super::super::puzzle::models;
+    use super::super::super::puzzle::Puzzle;
+    use super::super::{face_turns, Solver};
+    use super::*;
+
+    /// The rotation of the whole 3x3x3 cube in the direction of `U`.
+    fn y(puzzle: &Puzzle) -> Permutation {
+        let mut rotation = puzzle.clone();
+        let mut cycles = String::from("(U1 U3 U8 U6)(U2 U5 U7 U4)(D6 D8 D3 D1)(D4 D7 D5 D2)");
+        for i in 1..9 {
+            cycles.push_str(&format!("(F{0} L{0} B{0} R{0})", i));
+        }
+        rotation.add_move("Y", &cycles).unwrap();
+        rotation.move_named("Y").unwrap().clone()
+    }
+
+    #[test]
+    fn rotated_states_should_have_the_same_canonical_state() {
+        let puzzle = models::cube3();
+        let y = y(&puzzle);
+        let symmetry = Symmetry::new(&Group::with_base(puzzle.gset(), vec![], vec![y.clone()]));
+        let state = puzzle
+            .sequence("R U F'")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+
+        let (canonical, index) = symmetry.canonicalize(&state);
+
+        assert_eq!(symmetry.order(), 4);
+        for rotated in &[state.conjugate_by(&y), state.conjugate_by(&y.inverse())] {
+            assert_eq!(symmetry.canonicalize(rotated).0, canonical);
+        }
+        assert_eq!(symmetry.restore(&canonical, index), state);
+    }
+
+    #[test]
+    fn restored_moves_should_solve_the_original_state() {
+        let puzzle = models::cube3();
+        let moves = face_turns(&puzzle);
+        let symmetry = Symmetry::new(&Group::with_base(puzzle.gset(), vec![], vec![y(&puzzle)]));
+        let state = puzzle
+            .sequence("R U")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+        let (canonical, index) = symmetry.canonicalize(&state);
+        let solution = Solver::new(moves.clone()).solve(&canonical).unwrap();
+
+        let restored = solution
+            .iter()
+            .map(|(name, exponent)| {
+                let word = Word::new(vec![(name.clone(), *exponent)]);
+                let m = moves.iter().find(|m| m.word == word).unwrap();
+                symmetry.restore_move(m, &moves, index).unwrap()
+            })
+            .fold(state, |current, m| current.times(&m.permutation));
+
+        assert_eq!(solution.syllable_count(), 2);
+        assert!(restored.is_identity());
+    }
+}
@@ -0,0 +1,541 @@
+//! Lookup tables of the distance of every coset to a subgroup.
+//!
+//! A stage of a pipeline, or the first phase of `TwoPhase`, brings a state into
+//! a subgroup _H_. Whether a sequence of moves does so only depends on the
+//! right coset _Hs_ of the state, so the distance of every coset to _H_ is an
+//! exact estimate for such a stage, and an admissible estimate for any search
+//! that has to pass through _H_, like a pattern database.
+//!
+//! A `CosetTable` explores the cosets breadth first. A `CosetLabel` tells
+//! which coset a state is in, with a number that is the same for every state of
+//! the coset. `SubgroupCosets` labels a coset by the rank in the group of its
+//! canonical representative, see `Group::right_coset_representative`. When the
+//! subgroup consists of the states that keep a coloring of the stickers, like
+//! the subgroups of Thistlethwaite's algorithm, `ColoringCosets` labels a coset
+//! by the coloring of its states instead, which is a lot faster. The labels are
+//! stored sorted, and a binary search finds the position of a label, which
+//! numbers the cosets from 0 up to the index of the subgroup.
+//!
+//! A table can be written to and read from a binary format, so it only has to
+//! be built once. The format is a magic number, the fingerprint of the
+//! labeling, the number of cosets, the sorted labels as `u128` and the
+//! distances as `u16`. Integers are little endian. Reading checks the
+//! fingerprint, so a table is never used with another labeling than it was
+//! built with. With the `memmap2` feature `CosetTable::map` maps a file into
+//! memory instead of reading it, which makes loading a large table instant.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::group::Group;
+//! # use permutation_rs::puzzle::models;
+//! # use permutation_rs::solver;
+//! # use permutation_rs::solver::table::CosetTable;
+//! let puzzle = models::cube2();
+//! let u = puzzle.move_named("U").unwrap().clone();
+//! let r = puzzle.move_named("R").unwrap().clone();
+//! let group = Group::with_base(puzzle.gset(), vec![], vec![u.clone(), r]);
+//! let subgroup = Group::with_base(puzzle.gset(), vec![], vec![u]);
+//! let moves = solver::moves(&puzzle, &["U", "U2", "U'", "R", "R2", "R'"]).unwrap();
+//!
+//! let table = CosetTable::build(group, subgroup, &moves);
+//!
+//! assert_eq!(table.len(), 29160 / 4);
+//! let state = puzzle.sequence("U R").unwrap().permutation(&puzzle).unwrap();
+//! assert_eq!(table.distance(&state), Some(1));
+//! ```
+
+use super::super::group::fingerprint::Fnv1a;
+use super::super::group::permutation::Permutation;
+use super::super::group::{Group, GroupElement};
+use super::super::puzzle::colored::State;
+use super::Move;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+#[cfg(feature = "memmap2")]
+use std::fs::File;
+use std::hash::Hasher;
+use std::io;
+use std::io::{Read, Write};
+use std::ops::Deref;
+
+/// The first bytes of the binary format.
+const MAGIC: &[u8; 8] = b"COSETTB1";
+
+/// The length of the magic number, the fingerprint and the number of cosets.
+const HEADER: usize = 24;
+
+/// The length of a label.
+const LABEL: usize = 16;
+
+/// The length of a distance.
+const DISTANCE: usize = 2;
+
+/// A number for every right coset of a subgroup.
+pub trait CosetLabel {
+    /// The label of the coset of `state`, or `None` when `state` is not a
+    /// member of the group.
+    fn label(&self, state: &Permutation) -> Option<u128>;
+
+    /// A hash that identifies this labeling. It is stored with a table, so it
+    /// should be the same in every run of every version of the program.
+    fn fingerprint(&self) -> u64;
+}
+
+/// The right cosets of a subgroup, labeled by the rank in the group of their
+/// canonical representative.
+pub struct SubgroupCosets {
+    group: Group<u64, Permutation>,
+    subgroup: Group<u64, Permutation>,
+}
+
+impl SubgroupCosets {
+    /// The right cosets of `subgroup` in `group`. `subgroup` should be a
+    /// subgroup of `group`.
+    pub fn new(
+        group: Group<u64, Permutation>,
+        subgroup: Group<u64, Permutation>,
+    ) -> SubgroupCosets {
+        SubgroupCosets { group, subgroup }
+    }
+}
+
+impl CosetLabel for SubgroupCosets {
+    fn label(&self, state: &Permutation) -> Option<u128> {
+        self.group
+            .rank(&self.subgroup.right_coset_representative(state))
+    }
+
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a::new();
+        hasher.write_u64(self.group.fingerprint());
+        hasher.write_u64(self.subgroup.fingerprint());
+        hasher.finish()
+    }
+}
+
+/// The right cosets of the subgroup that keeps a coloring, labeled by the
+/// coloring of their states read as a number, with a digit per position.
+pub struct ColoringCosets {
+    solved: State<u8>,
+    radix: u128,
+}
+
+impl ColoringCosets {
+    /// The cosets of the subgroup of the states that look like `solved`.
+    ///
+    /// # Panics
+    /// Panics when the colorings do not fit in a `u128`, i.e. when the number
+    /// of colors to the power of the number of positions exceeds it.
+    pub fn new(solved: State<u8>) -> ColoringCosets {
+        let radix = solved
+            .colors()
+            .iter()
+            .max()
+            .map(|color| u128::from(*color) + 1)
+            .unwrap_or(1);
+        assert!(
+            radix.checked_pow(solved.colors().len() as u32).is_some(),
+            "colorings should fit in a u128"
+        );
+        ColoringCosets { solved, radix }
+    }
+}
+
+impl CosetLabel for ColoringCosets {
+    fn label(&self, state: &Permutation) -> Option<u128> {
+        Some(
+            self.solved
+                .apply(state)
+                .colors()
+                .iter()
+                .fold(0, |label, color| label * self.radix + u128::from(*color)),
+        )
+    }
+
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a::new();
+        hasher.write_usize(self.solved.colors().len());
+        hasher.write(self.solved.colors());
+        hasher.finish()
+    }
+}
+
+/// The distance of every coset of a subgroup reachable from the subgroup.
+pub struct CosetTable<L> {
+    labels: L,
+    bytes: Bytes,
+}
+
+/// The binary format of a table, either in memory or mapped from a file.
+enum Bytes {
+    Owned(Vec<u8>),
+    #[cfg(feature = "memmap2")]
+    Mapped(memmap2::Mmap),
+}
+
+impl Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Bytes::Owned(bytes) => bytes,
+            #[cfg(feature = "memmap2")]
+            Bytes::Mapped(map) => map,
+        }
+    }
+}
+
+impl CosetTable<SubgroupCosets> {
+    /// Explore the right cosets of `subgroup` in `group` breadth first from
+    /// the subgroup itself, like `CosetTable::explore` does.
+    pub fn build(
+        group: Group<u64, Permutation>,
+        subgroup: Group<u64, Permutation>,
+        moves: &[Move],
+    ) -> CosetTable<SubgroupCosets> {
+        CosetTable::explore(SubgroupCosets::new(group, subgroup), moves)
+    }
+}
+
+impl<L> CosetTable<L>
+where
+    L: CosetLabel,
+{
+    /// Explore the cosets labeled by `labels` breadth first from the subgroup
+    /// itself. `moves` should be members of the group that contain the
+    /// inverse of every move.
+    ///
+    /// # Panics
+    /// Panics when a coset is more than `u16::MAX` moves away from the
+    /// subgroup.
+    pub fn explore(labels: L, moves: &[Move]) -> CosetTable<L> {
+        let mut distances: HashMap<u128, u16> = HashMap::new();
+        let mut to_visit: VecDeque<(Permutation, u16)> = VecDeque::new();
+        let start = Permutation::identity();
+        if let Some(label) = labels.label(&start) {
+            distances.insert(label, 0);
+            to_visit.push_back((start, 0));
+        }
+        while let Some((state, distance)) = to_visit.pop_front() {
+            let distance = distance
+                .checked_add(1)
+                .expect("distances should fit in a u16");
+            for turn in moves {
+                let next = state.times(&turn.permutation);
+                if let Some(label) = labels.label(&next) {
+                    if let Entry::Vacant(entry) = distances.entry(label) {
+                        entry.insert(distance);
+                        to_visit.push_back((next, distance));
+                    }
+                }
+            }
+        }
+
+        let mut entries: Vec<(u128, u16)> = distances.into_iter().collect();
+        entries.sort_unstable();
+        let mut bytes = header(&labels, entries.len());
+        for (label, _) in &entries {
+            bytes.extend_from_slice(&label.to_le_bytes());
+        }
+        for (_, distance) in &entries {
+            bytes.extend_from_slice(&distance.to_le_bytes());
+        }
+        CosetTable {
+            labels,
+            bytes: Bytes::Owned(bytes),
+        }
+    }
+
+    /// The labeling of the cosets of this table.
+    pub fn labels(&self) -> &L {
+        &self.labels
+    }
+
+    /// The number of cosets in this table.
+    pub fn len(&self) -> usize {
+        count(&self.bytes)
+    }
+
+    /// Determine if this table has no cosets. A built table contains at
+    /// least the subgroup itself, but a table that is read need not.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The largest distance in this table.
+    pub fn max_distance(&self) -> usize {
+        (0..self.len())
+            .map(|index| self.distance_at(index))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The index of the coset of `state`, or `None` when `state` is not a
+    /// member of the group or its coset can not be reached.
+    pub fn index(&self, state: &Permutation) -> Option<usize> {
+        let label = self.labels.label(state)?;
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let candidate = self.label_at(middle);
+            if candidate == label {
+                return Some(middle);
+            } else if candidate < label {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        None
+    }
+
+    /// The number of moves that bring `state` into the subgroup, or `None`
+    /// when its coset is not in this table.
+    pub fn distance(&self, state: &Permutation) -> Option<usize> {
+        self.index(state).map(|index| self.distance_at(index))
+    }
+
+    /// Write this table in its binary format.
+    pub fn write<W>(&self, mut writer: W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        writer.write_all(&self.bytes)
+    }
+
+    /// Read a table in its binary format, for the cosets labeled by `labels`.
+    /// Fails when the table was built with another labeling.
+    pub fn read<R>(labels: L, mut reader: R) -> io::Result<CosetTable<L>>
+    where
+        R: Read,
+    {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+        validate(&labels, &bytes)?;
+        Ok(CosetTable {
+            labels,
+            bytes: Bytes::Owned(bytes),
+        })
+    }
+
+    /// Map a file with a table in its binary format into memory, for the
+    /// cosets labeled by `labels`. Fails when the table was built with another
+    /// labeling.
+    ///
+    /// The file must not change while the table is in use.
+    #[cfg(feature = "memmap2")]
+    pub fn map(labels: L, file: &File) -> io::Result<CosetTable<L>> {
+        // Safety: the bytes are validated before use and the caller promises
+        // that the file does not change while it is mapped.
+        let map = unsafe { memmap2::Mmap::map(file)? };
+        validate(&labels, &map)?;
+        Ok(CosetTable {
+            labels,
+            bytes: Bytes::Mapped(map),
+        })
+    }
+
+    fn label_at(&self, index: usize) -> u128 {
+        let start = HEADER + index * LABEL;
+        let mut label = [0; LABEL];
+        label.copy_from_slice(&self.bytes[start..start + LABEL]);
+        u128::from_le_bytes(label)
+    }
+
+    fn distance_at(&self, index: usize) -> usize {
+        let start = HEADER + self.len() * LABEL + index * DISTANCE;
+        let mut distance = [0; DISTANCE];
+        distance.copy_from_slice(&self.bytes[start..start + DISTANCE]);
+        u16::from_le_bytes(distance) as usize
+    }
+}
+
+fn header<L>(labels: &L, count: usize) -> Vec<u8>
+where
+    L: CosetLabel,
+{
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend_from_slice(&labels.fingerprint().to_le_bytes());
+    bytes.extend_from_slice(&(count as u64).to_le_bytes());
+    bytes
+}
+
+/// The number of cosets in the header of `bytes`.
+fn count(bytes: &[u8]) -> usize {
+    let mut count = [0; 8];
+    count.copy_from_slice(&bytes[16..HEADER]);
+    u64::from_le_bytes(count) as usize
+}
+
+fn validate<L>(labels: &L, bytes: &[u8]) -> io::Result<()>
+where
+    L: CosetLabel,
+{
+    if bytes.len() < HEADER || &bytes[..8] != MAGIC {
+        return Err(invalid("not a coset table"));
+    }
+    if bytes[..16] != header(labels, 0)[..16] {
+        return Err(invalid("table was built with another labeling"));
+    }
+    let count = count(bytes);
+    if bytes.len() as u128 != HEADER as u128 + count as u128 * (LABEL + DISTANCE) as u128 {
+        return Err(invalid("length does not match the number of cosets"));
+    }
+    Ok(())
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::group::free::Word;
+    use super::super::super::puzzle::models;
+    use super::super::moves;
+    use super::*;
+
+    /// A cyclic group of order 600, generated by cycles of 8, 3 and 25 points,
+    /// with moves that turn it either way.
+    fn cyclic() -> (Group<u64, Permutation>, Vec<Move>) {
+        let mut images = HashMap::new();
+        for &(start, length) in &[(0u64, 8u64), (8, 3), (11, 25)] {
+            for offset in 0..length {
+                images.insert(start + offset, start + (offset + 1) % length);
+            }
+        }
+        let cycle = Permutation::new(images);
+        let moves = vec![("c", cycle.clone()), ("C", cycle.inverse())]
+            .into_iter()
+            .map(|(name, permutation)| Move {
+                word: Word::new(vec![(name.to_string(), 1)]),
+                permutation,
+            })
+            .collect();
+        (
+            Group::with_base((0..36).collect(), vec![], vec![cycle]),
+            moves,
+        )
+    }
+
+    fn trivial() -> Group<u64, Permutation> {
+        Group::with_base((0..36).collect(), vec![], vec![])
+    }
+
+    fn table() -> CosetTable<SubgroupCosets> {
+        let (group, moves) = cyclic();
+        CosetTable::build(group, trivial(), &moves)
+    }
+
+    #[test]
+    fn table_should_give_the_distance_to_the_subgroup() {
+        let puzzle = models::cube2();
+        let u = puzzle.move_named("U").unwrap().clone();
+        let r = puzzle.move_named("R").unwrap().clone();
+        let group = Group::with_base(puzzle.gset(), vec![], vec![u.clone(), r]);
+        let subgroup = Group::with_base(puzzle.gset(), vec![], vec![u]);
+        let moves = moves(&puzzle, &["U", "U2", "U'", "R", "R2", "R'"]).unwrap();
+        let state = |sequence: &str| {
+            puzzle
+                .sequence(sequence)
+                .unwrap()
+                .permutation(&puzzle)
+                .unwrap()
+        };
+
+        let table = CosetTable::build(group, subgroup, &moves);
+
+        assert_eq!(table.len(), 29160 / 4);
+        assert_eq!(table.distance(&state("U2")), Some(0));
+        assert_eq!(table.distance(&state("U R2")), Some(1));
+        assert_eq!(table.distance(&state("R U")), Some(2));
+        assert_eq!(table.index(&state("U R U")), table.index(&state("R U")));
+        assert_eq!(table.distance(&state("F")), None);
+    }
+
+    #[test]
+    fn distances_should_not_be_limited_to_a_byte() {
+        let table = table();
+
+        assert_eq!(table.len(), 600);
+        assert_eq!(table.max_distance(), 300);
+    }
+
+    #[test]
+    fn colorings_should_label_the_cosets_of_their_subgroup() {
+        let puzzle = models::cube2();
+        let solved = State::from_stickers(&puzzle, |sticker| u8::from(sticker.starts_with('U')));
+        let moves = moves(&puzzle, &["U", "R", "R'", "F", "F'"]).unwrap();
+
+        let table = CosetTable::explore(ColoringCosets::new(solved), &moves);
+
+        // The 4 corners of U among the 7 that move, each in one of 3 orientations.
+        assert_eq!(table.len(), 35 * 81);
+        let r = puzzle.move_named("R").unwrap();
+        let u = puzzle.move_named("U").unwrap();
+        assert_eq!(table.distance(u), Some(0));
+        assert_eq!(table.distance(&u.times(r)), Some(1));
+    }
+
+    #[test]
+    fn table_should_survive_writing_and_reading() {
+        let table = table();
+        let mut bytes: Vec<u8> = vec![];
+        table.write(&mut bytes).unwrap();
+        let (group, _) = cyclic();
+
+        let read = CosetTable::read(SubgroupCosets::new(group, trivial()), &bytes[..]).unwrap();
+
+        assert_eq!(&read.bytes[..], &table.bytes[..]);
+        assert_eq!(read.max_distance(), table.max_distance());
+    }
+
+    #[test]
+    fn reading_should_reject_tables_of_other_groups() {
+        let mut bytes: Vec<u8> = vec![];
+        table().write(&mut bytes).unwrap();
+        let (group, moves) = cyclic();
+        let other = Group::with_base(
+            (0..36).collect(),
+            vec![],
+            vec![moves[0].permutation.clone()],
+        );
+
+        let error = CosetTable::read(SubgroupCosets::new(group, other), &bytes[..])
+            .err()
+            .unwrap();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn a_table_without_cosets_should_be_empty() {
+        let (group, _) = cyclic();
+        let labels = SubgroupCosets::new(group, trivial());
+        let bytes = header(&labels, 0);
+
+        let read = CosetTable::read(labels, &bytes[..]).unwrap();
+
+        assert!(read.is_empty());
+        assert_eq!(read.distance(&Permutation::identity()), None);
+    }
+
+    #[cfg(feature = "memmap2")]
+    #[test]
+    fn table_should_be_mapped_from_a_file() {
+        let table = table();
+        let path =
+            std::env::temp_dir().join(format!("permutation-rs-coset-table-{}", std::process::id()));
+        table.write(File::create(&path).unwrap()).unwrap();
+        let (group, _) = cyclic();
+
+        let mapped = CosetTable::map(
+            SubgroupCosets::new(group, trivial()),
+            &File::open(&path).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(&mapped.bytes[..], &table.bytes[..]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
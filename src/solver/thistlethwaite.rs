@@ -0,0 +1,420 @@
+//! [Thistlethwaite's algorithm](https://www.jaapsch.net/puzzles/thistle.htm)
+//! for the 3x3x3 cube.
+//!
+//! The algorithm solves the cube through a chain of nested subgroups
+//!
+//! * _G0_ = ⟨U, D, L, R, F, B⟩, the whole cube group,
+//! * _G1_ = ⟨U, D, L, R, F2, B2⟩, where every edge is oriented,
+//! * _G2_ = ⟨U, D, L2, R2, F2, B2⟩, where also every corner is oriented and the
+//!   middle layer edges are in the middle layer,
+//! * _G3_ = ⟨U2, D2, L2, R2, F2, B2⟩, the squares group,
+//! * _G4_ = {e}.
+//!
+//! Each stage brings the state into the next subgroup, with the moves of the
+//! current one. The stages take at most 7, 10, 13 and 15 moves, so a solution
+//! has at most 45 moves.
+//!
+//! _G1_ and _G2_ are the states that keep a coloring of the stickers: the
+//! stickers that tell the orientation of an edge, or of a corner, get a color
+//! of their own. Such a coloring labels the cosets, see `ColoringCosets`, and
+//! a `CosetTable` gives the distance of every coset to the subgroup. These
+//! lookup tables guide the search of each stage. _G3_ is not the group of a
+//! coloring, so its stage decides membership with a stabilizer chain, guided by
+//! the coloring that only tells opposite faces apart. The last stage is guided
+//! by pattern databases of the corners and the edges.
+//!
+//! Building the tables takes most of the time of `cube3`. Build `Tables` once
+//! and share them with `cube3_with` when more than one pipeline is needed, or
+//! write them to a directory and read them back in a later run.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::puzzle::models;
+//! # use permutation_rs::solver::thistlethwaite;
+//! let puzzle = models::cube3();
+//! let state = puzzle.sequence("R U F' D").unwrap().permutation(&puzzle).unwrap();
+//! let pipeline = thistlethwaite::cube3();
+//!
+//! let solution = pipeline.solve(&state).unwrap();
+//!
+//! assert!(solution.word().syllable_count() <= 45);
+//! ```
+
+use super::super::group::permutation::Permutation;
+use super::super::group::Group;
+use super::super::puzzle::colored::State;
+use super::super::puzzle::{models, Puzzle};
+use super::pattern::{self, PatternDb, Projection};
+use super::pipeline::{Pipeline, Stage};
+use super::table::{ColoringCosets, CosetTable};
+use super::{face_turns, moves, Move};
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::path::Path;
+use std::rc::Rc;
+
+/// The stickers of the edges in the middle layer between `U` and `D` that are
+/// on the `F` or `B` face.
+const SLICE_FRONT_BACK: [&str; 4] = ["F4", "F5", "B4", "B5"];
+
+/// The Thistlethwaite pipeline for the 3x3x3 cube of `models::cube3`, with
+/// freshly built lookup tables.
+pub fn cube3() -> Pipeline {
+    cube3_with(Rc::new(Tables::build()))
+}
+
+/// The Thistlethwaite pipeline for the 3x3x3 cube of `models::cube3`, guided
+/// by `tables`.
+pub fn cube3_with(tables: Rc<Tables>) -> Pipeline {
+    let puzzle = models::cube3();
+    let g1_moves = g1_moves(&puzzle);
+    let g2_moves = g2_moves(&puzzle);
+    let g3_moves = g3_moves(&puzzle);
+    let squares = Group::with_base(
+        puzzle.gset(),
+        vec![],
+        g3_moves
+            .iter()
+            .map(|turn| turn.permutation.clone())
+            .collect(),
+    );
+    let edges = edge_orientation(&puzzle);
+    let g2 = g2_coloring(&puzzle);
+    let (g1_tables, g2_tables, g3_tables) = (tables.clone(), tables.clone(), tables.clone());
+
+    Pipeline::new()
+        .with_stage(
+            Stage::new("G1", face_turns(&puzzle), move |state| edges.apply(state))
+                .with_estimate(move |state| distance(&g1_tables.edges, state))
+                .with_max_depth(7),
+        )
+        .with_stage(
+            Stage::new("G2", g1_moves, move |state| g2.apply(state))
+                .with_estimate(move |state| {
+                    distance(&g2_tables.corners, state).max(distance(&g2_tables.slice, state))
+                })
+                .with_max_depth(10),
+        )
+        .with_stage(
+            Stage::into_group("G3", g2_moves, squares)
+                .with_estimate(move |state| distance(&g3_tables.faces, state))
+                .with_max_depth(13),
+        )
+        .with_stage(
+            Stage::new("G4", g3_moves, |state| state.clone())
+                .with_estimate(move |state| pattern::estimate(&tables.databases, state))
+                .with_max_depth(15),
+        )
+        .with_move_rules(puzzle.move_rules())
+}
+
+/// The lookup tables that guide the stages of the pipeline. Building them
+/// takes most of the time of `cube3`, so build them once and share them with
+/// every pipeline that needs them.
+pub struct Tables {
+    edges: CosetTable<ColoringCosets>,
+    corners: CosetTable<ColoringCosets>,
+    slice: CosetTable<ColoringCosets>,
+    faces: CosetTable<ColoringCosets>,
+    databases: Vec<PatternDb>,
+}
+
+/// The files in a directory of tables, in the order of the fields of `Tables`.
+const COSET_FILES: [&str; 4] = [
+    "edges.cosets",
+    "corners.cosets",
+    "slice.cosets",
+    "faces.cosets",
+];
+
+/// The files of the pattern databases in a directory of tables.
+const DATABASE_FILES: [&str; 2] = ["corners.pdb", "edges.pdb"];
+
+impl Tables {
+    /// Build the lookup tables by breadth first searches.
+    pub fn build() -> Tables {
+        let puzzle = models::cube3();
+        let g1_moves = g1_moves(&puzzle);
+        let g3_moves = g3_moves(&puzzle);
+        let [edges, corners, slice, faces] = labels(&puzzle);
+        let databases = projections(&puzzle)
+            .into_iter()
+            .map(|projection| PatternDb::build(projection, &g3_moves))
+            .collect();
+        Tables {
+            edges: CosetTable::explore(edges, &face_turns(&puzzle)),
+            corners: CosetTable::explore(corners, &g1_moves),
+            slice: CosetTable::explore(slice, &g1_moves),
+            faces: CosetTable::explore(faces, &g2_moves(&puzzle)),
+            databases,
+        }
+    }
+
+    /// Write the lookup tables to files in `directory`, which should exist.
+    pub fn write<P>(&self, directory: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let directory = directory.as_ref();
+        let tables = [&self.edges, &self.corners, &self.slice, &self.faces];
+        for (table, file) in tables.iter().zip(COSET_FILES.iter()) {
+            table.write(File::create(directory.join(file))?)?;
+        }
+        for (database, file) in self.databases.iter().zip(DATABASE_FILES.iter()) {
+            database.write(File::create(directory.join(file))?)?;
+        }
+        Ok(())
+    }
+
+    /// Read the lookup tables that `Tables::write` wrote to `directory`.
+    /// Fails when a table was built for other colorings.
+    pub fn read<P>(directory: P) -> io::Result<Tables>
+    where
+        P: AsRef<Path>,
+    {
+        let directory = directory.as_ref();
+        let puzzle = models::cube3();
+        let [edges, corners, slice, faces] = labels(&puzzle);
+        let read = |labels, file: &str| CosetTable::read(labels, File::open(directory.join(file))?);
+        let mut databases = vec![];
+        for file in DATABASE_FILES.iter() {
+            databases.push(PatternDb::read(BufReader::new(File::open(
+                directory.join(file),
+            )?))?);
+        }
+        Ok(Tables {
+            edges: read(edges, COSET_FILES[0])?,
+            corners: read(corners, COSET_FILES[1])?,
+            slice: read(slice, COSET_FILES[2])?,
+            faces: read(faces, COSET_FILES[3])?,
+            databases,
+        })
+    }
+}
+
+/// The labels of the cosets of the tables, in the order of the fields of
+/// `Tables`.
+fn labels(puzzle: &Puzzle) -> [ColoringCosets; 4] {
+    [
+        ColoringCosets::new(edge_orientation(puzzle)),
+        ColoringCosets::new(corner_orientation(puzzle)),
+        ColoringCosets::new(middle_layer(puzzle)),
+        ColoringCosets::new(opposite_faces(puzzle)),
+    ]
+}
+
+/// The projections of the pattern databases on the corners and on the edges.
+fn projections(puzzle: &Puzzle) -> Vec<Projection> {
+    [
+        vec!["U1", "U3", "U6", "U8", "D1", "D3", "D6", "D8"],
+        vec![
+            "U2", "U4", "U5", "U7", "D2", "D4", "D5", "D7", "F4", "F5", "B4", "B5",
+        ],
+    ]
+    .iter()
+    .map(|labels| {
+        let points = labels
+            .iter()
+            .map(|label| puzzle.sticker(label).expect("sticker exists"))
+            .collect();
+        Projection::new(points)
+    })
+    .collect()
+}
+
+/// The number of moves to bring the coloring of `state` back to the solved
+/// coloring of `table`, or 0 when it can not be reached.
+fn distance(table: &CosetTable<ColoringCosets>, state: &Permutation) -> usize {
+    table.distance(state).unwrap_or(0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Corner,
+    Edge,
+}
+
+/// The kind of piece a sticker of `models::cube3` belongs to. The stickers of
+/// a face are numbered in reading order without the center, so the stickers
+/// at the ends of the top and the bottom row are corners.
+fn kind(sticker: &str) -> Kind {
+    match &sticker[1..] {
+        "1" | "3" | "6" | "8" => Kind::Corner,
+        _ => Kind::Edge,
+    }
+}
+
+fn is_up_or_down(sticker: &str) -> bool {
+    sticker.starts_with('U') || sticker.starts_with('D')
+}
+
+fn is_middle_layer(sticker: &str) -> bool {
+    !is_up_or_down(sticker) && (sticker.ends_with('4') || sticker.ends_with('5'))
+}
+
+/// The edge stickers that stay in place under the moves of _G1_: the `U` and
+/// `D` stickers, and the `F` and `B` stickers of the middle layer.
+fn is_edge_orientation(sticker: &str) -> bool {
+    is_up_or_down(sticker) || SLICE_FRONT_BACK.contains(&sticker)
+}
+
+/// The coloring of _G1_: the edge stickers that tell the orientation of an
+/// edge apart from the other edge stickers.
+fn edge_orientation(puzzle: &Puzzle) -> State<u8> {
+    State::from_stickers(puzzle, |sticker| match kind(sticker) {
+        Kind::Edge if is_edge_orientation(sticker) => 1,
+        Kind::Edge => 0,
+        _ => 2,
+    })
+}
+
+/// The `U` and `D` stickers of the corners apart from the other corner
+/// stickers, which tells the orientation of the corners.
+fn corner_orientation(puzzle: &Puzzle) -> State<u8> {
+    State::from_stickers(puzzle, |sticker| match kind(sticker) {
+        Kind::Corner if is_up_or_down(sticker) => 1,
+        Kind::Corner => 0,
+        _ => 2,
+    })
+}
+
+/// The edges of the middle layer apart from the other edges.
+fn middle_layer(puzzle: &Puzzle) -> State<u8> {
+    State::from_stickers(puzzle, |sticker| match kind(sticker) {
+        Kind::Edge if is_middle_layer(sticker) => 1,
+        Kind::Edge => 0,
+        _ => 2,
+    })
+}
+
+/// The coloring of _G2_: the `U` and `D` stickers, and the `F` and `B`
+/// stickers of the middle layer.
+fn g2_coloring(puzzle: &Puzzle) -> State<u8> {
+    State::from_stickers(puzzle, |sticker| {
+        if SLICE_FRONT_BACK.contains(&sticker) {
+            2
+        } else if is_up_or_down(sticker) {
+            1
+        } else {
+            0
+        }
+    })
+}
+
+/// The coloring that only tells opposite faces apart.
+fn opposite_faces(puzzle: &Puzzle) -> State<u8> {
+    State::from_stickers(puzzle, |sticker| match &sticker[0..1] {
+        "U" | "D" => 0,
+        "L" | "R" => 1,
+        _ => 2,
+    })
+}
+
+/// The moves of _G1_.
+fn g1_moves(puzzle: &Puzzle) -> Vec<Move> {
+    stage_moves(puzzle, &["U", "D", "L", "R"], &["F", "B"])
+}
+
+/// The moves of _G2_.
+fn g2_moves(puzzle: &Puzzle) -> Vec<Move> {
+    stage_moves(puzzle, &["U", "D"], &["L", "R", "F", "B"])
+}
+
+/// The moves of _G3_.
+fn g3_moves(puzzle: &Puzzle) -> Vec<Move> {
+    stage_moves(puzzle, &[], &["U", "D", "L", "R", "F", "B"])
+}
+
+/// Every power of the moves in `quarter`, and the half turns of the moves in
+/// `half`.
+fn stage_moves(puzzle: &Puzzle, quarter: &[&str], half: &[&str]) -> Vec<Move> {
+    let mut names = vec![];
+    for name in quarter {
+        names.push(name.to_string());
+        names.push(format!("{}2", name));
+        names.push(format!("{}'", name));
+    }
+    for name in half {
+        names.push(format!("{}2", name));
+    }
+    let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+    moves(puzzle, &names).expect("cube moves should exist")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::group::GroupElement;
+    use super::*;
+
+    #[test]
+    fn tables_should_count_the_cosets() {
+        let puzzle = models::cube3();
+        let edges = edge_orientation(&puzzle);
+
+        let table = CosetTable::explore(ColoringCosets::new(edges.clone()), &face_turns(&puzzle));
+
+        assert_eq!(table.len(), 2048);
+        assert_eq!(table.max_distance(), 7);
+        assert!(g1_moves(&puzzle)
+            .iter()
+            .all(|turn| edges.apply(&turn.permutation) == edges));
+    }
+
+    #[test]
+    fn stages_should_keep_the_progress_of_earlier_stages() {
+        let pipeline = cube3();
+        let stages = pipeline.stages();
+
+        for (index, stage) in stages.iter().enumerate().skip(1) {
+            for turn in stage.moves() {
+                assert!(stages[index - 1].is_reached(&turn.permutation));
+            }
+        }
+    }
+
+    #[test]
+    fn tables_should_survive_writing_and_reading() {
+        let puzzle = models::cube3();
+        let state = puzzle
+            .sequence("R U F' D L2 B")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+        let tables = Tables::build();
+        let directory = std::env::temp_dir().join(format!(
+            "permutation-rs-thistlethwaite-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        tables.write(&directory).unwrap();
+        let read = Tables::read(&directory).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(read.edges.distance(&state), tables.edges.distance(&state));
+        assert_eq!(read.faces.distance(&state), tables.faces.distance(&state));
+        assert_eq!(
+            pattern::estimate(&read.databases, &state),
+            pattern::estimate(&tables.databases, &state)
+        );
+        assert!(cube3_with(Rc::new(read)).solve(&state).is_some());
+    }
+
+    #[test]
+    fn pipeline_should_solve_the_cube() {
+        let puzzle = models::cube3();
+        let state = puzzle
+            .sequence("R U F' D L2 B")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+
+        let solution = cube3().solve(&state).unwrap();
+
+        let images = puzzle.move_images();
+        assert!(state
+            .times(&solution.word().evaluate(&images))
+            .is_identity());
+    }
+}
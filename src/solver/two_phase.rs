@@ -0,0 +1,238 @@
+//! Two-phase solving, as popularized by Kociemba for the 3x3x3 cube.
+//!
+//! Solving is split along a subgroup _H_ of the puzzle group _G_. The first
+//! phase searches a short sequence that brings the state into _H_, the second
+//! phase searches a short sequence of moves of _H_ that solves it. Both
+//! searches are far smaller than a search in _G_ itself.
+//!
+//! Whether a state is in _H_ is told by a coset label: a function that takes
+//! the same value on every element of a coset _xH_. A state is in _H_ exactly
+//! when its label equals the label of the solved state.
+//!
+//! `cube3_g1` wires this up for the 3x3x3 cube and its subgroup
+//! _G1_ = ⟨U, D, R2, L2, F2, B2⟩. It only searches canonical sequences, where
+//! of the turns of opposite faces `U` comes before `D`, `L` before `R` and `F`
+//! before `B`.
+//!
+//! # Examples
+//! ```rust
+//! # use permutation_rs::puzzle::models;
+//! # use permutation_rs::solver::two_phase;
+//! let puzzle = models::cube3();
+//! let state = puzzle.sequence("R U F'").unwrap().permutation(&puzzle).unwrap();
+//! let solver = two_phase::cube3_g1();
+//!
+//! let solution = solver.solve(&state).unwrap();
+//!
+//! assert_eq!(solution.first.to_string(), "F^1U^-1R^1");
+//! assert_eq!(solution.second.to_string(), "R^2");
+//! ```
+
+use super::super::group::free::Word;
+use super::super::group::peephole::MoveRules;
+use super::super::group::permutation::Permutation;
+use super::super::group::{GroupAction, GroupElement};
+use super::super::puzzle::models;
+use super::pattern::PatternDb;
+use super::search::ida_star;
+use super::{face_turns, moves, word, Move, Phase};
+
+/// A two-phase solver with coset labels of type `L`.
+pub struct TwoPhase<L> {
+    moves: Vec<Move>,
+    subgroup_moves: Vec<Move>,
+    label: Box<dyn Fn(&Permutation) -> L>,
+    target: L,
+    rules: Option<MoveRules<String>>,
+    databases: Vec<PatternDb>,
+    max_depths: (usize, usize),
+}
+
+impl<L> TwoPhase<L>
+where
+    L: PartialEq,
+{
+    /// Create a two-phase solver. The first phase uses `moves`, the second phase
+    /// uses `subgroup_moves`, which should generate the subgroup _H_. `label`
+    /// should be constant on the cosets of _H_.
+    pub fn new<F>(moves: Vec<Move>, subgroup_moves: Vec<Move>, label: F) -> TwoPhase<L>
+    where
+        F: Fn(&Permutation) -> L + 'static,
+    {
+        let target = label(&Permutation::identity());
+        TwoPhase {
+            moves,
+            subgroup_moves,
+            label: Box::new(label),
+            target,
+            rules: None,
+            databases: vec![],
+            max_depths: (12, 18),
+        }
+    }
+
+    /// Limit the number of moves of each phase. Defaults to 12 for the first
+    /// and 18 for the second phase.
+    pub fn with_max_depths(mut self, first: usize, second: usize) -> TwoPhase<L> {
+        self.max_depths = (first, second);
+        self
+    }
+
+    /// Only search canonical sequences under `rules` in both phases, like
+    /// `Solver::with_move_rules`.
+    pub fn with_move_rules(mut self, rules: MoveRules<String>) -> TwoPhase<L> {
+        self.rules = Some(rules);
+        self
+    }
+
+    /// Guide the second phase with `databases`. They should be built with the
+    /// moves of the subgroup, otherwise their estimates are not admissible.
+    pub fn with_pattern_dbs(mut self, databases: Vec<PatternDb>) -> TwoPhase<L> {
+        self.databases = databases;
+        self
+    }
+
+    /// Determine if `state` is in the subgroup _H_.
+    pub fn in_subgroup(&self, state: &Permutation) -> bool {
+        (self.label)(state) == self.target
+    }
+
+    /// Find a solution for `state`. Returns `None` when one of the phases finds
+    /// no sequence within its maximum depth.
+    pub fn solve(&self, state: &Permutation) -> Option<Solution> {
+        let first = Phase {
+            moves: &self.moves,
+            costs: &vec![1; self.moves.len()],
+            rules: self.rules.as_ref(),
+            goal: &|state: &Permutation| self.in_subgroup(state),
+            databases: &[],
+            perimeter: None,
+            estimate: None,
+        };
+        let first_path = ida_star(&first, state, self.max_depths.0)?;
+        let first_word = word(&self.moves, &first_path);
+        let halfway = first_path.iter().fold(state.clone(), |current, index| {
+            current.times(&self.moves[*index].permutation)
+        });
+
+        let second = Phase {
+            moves: &self.subgroup_moves,
+            costs: &vec![1; self.subgroup_moves.len()],
+            rules: self.rules.as_ref(),
+            goal: &|state: &Permutation| state.is_identity(),
+            databases: &self.databases,
+            perimeter: None,
+            estimate: None,
+        };
+        let second_path = ida_star(&second, &halfway, self.max_depths.1)?;
+        let second_word = word(&self.subgroup_moves, &second_path);
+        Some(Solution {
+            first: first_word,
+            second: second_word,
+        })
+    }
+}
+
+/// The sequences found by the two phases of a `TwoPhase` solver.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    /// The sequence that brings the state into the subgroup.
+    pub first: Word<String>,
+    /// The sequence that solves the state within the subgroup.
+    pub second: Word<String>,
+}
+
+impl Solution {
+    /// The complete solution: the first phase followed by the second.
+    pub fn word(&self) -> Word<String> {
+        self.first.times(&self.second)
+    }
+}
+
+/// A two-phase solver for the 3x3x3 cube of `models::cube3`, with subgroup
+/// _G1_ = ⟨U, D, R2, L2, F2, B2⟩.
+///
+/// _G1_ consists of the states that keep every `U` and `D` sticker on the `U`
+/// or `D` face and every `F` and `B` sticker of a middle layer edge on the `F`
+/// or `B` face. The label of a state records for every sticker which of these
+/// kinds of positions it occupies.
+pub fn cube3_g1() -> TwoPhase<Vec<u8>> {
+    let puzzle = models::cube3();
+    let classes: Vec<u8> = puzzle
+        .stickers()
+        .iter()
+        .map(|sticker| g1_class(sticker))
+        .collect();
+    let subgroup_moves = moves(
+        &puzzle,
+        &["U", "U2", "U'", "D", "D2", "D'", "R2", "L2", "F2", "B2"],
+    )
+    .expect("G1 moves should exist");
+    TwoPhase::new(face_turns(&puzzle), subgroup_moves, move |state| {
+        (0..classes.len() as u64)
+            .map(|sticker| classes[state.act_on(&sticker) as usize])
+            .collect()
+    })
+    .with_move_rules(puzzle.move_rules())
+}
+
+fn g1_class(sticker: &str) -> u8 {
+    match sticker {
+        "F4" | "F5" | "B4" | "B5" => 2,
+        _ if sticker.starts_with('U') || sticker.starts_with('D') => 1,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn g1_moves_should_keep_the_label() {
+        let solver = cube3_g1();
+
+        for m in &solver.subgroup_moves {
+            assert!(solver.in_subgroup(&m.permutation));
+        }
+        let puzzle = models::cube3();
+        let r = puzzle.move_named("R").unwrap();
+        assert!(!solver.in_subgroup(r));
+    }
+
+    #[test]
+    fn solution_should_solve_the_cube() {
+        let puzzle = models::cube3();
+        let state = puzzle
+            .sequence("R U F'")
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+        let solver = cube3_g1();
+
+        let solution = solver.solve(&state).unwrap();
+
+        let first = puzzle
+            .sequence(&moves_of(&solution.first))
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+        assert!(solver.in_subgroup(&state.times(&first)));
+        let all = puzzle
+            .sequence(&moves_of(&solution.word()))
+            .unwrap()
+            .permutation(&puzzle)
+            .unwrap();
+        assert!(state.times(&all).is_identity());
+    }
+
+    fn moves_of(word: &Word<String>) -> String {
+        word.iter()
+            .map(|(name, exponent)| match *exponent {
+                e if e < 0 => format!("{}{}'", name, -e),
+                e => format!("{}{}", name, e),
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
@@ -0,0 +1,224 @@
+//! Bindings for JavaScript, with the `wasm` feature.
+//!
+//! A `PuzzleGroup` bundles a puzzle with its group, so a browser can check and
+//! solve states without dealing with stabilizer chains. It is created from a
+//! puzzle definition, see `puzzle`, or from generators as JSON: an array with,
+//! for every generator, the array of images of the points `0..n`.
+//!
+//! A state lists for every position the index of the sticker that occupies it,
+//! like the `--state` option of the `solve` binary.
+//!
+//! ```js
+//! import init, { build_group } from "./pkg/permutation_rs.js";
+//!
+//! await init();
+//! const group = build_group("[[1, 2, 0], [1, 0, 2]]");
+//! console.log(group.order());              // "6"
+//! console.log(group.solve([2, 0, 1]));     // e.g. "a"
+//! ```
+//!
+//! The functions that can fail return a `Result` with a message, which
+//! JavaScript sees as a thrown exception.
+
+use super::group::permutation::Permutation;
+use super::group::special::SLPPermutation;
+use super::group::tree::SLP;
+use super::group::Group;
+use super::puzzle::Puzzle;
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+/// A puzzle and the group generated by its moves.
+#[wasm_bindgen]
+pub struct PuzzleGroup {
+    puzzle: Puzzle,
+    group: Group<u64, SLPPermutation>,
+}
+
+/// Create a `PuzzleGroup` from a puzzle definition.
+#[wasm_bindgen]
+pub fn load_puzzle(definition: &str) -> Result<PuzzleGroup, JsValue> {
+    PuzzleGroup::from_definition(definition).map_err(|message| JsValue::from_str(&message))
+}
+
+/// Create a `PuzzleGroup` from generators as JSON. The generators are named
+/// `a`, `b`, and so on, continuing with `aa` after `z`.
+#[wasm_bindgen]
+pub fn build_group(generators_json: &str) -> Result<PuzzleGroup, JsValue> {
+    PuzzleGroup::from_generators(generators_json).map_err(|message| JsValue::from_str(&message))
+}
+
+#[wasm_bindgen]
+impl PuzzleGroup {
+    /// The order of the group, as a decimal string because it can exceed the
+    /// safe integers of JavaScript.
+    pub fn order(&self) -> String {
+        self.group.big_order().to_string()
+    }
+
+    /// Determine if `state` can be reached with the moves of the puzzle.
+    pub fn is_member(&self, state: Vec<u32>) -> bool {
+        match self.state(&state) {
+            Ok(permutation) => self
+                .group
+                .contains(&SLPPermutation::new(SLP::Identity, permutation)),
+            Err(_) => false,
+        }
+    }
+
+    /// A sequence of moves that solves `state`, in the notation of the puzzle.
+    pub fn solve(&self, state: Vec<u32>) -> Result<String, JsValue> {
+        self.solve_state(&state)
+            .map_err(|message| JsValue::from_str(&message))
+    }
+}
+
+impl PuzzleGroup {
+    fn from_definition(definition: &str) -> Result<PuzzleGroup, String> {
+        let puzzle: Puzzle = definition.parse().map_err(|error| format!("{}", error))?;
+        Ok(PuzzleGroup::from_puzzle(puzzle))
+    }
+
+    fn from_generators(generators_json: &str) -> Result<PuzzleGroup, String> {
+        let generators = parse_generators(generators_json)?;
+        let degree = generators
+            .iter()
+            .map(|images| images.len())
+            .max()
+            .unwrap_or(0);
+        let mut puzzle = Puzzle::new((0..degree).map(|point| point.to_string()).collect());
+        for (index, images) in generators.iter().enumerate() {
+            let images: HashMap<u64, u64> = images
+                .iter()
+                .enumerate()
+                .map(|(point, image)| (point as u64, *image))
+                .collect();
+            let permutation = Permutation::try_new(images).map_err(|error| error.to_string())?;
+            let cycles: String = permutation
+                .cycles()
+                .iter()
+                .map(|cycle| {
+                    let points: Vec<String> = cycle.iter().map(|p| p.to_string()).collect();
+                    format!("({})", points.join(" "))
+                })
+                .collect();
+            puzzle
+                .add_move(&generator_name(index), &cycles)
+                .map_err(|error| error.to_string())?;
+        }
+        Ok(PuzzleGroup::from_puzzle(puzzle))
+    }
+
+    fn from_puzzle(puzzle: Puzzle) -> PuzzleGroup {
+        let group = puzzle.group();
+        PuzzleGroup { puzzle, group }
+    }
+
+    fn state(&self, state: &[u32]) -> Result<Permutation, String> {
+        if state.len() != self.puzzle.stickers().len() {
+            return Err(format!(
+                "state should have {} stickers",
+                self.puzzle.stickers().len()
+            ));
+        }
+        let images: HashMap<u64, u64> = state
+            .iter()
+            .enumerate()
+            .map(|(position, sticker)| (*sticker as u64, position as u64))
+            .collect();
+        Permutation::try_new(images).map_err(|error| error.to_string())
+    }
+
+    fn solve_state(&self, state: &[u32]) -> Result<String, String> {
+        let state = self.state(state)?;
+        let verified = self
+            .group
+            .solve_verified(&state, &self.puzzle.morphism(), &self.puzzle.move_images())
+            .map_err(|error| error.to_string())?;
+        Ok(verified.word.format_with(&self.puzzle.notation()))
+    }
+}
+
+/// A name of letters only, because digits denote powers in move notation.
+fn generator_name(mut index: usize) -> String {
+    let mut name = vec![];
+    loop {
+        name.push((b'a' + (index % 26) as u8) as char);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    name.iter().rev().collect()
+}
+
+/// Parse a JSON array of arrays of non negative integers.
+fn parse_generators(json: &str) -> Result<Vec<Vec<u64>>, String> {
+    let malformed = || String::from("expected an array of arrays of points");
+    let inner = json
+        .trim()
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .ok_or_else(malformed)?
+        .trim();
+    let mut generators = vec![];
+    let mut rest = inner;
+    while !rest.is_empty() {
+        let start = rest.strip_prefix('[').ok_or_else(malformed)?;
+        let end = start.find(']').ok_or_else(malformed)?;
+        let images = start[..end]
+            .split(',')
+            .map(|point| point.trim())
+            .filter(|point| !point.is_empty())
+            .map(|point| point.parse::<u64>().map_err(|_| malformed()))
+            .collect::<Result<Vec<u64>, String>>()?;
+        generators.push(images);
+        rest = start[(end + 1)..].trim_start();
+        if let Some(next) = rest.strip_prefix(',') {
+            rest = next.trim_start();
+        } else if !rest.is_empty() {
+            return Err(malformed());
+        }
+    }
+    Ok(generators)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::group::GroupElement;
+    use super::*;
+
+    #[test]
+    fn generators_should_parse_from_json() {
+        assert_eq!(
+            parse_generators(" [[1, 2, 0], [1,0,2]] "),
+            Ok(vec![vec![1, 2, 0], vec![1, 0, 2]])
+        );
+        assert_eq!(parse_generators("[]"), Ok(vec![]));
+        assert!(parse_generators("[[1, 2], x]").is_err());
+    }
+
+    #[test]
+    fn generator_names_should_only_use_letters() {
+        assert_eq!(generator_name(0), "a");
+        assert_eq!(generator_name(25), "z");
+        assert_eq!(generator_name(26), "aa");
+        assert_eq!(generator_name(27), "ab");
+    }
+
+    #[test]
+    fn group_should_solve_states() {
+        let group = PuzzleGroup::from_generators("[[1, 2, 0], [1, 0, 2]]").unwrap();
+
+        assert_eq!(group.order(), "6");
+        assert!(group.is_member(vec![2, 0, 1]));
+        assert!(!group.is_member(vec![0, 0, 1]));
+        let moves = group.solve_state(&[2, 0, 1]).unwrap();
+        let solution = group.puzzle.sequence(&moves).unwrap();
+        assert!(group
+            .state(&[2, 0, 1])
+            .unwrap()
+            .times(&solution.permutation(&group.puzzle).unwrap())
+            .is_identity());
+    }
+}
@@ -0,0 +1,59 @@
+#[macro_use]
+extern crate permutation_rs;
+
+use permutation_rs::group::permutation::Permutation;
+use permutation_rs::group::special::SLPPermutation;
+use permutation_rs::group::tree::SLP;
+use permutation_rs::group::{Group, GroupElement};
+use std::collections::HashMap;
+
+#[test]
+fn change_base_should_preserve_witnesses() {
+    let group = s4().change_base(vec![3u64, 2u64]);
+
+    let permutation = permute!(0, 2, 1, 3, 2, 1, 3, 0);
+    let element = SLPPermutation::new(SLP::Identity, permutation.clone());
+
+    let stripped = group.strip(element);
+
+    assert!(stripped.element.1.is_identity());
+    assert_eq!(
+        stripped.element.0.evaluate_with(&images()),
+        permutation.inverse()
+    );
+}
+
+#[test]
+fn stabilizer_should_preserve_witnesses() {
+    let stabilizer = s4().stabilizer(0u64);
+
+    let permutation = permute!(0, 0, 1, 3, 2, 1, 3, 2);
+    let element = SLPPermutation::new(SLP::Identity, permutation.clone());
+
+    let stripped = stabilizer.strip(element);
+
+    assert_eq!(stabilizer.order(), 6);
+    assert!(stripped.element.1.is_identity());
+    assert_eq!(
+        stripped.element.0.evaluate_with(&images()),
+        permutation.inverse()
+    );
+}
+
+fn images() -> HashMap<u64, Permutation> {
+    let mut images = HashMap::new();
+    images.insert(0u64, permute!(0, 1, 1, 0, 2, 2, 3, 3));
+    images.insert(1u64, permute!(0, 1, 1, 2, 2, 3, 3, 0));
+    images
+}
+
+fn s4() -> Group<u64, SLPPermutation> {
+    let transposition = SLPPermutation::new(SLP::Generator(0), permute!(0, 1, 1, 0, 2, 2, 3, 3));
+
+    let rotation = SLPPermutation::new(SLP::Generator(1), permute!(0, 1, 1, 2, 2, 3, 3, 0));
+
+    let gset = vec![0u64, 1u64, 2u64, 3u64];
+    let generators = vec![transposition, rotation];
+
+    Group::new(gset, generators)
+}
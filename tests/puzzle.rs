@@ -0,0 +1,51 @@
+extern crate permutation_rs;
+
+use permutation_rs::group::special::SLPPermutation;
+use permutation_rs::group::tree::SLP;
+use permutation_rs::group::{Group, GroupElement};
+use permutation_rs::puzzle::{models, Puzzle};
+use std::env;
+use std::fs;
+
+#[test]
+fn load_should_read_a_puzzle_definition() {
+    let path = env::temp_dir().join("permutation-rs-brainbow.puzzle");
+    fs::write(
+        &path,
+        "# The brainbow\nstickers: a b c d e f\nmove T: (d f)\nmove R: (a b c d e f)\n",
+    )
+    .unwrap();
+
+    let puzzle = Puzzle::load(&path).unwrap();
+    let group = puzzle.group();
+
+    let scramble = puzzle
+        .move_named("R")
+        .unwrap()
+        .times(puzzle.move_named("T").unwrap());
+    let stripped = group.strip(SLPPermutation::new(SLP::Identity, scramble));
+
+    assert_eq!(group.order(), 72);
+    assert!(stripped.element.1.is_identity());
+    assert!(!stripped.transform(&puzzle.morphism()).is_identity());
+}
+
+#[test]
+#[ignore = "builds large stabilizer chains, run with --release -- --ignored"]
+fn generated_puzzles_should_have_the_known_orders() {
+    let order =
+        |puzzle: Puzzle| Group::with_base(puzzle.gset(), vec![], puzzle.generators()).big_order();
+
+    assert_eq!(
+        order(models::cube(4)).to_string(),
+        "16972688908618238933770849245964147960401887232000000000"
+    );
+    assert_eq!(
+        order(models::megaminx()).to_string(),
+        "100669616553523347122516032313645505168688116411019768627200000000000"
+    );
+    assert_eq!(
+        order(models::kilominx()).to_string(),
+        "1413834128545313800765440000"
+    );
+}
@@ -2,9 +2,10 @@
 extern crate permutation_rs as pr;
 
 use pr::group::permutation::Permutation;
-use pr::group::tree::SLPFactory;
-use pr::group::GroupElement;
+use pr::group::tree::{SLPFactory, SyncSLPFactory};
+use pr::group::{Group, GroupElement};
 use std::collections::HashMap;
+use std::thread;
 
 #[test]
 fn should_correctly_evaluate_to_elements() {
@@ -23,3 +24,31 @@ fn should_correctly_evaluate_to_elements() {
 
     assert_eq!(g, expected);
 }
+
+#[test]
+fn sync_words_should_be_usable_across_threads() {
+    let factory = SyncSLPFactory::new();
+    let u = factory.generator(permute!(0, 1, 1, 2, 2, 3, 3, 0));
+    let v = factory.generator(permute!(0, 1, 1, 0, 2, 2, 3, 3));
+
+    let handle = {
+        let (u, v) = (u.clone(), v.clone());
+        thread::spawn(move || u.times(&v).inverse())
+    };
+    let expression = handle.join().unwrap();
+
+    assert_eq!(expression.evaluate(), permute!(0, 0, 1, 3, 2, 1, 3, 2));
+}
+
+#[test]
+fn sync_words_should_generate_a_group() {
+    let factory = SyncSLPFactory::new();
+    let generators = vec![
+        factory.generator(permute!(0, 1, 1, 2, 2, 3, 3, 0)),
+        factory.generator(permute!(0, 1, 1, 0, 2, 2, 3, 3)),
+    ];
+
+    let group = Group::with_base(vec![0, 1, 2, 3], vec![], generators);
+
+    assert_eq!(group.order(), 24);
+}
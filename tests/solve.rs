@@ -0,0 +1,47 @@
+extern crate permutation_rs;
+
+use permutation_rs::group::GroupElement;
+use permutation_rs::puzzle::Puzzle;
+use std::env;
+use std::fs;
+use std::process::Command;
+
+const BRAINBOW: &str = "stickers: a b c d e f\nmove T: (d f)\nmove R: (a b c d e f)\n";
+
+#[test]
+fn solve_should_print_a_solving_sequence() {
+    let path = env::temp_dir().join("permutation-rs-solve-brainbow.puzzle");
+    fs::write(&path, BRAINBOW).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_solve"))
+        .arg(&path)
+        .args(["--scramble", "R T R"])
+        .arg("--reduce")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let solution = String::from_utf8(output.stdout).unwrap();
+    let puzzle: Puzzle = BRAINBOW.parse().unwrap();
+    let scramble = puzzle.sequence("R T R").unwrap();
+    let solution = puzzle.sequence(&solution).unwrap();
+    let state = scramble
+        .permutation(&puzzle)
+        .unwrap()
+        .times(&solution.permutation(&puzzle).unwrap());
+    assert!(state.is_identity());
+}
+
+#[test]
+fn solve_should_reject_unsolvable_states() {
+    let path = env::temp_dir().join("permutation-rs-solve-brainbow-state.puzzle");
+    fs::write(&path, BRAINBOW).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_solve"))
+        .arg(&path)
+        .args(["--state", "b a c d e f"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+}
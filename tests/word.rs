@@ -48,6 +48,57 @@ fn check_returned_word() {
     );
 }
 
+#[test]
+fn check_returned_word_with_move_names() {
+    let group = s6();
+
+    let element = SLPPermutation::new(SLP::Identity, permute!(0, 1, 1, 0, 2, 2, 3, 3, 4, 4, 5, 5));
+
+    let stripped = group.strip(element);
+
+    let morphism = morphism!(0, String::from("T"), 1, String::from("R"));
+
+    let word = stripped.transform(&morphism).inverse();
+
+    let mut images = HashMap::new();
+    images.insert(
+        String::from("T"),
+        permute!(0, 1, 1, 0, 2, 2, 3, 3, 4, 4, 5, 5),
+    );
+    images.insert(
+        String::from("R"),
+        permute!(0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 0),
+    );
+
+    assert!(stripped.element.1.is_identity());
+    assert_eq!(
+        word.evaluate(&images),
+        permute!(0, 1, 1, 0, 2, 2, 3, 3, 4, 4, 5, 5)
+    );
+    assert!(word
+        .iter()
+        .all(|(symbol, _)| symbol == "T" || symbol == "R"));
+}
+
+#[test]
+fn check_returned_slp_evaluates_to_inverse() {
+    let group = s6();
+
+    let permutation = permute!(0, 1, 1, 0, 2, 5, 3, 4, 4, 3, 5, 2);
+    let element = SLPPermutation::new(SLP::Identity, permutation.clone());
+
+    let stripped = group.strip(element);
+
+    let mut images = HashMap::new();
+    images.insert(0u64, permute!(0, 1, 1, 0, 2, 2, 3, 3, 4, 4, 5, 5));
+    images.insert(1u64, permute!(0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 0));
+
+    assert_eq!(
+        stripped.element.0.evaluate_with(&images),
+        permutation.inverse()
+    );
+}
+
 fn s6() -> Group<u64, SLPPermutation> {
     let transposition = SLPPermutation::new(
         SLP::Generator(0),